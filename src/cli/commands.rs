@@ -5,6 +5,24 @@ use clap::{Parser, Subcommand};
 #[command(about = "A CLI tool for explicit environment variable management")]
 #[command(version)]
 pub struct Cli {
+    /// Suppress informational messages (errors are still printed)
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Print traces of config loading to stderr (-v files and decryption, -vv also
+    /// inheritance and interpolation); give it before the subcommand
+    #[arg(short, long, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Environment to use when a command's environment argument is omitted; for
+    /// `init`, an environment to create instead of dev/prod (repeatable)
+    #[arg(long = "env", global = true, value_name = "ENVIRONMENT")]
+    pub env: Vec<String>,
+
+    /// Disable colored output (also disabled when NO_COLOR is set or TERM=dumb)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -22,14 +40,23 @@ pub enum Commands {
     },
     /// Start a subshell with the specified environment
     Shell {
-        /// Environment name to activate
-        environment: String,
+        /// Environment name to activate (see `--env` and `settings.environment_from`)
+        environment: Option<String>,
         /// Skip confirmation prompt for environments that require it
         #[arg(short, long)]
         yes: bool,
-        /// Shell to use (defaults to $SHELL)
+        /// Shell to use (defaults to `settings.shell`, then $SHELL)
         #[arg(long)]
         shell: Option<String>,
+        /// Run this command in the shell and exit instead of starting an interactive session
+        #[arg(short, long, value_name = "CMD")]
+        command: Option<String>,
+        /// Layer this dotenv file over the configured variables (repeatable, later files win)
+        #[arg(long = "env-file", value_name = "PATH")]
+        env_files: Vec<std::path::PathBuf>,
+        /// Export encrypted values as stored instead of decrypting them
+        #[arg(long)]
+        no_decrypt: bool,
     },
     /// Execute a command with the specified environment
     Exec {
@@ -38,64 +65,333 @@ pub enum Commands {
         /// Skip confirmation prompt for environments that require it
         #[arg(short, long)]
         yes: bool,
+        /// Run the command with a cleared environment (only Stand variables and PATH, HOME, etc.)
+        #[arg(long)]
+        isolated: bool,
+        /// Read extra dotenv variables from stdin (they override configured values)
+        #[arg(long)]
+        stdin_env: bool,
+        /// Run the command in this directory
+        #[arg(long, value_name = "DIR")]
+        cwd: Option<std::path::PathBuf>,
+        /// Run the command in the project root (the directory containing .stand.toml)
+        #[arg(long, conflicts_with = "cwd")]
+        chdir_to_root: bool,
+        /// Kill the command after this many seconds and exit with status 124
+        #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
+        /// Capture stdout and stderr and print them with the exit code as a JSON object
+        #[arg(long)]
+        capture: bool,
+        /// Write the resolved variables as dotenv to FILE (`-` for stderr) before running
+        #[arg(long, value_name = "FILE")]
+        print_env_to: Option<std::path::PathBuf>,
+        /// Pass encrypted values to the command as stored instead of decrypting them
+        #[arg(long)]
+        no_decrypt: bool,
+        /// Run the command through `$SHELL -c` so pipes, globs and `&&` work
+        #[arg(long)]
+        shell: bool,
+        /// Re-run the command up to N more times while it exits non-zero or times out
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 0,
+            conflicts_with = "capture"
+        )]
+        retry: u32,
+        /// Seconds to wait between attempts
+        #[arg(long, value_name = "SECONDS", default_value_t = 1, requires = "retry")]
+        retry_delay: u64,
+        /// Only pass variables written in the environment's own section (no [common] or extends)
+        #[arg(long)]
+        no_inherit: bool,
+        /// Fail if a resolved value still contains `${` (e.g. a typo like `${VAR` or an escaped `$${VAR}`)
+        #[arg(long)]
+        strict: bool,
         /// Command to execute
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         command: Vec<String>,
     },
+    /// Run a command with an environment and restart it when the configuration changes
+    Watch {
+        /// Environment name to use
+        environment: String,
+        /// Skip confirmation prompt for environments that require it
+        #[arg(short, long)]
+        yes: bool,
+        /// Run the command with a cleared environment (only Stand variables and PATH, HOME, etc.)
+        #[arg(long)]
+        isolated: bool,
+        /// Run the command through `$SHELL -c` so pipes, globs and `&&` work
+        #[arg(long)]
+        shell: bool,
+        /// Command to run; defaults to the environment's `default_command`
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    /// Set the active environment for this project
+    Switch {
+        /// Environment name to activate
+        environment: String,
+        /// Skip confirmation prompt for environments that require it
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Show recent environment switches
+    History {
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value_t = crate::commands::history::DEFAULT_HISTORY_LIMIT)]
+        limit: usize,
+    },
     /// List all available environments
-    List,
+    List {
+        /// Show environments as a tree of their `extends` relationships
+        #[arg(long)]
+        tree: bool,
+        /// Only show environments whose name or description contains PATTERN (case-insensitive)
+        #[arg(long, value_name = "PATTERN", conflicts_with = "tree")]
+        filter: Option<String>,
+        /// Also list environments marked `hidden = true`
+        #[arg(long)]
+        all: bool,
+    },
+    /// Find Stand projects in subdirectories and list their environments
+    Projects {
+        /// How many directory levels below the current one to search
+        #[arg(long, default_value_t = crate::commands::projects::DEFAULT_MAX_DEPTH)]
+        max_depth: usize,
+    },
+    /// List the system variables referenced with `${VAR}` in the configuration
+    Deps {
+        /// Only list the references of this environment (including inherited ones)
+        environment: Option<String>,
+    },
     /// Inspect environment variables defined for an environment
     Inspect {
-        /// Environment name
-        environment: String,
+        /// Environment name (see `--env` and `settings.environment_from`)
+        environment: Option<String>,
         /// Show actual values (default: show names only)
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "mask")]
         values: bool,
+        /// Show values masked, revealing `settings.mask_visible_chars` leading characters
+        #[arg(long)]
+        mask: bool,
+        /// Keep `${VAR}` references to unset system variables instead of failing, and list them
+        #[arg(long)]
+        resolve_system: bool,
+        /// Next to each value, show ENV's value where it differs (requires --values)
+        #[arg(long, value_name = "ENV", requires = "values")]
+        values_from: Option<String>,
+        /// Only show variables written in the environment's own section (no [common] or extends)
+        #[arg(long)]
+        no_inherit: bool,
     },
     /// Set a variable in the configuration file
     Set {
-        /// Environment name
+        /// Environment name (with --common, the variable name)
         environment: String,
-        /// Variable name
-        key: String,
+        /// Variable name (with --common, the value)
+        #[arg(required_unless_present = "common")]
+        key: Option<String>,
         /// Variable value (if omitted with --encrypt, prompts for input)
+        #[arg(conflicts_with_all = ["from_stdin", "common"])]
         value: Option<String>,
+        /// Set the variable in [common] instead: stand set --common <KEY> [VALUE]
+        #[arg(long)]
+        common: bool,
         /// Encrypt the value before storing
         #[arg(short, long)]
         encrypt: bool,
+        /// Read the value from stdin until EOF, keeping newlines
+        #[arg(long)]
+        from_stdin: bool,
+        /// Add the value after the current one (PATH-style), creating the variable if absent
+        #[arg(long, conflicts_with = "prepend")]
+        append: bool,
+        /// Add the value before the current one (PATH-style), creating the variable if absent
+        #[arg(long)]
+        prepend: bool,
+        /// Separator for --append/--prepend (default: `:`, `;` on Windows)
+        #[arg(long, value_name = "SEP")]
+        separator: Option<String>,
     },
-    /// Get a variable value from the configuration
-    Get {
+    /// Remove a variable (or every variable) from an environment
+    Unset {
         /// Environment name
         environment: String,
         /// Variable name
-        key: String,
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        key: Option<String>,
+        /// Remove every variable, keeping description, color and other properties
+        #[arg(long)]
+        all: bool,
+        /// Skip the confirmation prompt for --all
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Rename an environment and update the references to it
+    Rename {
+        /// Current environment name
+        old: String,
+        /// New environment name
+        new: String,
+    },
+    /// Create a new environment from an existing one
+    Copy {
+        /// Environment to copy
+        src: String,
+        /// Name of the new environment
+        dst: String,
+        /// Make the new environment extend the source instead of copying its variables
+        #[arg(long)]
+        extend: bool,
+    },
+    /// Import variables from a .env file into an environment
+    Import {
+        /// Environment name
+        environment: String,
+        /// Path to the .env file
+        file: std::path::PathBuf,
+        /// Encrypt each value before storing
+        #[arg(short, long)]
+        encrypt: bool,
+        /// Replace variables that already exist in the environment
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// Write a resolved, decrypted environment to a .env file
+    Export {
+        /// Environment name
+        environment: String,
+        /// Output file path
+        file: std::path::PathBuf,
+        /// Overwrite the output file if it exists
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Get a variable value from the configuration
+    #[command(override_usage = "stand get [OPTIONS] [ENVIRONMENT] <KEY>")]
+    Get {
+        /// Environment name (omit to use `--env`, the active or the default environment)
+        #[arg(value_name = "ENVIRONMENT", required_unless_present = "all")]
+        environment: Option<String>,
+        /// Variable name
+        #[arg(conflicts_with = "all")]
+        key: Option<String>,
+        /// Ignore `[common]` and `extends`; only read the environment's own variables
+        #[arg(long)]
+        raw: bool,
+        /// Print every variable as KEY=VALUE instead of a single value
+        #[arg(long)]
+        all: bool,
+        /// Mask values printed by `--all`, revealing `settings.mask_visible_chars` leading characters
+        #[arg(long, requires = "all")]
+        mask: bool,
     },
     /// Manage encryption settings
     #[command(subcommand)]
     Encrypt(EncryptCommands),
+    /// Show and share encryption keys
+    #[command(subcommand)]
+    Keys(KeysCommands),
+    /// Show which configuration is in effect
+    #[command(subcommand)]
+    Config(ConfigCommands),
     /// Validate the configuration
-    Validate,
+    Validate {
+        /// Also resolve every environment and print a pass/fail summary
+        #[arg(long)]
+        all: bool,
+        /// Print every error and warning as a JSON array
+        #[arg(long, conflicts_with = "all")]
+        json: bool,
+    },
+    /// Check that an environment fully resolves (interpolation, inheritance, decryption)
+    Check {
+        /// Environment name to check
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        environment: Option<String>,
+        /// Check every environment and print a pass/fail summary
+        #[arg(long)]
+        all: bool,
+    },
+    /// Diagnose common setup problems (configuration, keys, shell)
+    Doctor,
     /// Show the current active environment
-    Current,
+    Current {
+        /// Print the project root and configuration file in effect instead
+        #[arg(long)]
+        print_path: bool,
+    },
     /// Show environment variables in the current Stand subshell
     Env {
-        /// Output in JSON format
-        #[arg(long)]
+        /// Output format
+        #[arg(long, value_enum, conflicts_with_all = ["json", "export"])]
+        format: Option<crate::commands::env::EnvFormat>,
+        /// Output in JSON format (same as `--format json`)
+        #[arg(long, conflicts_with = "export")]
         json: bool,
+        /// Output `export KEY='value'` lines for use with `eval` (same as `--format export`)
+        #[arg(long)]
+        export: bool,
         /// Show only Stand marker variables (STAND_*)
         #[arg(long, conflicts_with = "user_only")]
         stand_only: bool,
         /// Show only user-defined variables
         #[arg(long, conflicts_with = "stand_only")]
         user_only: bool,
+        /// Annotate each user variable as added, overridden or unchanged
+        /// compared with the current environment
+        #[arg(long, conflicts_with_all = ["format", "json", "export", "stand_only"])]
+        diff_system: bool,
+    },
+    /// Print environment names matching a prefix (used by shell completion scripts)
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// The partial word being completed
+        #[arg(default_value = "")]
+        current: String,
     },
 }
 
 #[derive(Subcommand, Debug)]
 pub enum EncryptCommands {
     /// Enable encryption for this project (generates key pair)
-    Enable,
+    Enable {
+        /// Also encrypt to the age public keys in FILE (one per line, `#` comments allowed)
+        #[arg(long, value_name = "FILE")]
+        recipients_file: Option<std::path::PathBuf>,
+    },
     /// Disable encryption and decrypt all values
     Disable,
 }
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Print the absolute path of the project's .stand.toml
+    Path,
+    /// Print the effective configuration (includes, overlay and inheritance applied,
+    /// values interpolated, encrypted values masked) as TOML
+    Show,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum KeysCommands {
+    /// Print the project's public key and its fingerprint
+    Show,
+    /// Print the private key so it can be moved to another machine
+    Export {
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Write .stand.keys from an exported private key
+    Import {
+        /// File containing the key (reads stdin when omitted or `-`)
+        file: Option<std::path::PathBuf>,
+        /// Replace an existing .stand.keys
+        #[arg(short, long)]
+        force: bool,
+    },
+}
@@ -0,0 +1,300 @@
+// check.rs command implementation
+//
+// Verify that an environment fully resolves: inheritance, interpolation and decryption.
+
+use crate::config::loader::{
+    apply_dotenv_cascade, apply_variable_inheritance, interpolate_string_with,
+    load_config_toml_uninterpolated, PseudoVariables,
+};
+use crate::config::types::Configuration;
+use crate::config::ConfigError;
+use crate::crypto::{decrypt_value, is_encrypted, keys, load_private_key_for_decryption};
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A single problem found while checking an environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckIssue {
+    /// Variable the problem relates to, if any
+    pub variable: Option<String>,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+/// Every problem found while resolving one environment
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+    /// Name of the checked environment
+    pub env_name: String,
+    /// Number of variables after merging common and inherited values
+    pub variable_count: usize,
+    /// Problems found, in the order they were detected
+    pub issues: Vec<CheckIssue>,
+}
+
+impl CheckReport {
+    /// Returns true if the environment resolved without problems
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Format the report for display
+    pub fn format(&self) -> String {
+        if self.is_ok() {
+            return format!(
+                "✓ Environment '{}' resolves ({} variables)\n",
+                self.env_name, self.variable_count
+            );
+        }
+
+        let noun = if self.issues.len() == 1 {
+            "problem"
+        } else {
+            "problems"
+        };
+        let mut output = format!(
+            "✗ Environment '{}' has {} {}:\n",
+            self.env_name,
+            self.issues.len(),
+            noun
+        );
+        for issue in &self.issues {
+            match &issue.variable {
+                Some(variable) => {
+                    output.push_str(&format!("  - {}: {}\n", variable, issue.message))
+                }
+                None => output.push_str(&format!("  - {}\n", issue.message)),
+            }
+        }
+        output
+    }
+}
+
+/// Check that an environment fully resolves
+///
+/// Reads the same files and runs the same steps as loading the environment for
+/// `shell`/`exec` (local overlay, inheritance, dotenv cascade, `${VAR}` interpolation,
+/// decryption) but collects every problem into the report instead of stopping at
+/// the first one.
+///
+/// # Errors
+/// Returns an error only if the configuration cannot be read or the environment
+/// does not exist; resolution problems are reported in the [`CheckReport`].
+pub fn check_environment(project_dir: &Path, env_name: &str) -> Result<CheckReport> {
+    let config = load_config_toml_uninterpolated(project_dir)?;
+    check_configured_environment(&config, project_dir, env_name)
+}
+
+/// Check every environment in the configuration, sorted by name
+pub fn check_all_environments(project_dir: &Path) -> Result<Vec<CheckReport>> {
+    let config = load_config_toml_uninterpolated(project_dir)?;
+
+    let mut env_names: Vec<_> = config.environments.keys().collect();
+    env_names.sort();
+
+    env_names
+        .into_iter()
+        .map(|name| check_configured_environment(&config, project_dir, name))
+        .collect()
+}
+
+/// Format a one-line-per-environment pass/fail summary
+pub fn format_summary(reports: &[CheckReport]) -> String {
+    let width = reports.iter().map(|r| r.env_name.len()).max().unwrap_or(0);
+
+    let mut output = String::new();
+    for report in reports {
+        let status = match report.issues.len() {
+            0 => "OK".to_string(),
+            1 => "1 error".to_string(),
+            n => format!("{} errors", n),
+        };
+        output.push_str(&format!(
+            "  {:width$}  {}\n",
+            format!("{}:", report.env_name),
+            status,
+            width = width + 1
+        ));
+    }
+    output
+}
+
+/// Check an environment of a loaded but uninterpolated configuration
+///
+/// `config` is what [`load_config_toml_uninterpolated`] returns. Variables are only
+/// resolved when the environment's inheritance chain is sound; a missing parent or
+/// a cycle is reported on its own.
+pub fn check_configured_environment(
+    config: &Configuration,
+    project_dir: &Path,
+    env_name: &str,
+) -> Result<CheckReport> {
+    if !config.environments.contains_key(env_name) {
+        let mut available: Vec<_> = config.environments.keys().cloned().collect();
+        available.sort();
+        return Err(anyhow!(
+            "Environment '{}' not found. Available: {}",
+            env_name,
+            available.join(", ")
+        ));
+    }
+
+    let mut issues = Vec::new();
+    let chain = inheritance_chain(config, env_name, &mut issues);
+    if !issues.is_empty() {
+        return Ok(CheckReport {
+            env_name: env_name.to_string(),
+            variable_count: 0,
+            issues,
+        });
+    }
+
+    let variables = match resolve_variables(config, project_dir, env_name, &chain) {
+        Ok(variables) => variables,
+        Err(e) => {
+            issues.push(CheckIssue {
+                variable: None,
+                message: e.to_string(),
+            });
+            BTreeMap::new()
+        }
+    };
+
+    // A pseudo-variable override that fails to interpolate is reported below like any value
+    let pseudo = PseudoVariables::new(project_dir, Some(env_name));
+    let pseudo = pseudo
+        .clone()
+        .with_overrides(|name| variables.get(name))
+        .unwrap_or(pseudo);
+    for (key, value) in &variables {
+        if let Err(e) = interpolate_string_with(value, Some(&pseudo)) {
+            let message = match e {
+                ConfigError::InterpolationError { variable } => {
+                    format!("references undefined variable '{}'", variable)
+                }
+                other => other.to_string(),
+            };
+            issues.push(CheckIssue {
+                variable: Some(key.clone()),
+                message,
+            });
+        }
+    }
+
+    check_decryption(&variables, project_dir, &mut issues);
+
+    Ok(CheckReport {
+        env_name: env_name.to_string(),
+        variable_count: variables.len(),
+        issues,
+    })
+}
+
+/// Merge `[common]`, the ancestors in `chain` and the dotenv cascade into the
+/// environment's variables, exactly as the loader does, without interpolating them
+fn resolve_variables(
+    config: &Configuration,
+    project_dir: &Path,
+    env_name: &str,
+    chain: &[String],
+) -> Result<BTreeMap<String, String>, ConfigError> {
+    // Other environments may be broken; only this environment's chain has to resolve
+    let mut resolved = config.clone();
+    resolved.environments.retain(|name, _| chain.contains(name));
+    apply_variable_inheritance(&mut resolved)?;
+    resolved.environments.retain(|name, _| name == env_name);
+    apply_dotenv_cascade(&mut resolved, project_dir)?;
+
+    Ok(resolved
+        .environments
+        .remove(env_name)
+        .map(|env| env.variables.into_iter().collect())
+        .unwrap_or_default())
+}
+
+/// Collect the environment and its existing ancestors, reporting missing parents and cycles
+///
+/// The result is in precedence order: the environment first, then its parents from
+/// last to first (each followed by its own ancestors). Shared ancestors appear once.
+fn inheritance_chain(
+    config: &Configuration,
+    env_name: &str,
+    issues: &mut Vec<CheckIssue>,
+) -> Vec<String> {
+    let mut chain = Vec::new();
+    collect_ancestors(config, env_name, &mut Vec::new(), &mut chain, issues);
+    chain
+}
+
+fn collect_ancestors(
+    config: &Configuration,
+    current: &str,
+    path: &mut Vec<String>,
+    chain: &mut Vec<String>,
+    issues: &mut Vec<CheckIssue>,
+) {
+    path.push(current.to_string());
+    chain.push(current.to_string());
+
+    for parent in config.environments[current].parents().iter().rev() {
+        if path.contains(parent) {
+            let mut cycle = path.clone();
+            cycle.push(parent.to_string());
+            issues.push(CheckIssue {
+                variable: None,
+                message: format!("circular inheritance: {}", cycle.join(" -> ")),
+            });
+            continue;
+        }
+        if !config.environments.contains_key(parent) {
+            issues.push(CheckIssue {
+                variable: None,
+                message: format!(
+                    "environment '{}' extends '{}' which does not exist",
+                    current, parent
+                ),
+            });
+            continue;
+        }
+        if !chain.contains(parent) {
+            collect_ancestors(config, parent, path, chain, issues);
+        }
+    }
+
+    path.pop();
+}
+
+/// Try to decrypt every encrypted value, reporting the ones that fail
+fn check_decryption(
+    variables: &BTreeMap<String, String>,
+    project_dir: &Path,
+    issues: &mut Vec<CheckIssue>,
+) {
+    let encrypted: Vec<_> = variables.iter().filter(|(_, v)| is_encrypted(v)).collect();
+    if encrypted.is_empty() {
+        return;
+    }
+
+    let identity = match load_private_key_for_decryption(project_dir)
+        .and_then(|key| keys::parse_private_key(&key))
+    {
+        Ok(identity) => identity,
+        Err(e) => {
+            issues.push(CheckIssue {
+                variable: None,
+                message: format!("encrypted values cannot be decrypted: {}", e),
+            });
+            return;
+        }
+    };
+
+    for (key, value) in encrypted {
+        if let Err(e) = decrypt_value(value, &identity) {
+            issues.push(CheckIssue {
+                variable: Some(key.clone()),
+                message: format!("cannot be decrypted: {}", e),
+            });
+        }
+    }
+}
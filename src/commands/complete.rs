@@ -0,0 +1,76 @@
+use crate::config::loader;
+use crate::utils::paths::find_project_root_from;
+use std::path::Path;
+
+/// Returns environment names starting with `current`, sorted alphabetically.
+///
+/// Used by shell completion scripts via the hidden `stand __complete` command.
+/// A missing or invalid configuration yields no candidates instead of an error,
+/// so that pressing TAB outside a Stand project never prints noise.
+pub fn complete_environments(project_path: &Path, current: &str) -> Vec<String> {
+    let Ok(project_root) = find_project_root_from(project_path) else {
+        return Vec::new();
+    };
+    let Ok(config) = loader::load_config_toml(&project_root) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = config
+        .environments
+        .into_keys()
+        .filter(|name| name.starts_with(current))
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_config(dir: &Path) {
+        let config = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+
+[environments.demo]
+description = "Demo"
+
+[environments.prod]
+description = "Production"
+"#;
+        fs::write(dir.join(".stand.toml"), config).unwrap();
+    }
+
+    #[test]
+    fn test_complete_returns_matching_prefix() {
+        let dir = TempDir::new().unwrap();
+        write_config(dir.path());
+
+        assert_eq!(complete_environments(dir.path(), "de"), vec!["demo", "dev"]);
+        assert_eq!(complete_environments(dir.path(), "p"), vec!["prod"]);
+        assert!(complete_environments(dir.path(), "x").is_empty());
+    }
+
+    #[test]
+    fn test_complete_empty_prefix_returns_all() {
+        let dir = TempDir::new().unwrap();
+        write_config(dir.path());
+
+        assert_eq!(
+            complete_environments(dir.path(), ""),
+            vec!["demo", "dev", "prod"]
+        );
+    }
+
+    #[test]
+    fn test_complete_without_config_returns_nothing() {
+        let dir = TempDir::new().unwrap();
+
+        assert!(complete_environments(dir.path(), "d").is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! Configuration introspection commands.
+//!
+//! Shows which configuration file is in effect and what it resolves to.
+
+use crate::config::loader;
+use crate::crypto::is_encrypted;
+use crate::utils::paths::find_project_root_from;
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Shown instead of encrypted values
+const ENCRYPTED_PLACEHOLDER: &str = "[ENCRYPTED]";
+
+/// Absolute path of the configuration file used from `start_dir`
+///
+/// The project root is searched upwards from `start_dir`; its `.stand.toml` is
+/// returned, or `.stand.yaml` for projects that only have the legacy format.
+pub fn config_path(start_dir: &Path) -> Result<PathBuf> {
+    let project_root = find_project_root_from(start_dir)?;
+    let project_root = project_root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", project_root.display()))?;
+
+    let path = loader::config_file(&project_root);
+    if !path.is_file() {
+        return Err(anyhow!(
+            "No .stand.toml found in {}",
+            project_root.display()
+        ));
+    }
+    Ok(path)
+}
+
+/// The effective configuration of the project containing `start_dir`, as TOML
+///
+/// Includes, `.stand.local.toml`, `[common]`, `extends` and cascaded dotenv files
+/// are merged in and `${VAR}` references are interpolated, so each environment
+/// lists exactly the variables `exec` would set. Encrypted values are shown as
+/// `[ENCRYPTED]`. Keys are sorted.
+pub fn show_config(start_dir: &Path) -> Result<String> {
+    let project_root = find_project_root_from(start_dir)?;
+    let mut config = loader::load_config_toml_with_inheritance(&project_root)?;
+
+    let common = config
+        .common
+        .iter_mut()
+        .flat_map(|common| common.values_mut());
+    let variables = config
+        .environments
+        .values_mut()
+        .flat_map(|env| env.variables.values_mut());
+    for value in common.chain(variables) {
+        if is_encrypted(value) {
+            *value = ENCRYPTED_PLACEHOLDER.to_string();
+        }
+    }
+
+    // Going through toml::Value sorts the keys, since HashMap order is arbitrary
+    let value = toml::Value::try_from(&config)
+        .map_err(|e| anyhow!("Failed to serialize the configuration: {}", e))?;
+    toml::to_string(&value).map_err(|e| anyhow!("Failed to serialize the configuration: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"
+version = "2.0"
+
+[common]
+APP_NAME = "demo"
+
+[environments.dev]
+description = "Development"
+API_URL = "http://localhost:3000"
+API_KEY = "encrypted:c2VjcmV0"
+
+[environments.prod]
+description = "Production"
+extends = "dev"
+API_URL = "https://api.example.com"
+"#;
+
+    #[test]
+    fn test_config_path_finds_project_root_from_subdirectory() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        let subdir = dir.path().join("src/app");
+        fs::create_dir_all(&subdir).unwrap();
+
+        let path = config_path(&subdir).unwrap();
+
+        assert!(path.is_absolute());
+        assert_eq!(path, dir.path().canonicalize().unwrap().join(".stand.toml"));
+    }
+
+    #[test]
+    fn test_config_path_without_project_is_an_error() {
+        let dir = tempdir().unwrap();
+
+        assert!(config_path(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_show_config_reflects_inheritance_and_masks_secrets() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let output = show_config(dir.path()).unwrap();
+        let shown: toml::Table = output.parse().unwrap();
+
+        let prod = shown["environments"]["prod"].as_table().unwrap();
+        assert_eq!(prod["API_URL"].as_str(), Some("https://api.example.com"));
+        // Inherited from dev and [common]
+        assert_eq!(prod["APP_NAME"].as_str(), Some("demo"));
+        assert_eq!(prod["API_KEY"].as_str(), Some("[ENCRYPTED]"));
+        assert!(!output.contains("c2VjcmV0"));
+    }
+}
@@ -0,0 +1,34 @@
+//! Global command-line options shared by command handlers.
+
+/// How much informational output a command prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only errors, warnings and requested data (`--quiet`)
+    Quiet,
+    /// Success messages and banners as well
+    #[default]
+    Normal,
+}
+
+/// Options from the top-level `Cli` that every command handler honours.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CommandContext {
+    pub verbosity: Verbosity,
+}
+
+impl CommandContext {
+    /// Build a context from the global `--quiet` flag.
+    pub fn new(quiet: bool) -> Self {
+        let verbosity = if quiet {
+            Verbosity::Quiet
+        } else {
+            Verbosity::Normal
+        };
+        Self { verbosity }
+    }
+
+    /// Returns true if informational messages should be suppressed.
+    pub fn is_quiet(&self) -> bool {
+        self.verbosity == Verbosity::Quiet
+    }
+}
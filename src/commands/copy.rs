@@ -0,0 +1,204 @@
+//! Copy command implementation.
+//!
+//! Creates a new environment from an existing one.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::utils::write_atomic;
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::commands::rename::is_valid_environment_name;
+use crate::config::{loader, ConfigError};
+
+/// Properties carried over to the new environment in `extend` mode.
+const EXTEND_KEYS: &[&str] = &["description", "color", "requires_confirmation"];
+
+/// Create `[environments.<dst>]` from `[environments.<src>]`.
+///
+/// By default the source table is duplicated as written: its own variables and
+/// properties, including its `extends`. With `extend`, the new environment gets
+/// `extends = "<src>"` and only the source's `description`, `color` and
+/// `requires_confirmation`, so it picks up later changes to the source. `<dst>` may
+/// not be an existing alias.
+pub fn copy_environment(
+    project_dir: &Path,
+    src: &str,
+    dst: &str,
+    extend: bool,
+) -> Result<(), CopyCommandError> {
+    if !is_valid_environment_name(dst) {
+        return Err(CopyCommandError::InvalidName(dst.to_string()));
+    }
+
+    let config = loader::load_config_toml(project_dir)?;
+    if !config.environments.contains_key(src) {
+        return Err(CopyCommandError::EnvironmentNotFound(src.to_string()));
+    }
+    if config.environments.contains_key(dst) {
+        return Err(CopyCommandError::AlreadyExists(dst.to_string()));
+    }
+    if config
+        .settings
+        .aliases
+        .as_ref()
+        .is_some_and(|aliases| aliases.contains_key(dst))
+    {
+        return Err(CopyCommandError::AliasExists(dst.to_string()));
+    }
+
+    let config_path = project_dir.join(".stand.toml");
+    let content = fs::read_to_string(&config_path)?;
+    let mut doc: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| CopyCommandError::TomlParse(e.to_string()))?;
+
+    let environments = doc
+        .get_mut("environments")
+        .and_then(Item::as_table_mut)
+        .ok_or_else(|| CopyCommandError::EnvironmentNotFound(src.to_string()))?;
+    let source = environments
+        .get(src)
+        .and_then(Item::as_table)
+        .ok_or_else(|| CopyCommandError::EnvironmentNotFound(src.to_string()))?;
+
+    // Build a fresh table so it is appended at the end of the file
+    let mut table = Table::new();
+    for (key, item) in source.iter() {
+        if !extend || EXTEND_KEYS.contains(&key) {
+            table.insert(key, item.clone());
+        }
+    }
+    if extend {
+        table.insert("extends", toml_edit::value(src));
+    }
+    environments.insert(dst, Item::Table(table));
+
+    write_atomic(&config_path, doc.to_string())?;
+    Ok(())
+}
+
+/// Error type for copy command.
+#[derive(Debug, thiserror::Error)]
+pub enum CopyCommandError {
+    #[error("Environment not found: {0}")]
+    EnvironmentNotFound(String),
+
+    #[error("Environment already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error(
+        "'{0}' is already an alias in settings.aliases; pick another name or remove the alias"
+    )]
+    AliasExists(String),
+
+    #[error("Invalid environment name '{0}': use letters, digits, '-' or '_'")]
+    InvalidName(String),
+
+    #[error("TOML parsing error: {0}")]
+    TomlParse(String),
+
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"version = "2.0"
+
+[environments.base]
+description = "Base"
+LOG_LEVEL = "info"
+
+[environments.prod]
+description = "Production"
+extends = "base"
+color = "red"
+requires_confirmation = true
+API_URL = "https://api.example.com"
+"#;
+
+    #[test]
+    fn test_copy_duplicates_environment() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        copy_environment(dir.path(), "prod", "prod-eu", false).unwrap();
+
+        let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+        let copy = &config.environments["prod-eu"];
+        assert_eq!(copy.description, "Production");
+        assert_eq!(copy.parents(), ["base"]);
+        assert_eq!(copy.color.as_deref(), Some("red"));
+        assert_eq!(copy.requires_confirmation, Some(true));
+        assert_eq!(copy.variables, config.environments["prod"].variables);
+
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert!(content.starts_with(CONFIG));
+    }
+
+    #[test]
+    fn test_copy_with_extend_references_source() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        copy_environment(dir.path(), "prod", "prod-eu", true).unwrap();
+
+        let raw = loader::load_config_toml(dir.path()).unwrap();
+        let copy = &raw.environments["prod-eu"];
+        assert_eq!(copy.parents(), ["prod"]);
+        assert_eq!(copy.color.as_deref(), Some("red"));
+        assert_eq!(copy.requires_confirmation, Some(true));
+        assert!(copy.variables.is_empty());
+
+        let resolved = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+        let vars = &resolved.environments["prod-eu"].variables;
+        assert_eq!(vars["API_URL"], "https://api.example.com");
+        assert_eq!(vars["LOG_LEVEL"], "info");
+    }
+
+    #[test]
+    fn test_copy_errors() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        assert!(matches!(
+            copy_environment(dir.path(), "prod", "base", false),
+            Err(CopyCommandError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            copy_environment(dir.path(), "staging", "qa", true),
+            Err(CopyCommandError::EnvironmentNotFound(_))
+        ));
+        assert!(matches!(
+            copy_environment(dir.path(), "prod", "prod eu", false),
+            Err(CopyCommandError::InvalidName(_))
+        ));
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert_eq!(content, CONFIG);
+    }
+
+    #[test]
+    fn test_copy_rejects_alias_as_destination() {
+        let dir = tempdir().unwrap();
+        let config = CONFIG.replace(
+            "version = \"2.0\"\n",
+            "version = \"2.0\"\n\n[settings]\naliases = { production = \"prod\" }\n",
+        );
+        fs::write(dir.path().join(".stand.toml"), &config).unwrap();
+
+        assert!(matches!(
+            copy_environment(dir.path(), "base", "production", false),
+            Err(CopyCommandError::AliasExists(_))
+        ));
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert_eq!(content, config);
+    }
+}
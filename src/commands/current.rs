@@ -1,6 +1,9 @@
+use crate::shell::detector::get_active_project_root;
 use crate::state::persistence::load_state;
 use crate::utils::colors::colorize_environment;
-use anyhow::Result;
+use crate::utils::paths::find_project_root_from;
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
 
 /// Show the current active environment
 pub fn handle_current() -> Result<()> {
@@ -15,7 +18,7 @@ pub fn handle_current() -> Result<()> {
                 }
                 None => {
                     println!("No environment is currently active");
-                    println!("Use 'stand shell <environment>' to activate an environment");
+                    println!("Use 'stand switch <environment>' to activate an environment");
                 }
             }
             Ok(())
@@ -27,9 +30,38 @@ pub fn handle_current() -> Result<()> {
     }
 }
 
+/// Describe the project root and configuration file in effect for `current_dir`
+///
+/// The root is discovered from `current_dir` like every other command, falling back
+/// to `STAND_PROJECT_ROOT` when running inside a Stand shell. Returns an error when
+/// neither yields a project.
+pub fn format_project_path(current_dir: &Path) -> Result<String> {
+    let root = find_project_root_from(current_dir)
+        .ok()
+        .or_else(|| get_active_project_root().map(PathBuf::from))
+        .ok_or_else(|| {
+            anyhow!(
+                "No .stand.toml found in {} or any parent directory. \
+                 Run 'stand init' to create one.",
+                current_dir.display()
+            )
+        })?;
+    let root = root.canonicalize().unwrap_or(root);
+
+    Ok(format!(
+        "Project root: {}\nConfig file:  {}\n",
+        root.display(),
+        root.join(".stand.toml").display()
+    ))
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::state::types::State;
+    use serial_test::serial;
+    use std::fs;
+    use tempfile::tempdir;
 
     #[test]
     fn test_current_logic() {
@@ -50,4 +82,37 @@ mod tests {
         state.clear_current_environment();
         assert_eq!(state.get_current_environment(), None);
     }
+
+    #[test]
+    #[serial]
+    fn test_format_project_path_from_subdirectory() {
+        std::env::remove_var("STAND_PROJECT_ROOT");
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), "version = \"2.0\"\n").unwrap();
+        let sub = dir.path().join("src");
+        fs::create_dir(&sub).unwrap();
+
+        let output = format_project_path(&sub).unwrap();
+
+        let root = dir.path().canonicalize().unwrap();
+        assert_eq!(
+            output,
+            format!(
+                "Project root: {}\nConfig file:  {}\n",
+                root.display(),
+                root.join(".stand.toml").display()
+            )
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_format_project_path_without_project() {
+        std::env::remove_var("STAND_PROJECT_ROOT");
+        let dir = tempdir().unwrap();
+
+        let result = format_project_path(dir.path());
+
+        assert!(result.unwrap_err().to_string().contains("stand init"));
+    }
 }
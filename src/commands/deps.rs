@@ -0,0 +1,148 @@
+// deps.rs command implementation
+//
+// List the system environment variables a configuration needs, e.g. to set up CI.
+
+use crate::config::loader;
+use anyhow::Result;
+use std::env;
+use std::path::Path;
+
+/// List the variables referenced with `${VAR}` by `env_name` (or by the whole configuration)
+///
+/// System variables that are not set in the current process are marked `(not set)`.
+pub fn list_dependencies(project_path: &Path, env_name: Option<&str>) -> Result<String> {
+    let config = loader::load_config_toml_uninterpolated(project_path)?;
+    let dependencies = config.list_dependencies(env_name)?;
+
+    if dependencies.system.is_empty() && dependencies.internal.is_empty() {
+        return Ok("No variable references found\n".to_string());
+    }
+
+    let mut output = String::new();
+    if !dependencies.system.is_empty() {
+        output.push_str("System variables:\n");
+        for name in &dependencies.system {
+            let marker = if env::var_os(name).is_some() {
+                ""
+            } else {
+                " (not set)"
+            };
+            output.push_str(&format!("  {}{}\n", name, marker));
+        }
+    }
+    if !dependencies.internal.is_empty() {
+        output.push_str("Provided by Stand:\n");
+        for name in &dependencies.internal {
+            output.push_str(&format!("  {}\n", name));
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"version = "2.0"
+
+[common]
+APP_NAME = "stand"
+LOG_DIR = "${STAND_PROJECT_ROOT}/log"
+
+[environments.base]
+description = "Base"
+DB_HOST = "${STAND_DEPS_DB_HOST}"
+
+[environments.dev]
+description = "Development for ${STAND_DEPS_USER}"
+extends = "base"
+DB_URL = "postgres://${STAND_DEPS_DB_HOST}/${STAND_ENV}"
+BANNER = "${APP_NAME} costs $$5, literal $${NOT_A_REFERENCE}"
+
+[environments.prod]
+description = "Production"
+API_KEY = "${STAND_DEPS_PROD_API_KEY}"
+"#;
+
+    fn names(set: &std::collections::BTreeSet<String>) -> Vec<&str> {
+        set.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_list_dependencies_partitions_references() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        let config = loader::load_config_toml_uninterpolated(dir.path()).unwrap();
+
+        let all = config.list_dependencies(None).unwrap();
+        assert_eq!(names(&all.internal), ["STAND_ENV", "STAND_PROJECT_ROOT"]);
+        // APP_NAME is a configuration variable, but interpolation reads it from the system
+        assert_eq!(
+            names(&all.system),
+            [
+                "APP_NAME",
+                "STAND_DEPS_DB_HOST",
+                "STAND_DEPS_PROD_API_KEY",
+                "STAND_DEPS_USER"
+            ]
+        );
+
+        // An environment sees its own, inherited and common references only
+        let prod = config.list_dependencies(Some("prod")).unwrap();
+        assert_eq!(names(&prod.internal), ["STAND_PROJECT_ROOT"]);
+        assert_eq!(names(&prod.system), ["STAND_DEPS_PROD_API_KEY"]);
+        let dev = config.list_dependencies(Some("dev")).unwrap();
+        assert_eq!(
+            names(&dev.system),
+            ["APP_NAME", "STAND_DEPS_DB_HOST", "STAND_DEPS_USER"]
+        );
+
+        assert!(config.list_dependencies(Some("missing")).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_list_dependencies_output_marks_unset_variables() {
+        env::set_var("STAND_DEPS_DB_HOST", "localhost");
+        env::remove_var("STAND_DEPS_PROD_API_KEY");
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let output = list_dependencies(dir.path(), Some("prod")).unwrap();
+        assert_eq!(
+            output,
+            "System variables:\n  STAND_DEPS_PROD_API_KEY (not set)\nProvided by Stand:\n  STAND_PROJECT_ROOT\n"
+        );
+        let output = list_dependencies(dir.path(), Some("base")).unwrap();
+        env::remove_var("STAND_DEPS_DB_HOST");
+        assert!(output.starts_with("System variables:\n  STAND_DEPS_DB_HOST\n"));
+
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\nA = \"1\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            list_dependencies(dir.path(), None).unwrap(),
+            "No variable references found\n"
+        );
+    }
+
+    #[test]
+    fn test_list_dependencies_rejects_malformed_placeholder() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\nA = \"${UNCLOSED\"\n",
+        )
+        .unwrap();
+
+        let err = list_dependencies(dir.path(), None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unterminated variable placeholder"));
+    }
+}
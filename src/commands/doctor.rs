@@ -0,0 +1,410 @@
+// doctor.rs command implementation
+//
+// Diagnose common setup problems: configuration, keys and shell detection.
+
+use crate::config::loader;
+use crate::crypto::{is_encrypted, keys, load_private_key_for_decryption};
+use crate::shell::detector::{
+    detect_user_shell, get_active_environment, is_stand_shell_active, ShellType,
+};
+use crate::utils::colors::{success_mark, warning_mark};
+use crate::utils::paths::find_project_root_from;
+use std::fs;
+use std::path::Path;
+
+/// Outcome of a single doctor check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoctorStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Result of one check, with a hint on how to fix it when it did not pass
+#[derive(Debug, Clone)]
+pub struct DoctorCheck {
+    /// Short name of what was checked
+    pub name: &'static str,
+    pub status: DoctorStatus,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: DoctorStatus::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: DoctorStatus::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: DoctorStatus::Fail,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Every check run by `stand doctor`, in display order
+#[derive(Debug, Clone)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// Returns true if any check failed (warnings do not count)
+    pub fn has_failures(&self) -> bool {
+        self.count(DoctorStatus::Fail) > 0
+    }
+
+    fn count(&self, status: DoctorStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Format the report as a checklist followed by a summary line
+    pub fn format(&self) -> String {
+        let mut output = String::new();
+        for check in &self.checks {
+            let mark = match check.status {
+                DoctorStatus::Pass => success_mark(),
+                DoctorStatus::Warn => warning_mark(),
+                DoctorStatus::Fail => "✗".to_string(),
+            };
+            output.push_str(&format!("{} {}: {}\n", mark, check.name, check.message));
+            if let Some(hint) = &check.hint {
+                output.push_str(&format!("    hint: {}\n", hint));
+            }
+        }
+        output.push_str(&format!(
+            "\n{} passed, {} warning(s), {} failed\n",
+            self.count(DoctorStatus::Pass),
+            self.count(DoctorStatus::Warn),
+            self.count(DoctorStatus::Fail)
+        ));
+        output
+    }
+}
+
+/// Run every check for the project containing `current_dir`
+///
+/// Checks that need a configuration are skipped when none is found.
+pub fn run_doctor(current_dir: &Path) -> DoctorReport {
+    let mut checks = Vec::new();
+
+    match find_project_root_from(current_dir) {
+        Ok(project_root) => check_project(&project_root, &mut checks),
+        Err(_) => checks.push(DoctorCheck::fail(
+            "Configuration",
+            format!(
+                "no .stand.toml found in {} or any parent directory",
+                current_dir.display()
+            ),
+            "run 'stand init' in your project root",
+        )),
+    }
+
+    checks.push(check_shell());
+    checks.push(check_stand_shell());
+
+    DoctorReport { checks }
+}
+
+/// Configuration, encryption key and keys file checks
+fn check_project(project_root: &Path, checks: &mut Vec<DoctorCheck>) {
+    let config = match loader::load_config_toml_with_validation(project_root) {
+        Ok(config) => config,
+        Err(e) => {
+            checks.push(DoctorCheck::fail(
+                "Configuration",
+                e.to_string(),
+                "run 'stand validate' for details",
+            ));
+            return;
+        }
+    };
+    let overlay = project_root.join(loader::LOCAL_CONFIG_FILE);
+    let overlay_note = if overlay.exists() {
+        format!(", with {}", overlay.display())
+    } else {
+        String::new()
+    };
+    checks.push(DoctorCheck::pass(
+        "Configuration",
+        format!(
+            "{} is valid ({} environment(s){})",
+            loader::config_file(project_root).display(),
+            config.environments.len(),
+            overlay_note
+        ),
+    ));
+
+    match &config.encryption {
+        None => checks.push(DoctorCheck::pass("Encryption", "not enabled")),
+        Some(encryption) => {
+            let has_encrypted = config
+                .environments
+                .values()
+                .flat_map(|env| env.variables.values())
+                .chain(config.common.iter().flat_map(|common| common.values()))
+                .any(|value| is_encrypted(value));
+            checks.push(check_private_key(
+                project_root,
+                &encryption.public_key,
+                has_encrypted,
+            ));
+        }
+    }
+
+    if project_root.join(".stand.keys").exists() {
+        checks.extend(check_keys_file(project_root));
+    }
+}
+
+/// Whether a private key is available and matches `[encryption].public_key`
+///
+/// A missing key only fails when there are encrypted values to decrypt.
+fn check_private_key(project_root: &Path, public_key: &str, has_encrypted: bool) -> DoctorCheck {
+    let identity = match load_private_key_for_decryption(project_root)
+        .and_then(|key| keys::parse_private_key(&key))
+    {
+        Ok(identity) => identity,
+        Err(e) => {
+            let hint = "set STAND_PRIVATE_KEY or STAND_PRIVATE_KEY_FILE, or run 'stand keys import <file>'";
+            return if has_encrypted {
+                DoctorCheck::fail("Private key", e.to_string(), hint)
+            } else {
+                DoctorCheck::warn("Private key", e.to_string(), hint)
+            };
+        }
+    };
+
+    if identity.to_public().to_string() != public_key {
+        return DoctorCheck::fail(
+            "Private key",
+            "does not match [encryption].public_key in .stand.toml",
+            "import the key this project was encrypted with ('stand keys import')",
+        );
+    }
+    DoctorCheck::pass("Private key", "available and matches the public key")
+}
+
+/// Permission, git tracking and .gitignore checks for `.stand.keys`
+fn check_keys_file(project_root: &Path) -> Vec<DoctorCheck> {
+    let mut checks: Vec<_> = keys::check_keys_file_security(project_root)
+        .into_iter()
+        .map(|warning| {
+            DoctorCheck::warn(
+                "Keys file",
+                warning.to_string(),
+                "keep .stand.keys private to your machine",
+            )
+        })
+        .collect();
+
+    let ignored = fs::read_to_string(project_root.join(".gitignore"))
+        .map(|content| content.lines().any(|line| line.trim() == ".stand.keys"))
+        .unwrap_or(false);
+    if !ignored {
+        checks.push(DoctorCheck::warn(
+            "Keys file",
+            ".stand.keys is not listed in .gitignore",
+            "add '.stand.keys' to .gitignore",
+        ));
+    }
+
+    if checks.is_empty() {
+        checks.push(DoctorCheck::pass(
+            "Keys file",
+            ".stand.keys is private and ignored by git",
+        ));
+    }
+    checks
+}
+
+/// The shell `stand shell` would start
+fn check_shell() -> DoctorCheck {
+    let shell = detect_user_shell();
+    if !Path::new(&shell).exists() {
+        return DoctorCheck::warn(
+            "Shell",
+            format!("{} (from $SHELL) does not exist", shell),
+            "set $SHELL to an installed shell or pass 'stand shell --shell <path>'",
+        );
+    }
+    match ShellType::from_path(&shell) {
+        ShellType::Other(_) => DoctorCheck::warn(
+            "Shell",
+            format!("{} is not bash, zsh or fish", shell),
+            "prompt and directory integration only work with bash, zsh and fish",
+        ),
+        _ => DoctorCheck::pass("Shell", shell),
+    }
+}
+
+/// Whether this process runs inside a Stand shell
+fn check_stand_shell() -> DoctorCheck {
+    if !is_stand_shell_active() {
+        return DoctorCheck::pass("Stand shell", "not inside a Stand shell");
+    }
+    let environment = get_active_environment().unwrap_or_else(|| "unknown".to_string());
+    DoctorCheck::pass(
+        "Stand shell",
+        format!("inside a Stand shell (environment '{}')", environment),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{encrypt_value, keys::generate_key_pair};
+    use serial_test::serial;
+    use std::env;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"version = "2.0"
+
+[environments.dev]
+description = "Development"
+API_URL = "http://localhost"
+"#;
+
+    fn find<'a>(report: &'a DoctorReport, name: &str) -> Vec<&'a DoctorCheck> {
+        report.checks.iter().filter(|c| c.name == name).collect()
+    }
+
+    #[test]
+    #[serial]
+    fn test_doctor_well_formed_project() {
+        env::remove_var("STAND_ACTIVE");
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let report = run_doctor(dir.path());
+
+        assert!(!report.has_failures());
+        let config = find(&report, "Configuration");
+        assert_eq!(config[0].status, DoctorStatus::Pass);
+        assert!(config[0].message.contains("1 environment(s)"));
+        assert_eq!(find(&report, "Encryption")[0].message, "not enabled");
+        assert!(find(&report, "Keys file").is_empty());
+        assert_eq!(
+            find(&report, "Stand shell")[0].message,
+            "not inside a Stand shell"
+        );
+
+        let output = report.format();
+        assert!(output.contains("Configuration: "));
+        assert!(output.contains(" failed\n"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_doctor_reports_loaded_files() {
+        env::remove_var("STAND_ACTIVE");
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.yaml"),
+            "version: \"2.0\"\nenvironments:\n  dev:\n    description: Development\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(".stand.local.toml"),
+            "[environments.dev]\nDEBUG = \"1\"\n",
+        )
+        .unwrap();
+
+        let report = run_doctor(dir.path());
+
+        let message = &find(&report, "Configuration")[0].message;
+        assert!(message.contains(".stand.yaml is valid"), "{}", message);
+        assert!(!message.contains(".stand.toml"), "{}", message);
+        assert!(message.ends_with(".stand.local.toml)"), "{}", message);
+    }
+
+    #[test]
+    fn test_doctor_missing_config() {
+        let dir = tempdir().unwrap();
+
+        let report = run_doctor(dir.path());
+
+        assert!(report.has_failures());
+        let config = find(&report, "Configuration");
+        assert_eq!(config[0].status, DoctorStatus::Fail);
+        assert!(report.format().contains("hint: run 'stand init'"));
+        // Environment-independent checks still run
+        assert_eq!(find(&report, "Shell").len(), 1);
+    }
+
+    #[test]
+    fn test_doctor_invalid_config_fails() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\nextends = \"missing\"\n",
+        )
+        .unwrap();
+
+        let report = run_doctor(dir.path());
+
+        assert!(report.has_failures());
+        assert!(report.format().contains("stand validate"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_doctor_encryption_key_checks() {
+        env::remove_var("STAND_PRIVATE_KEY");
+        let dir = tempdir().unwrap();
+        let key_pair = generate_key_pair();
+        let encrypted = encrypt_value("secret", &key_pair.to_recipient().unwrap()).unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            format!(
+                "{}TOKEN = \"{}\"\n\n[encryption]\npublic_key = \"{}\"\n",
+                CONFIG, encrypted, key_pair.public_key
+            ),
+        )
+        .unwrap();
+
+        // Encrypted values without a key are a hard failure
+        let report = run_doctor(dir.path());
+        assert!(report.has_failures());
+        assert_eq!(find(&report, "Private key")[0].status, DoctorStatus::Fail);
+
+        // A matching key that is not ignored by git only warns
+        keys::save_private_key(&dir.path().join(".stand.keys"), &key_pair.private_key).unwrap();
+        let report = run_doctor(dir.path());
+        assert!(!report.has_failures());
+        assert_eq!(find(&report, "Private key")[0].status, DoctorStatus::Pass);
+        assert!(find(&report, "Keys file")
+            .iter()
+            .any(|c| c.status == DoctorStatus::Warn && c.message.contains(".gitignore")));
+
+        fs::write(dir.path().join(".gitignore"), ".stand.keys\n").unwrap();
+        let report = run_doctor(dir.path());
+        assert_eq!(find(&report, "Keys file")[0].status, DoctorStatus::Pass);
+
+        // A key from another project does not match
+        keys::save_private_key(
+            &dir.path().join(".stand.keys"),
+            &generate_key_pair().private_key,
+        )
+        .unwrap();
+        let report = run_doctor(dir.path());
+        assert_eq!(find(&report, "Private key")[0].status, DoctorStatus::Fail);
+    }
+}
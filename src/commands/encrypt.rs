@@ -1,10 +1,12 @@
 //! Encryption management commands.
 
 use std::fs;
-use std::io::Write;
 use std::path::Path;
 
-use colored::Colorize;
+use crate::commands::CommandContext;
+use crate::utils::colors::{format_secret_summary, success_mark, warning_mark};
+use crate::utils::confirm::{Confirm, StdinConfirm};
+use crate::utils::write_atomic;
 use toml_edit::{DocumentMut, Item, Value};
 
 use crate::crypto::{
@@ -18,6 +20,30 @@ const CONFIG_FILE: &str = ".stand.toml";
 ///
 /// Generates a new key pair and adds the public key to .stand.toml.
 pub fn enable_encryption(project_dir: &Path) -> Result<(), EncryptionCommandError> {
+    enable_encryption_with_context(project_dir, &CommandContext::default())
+}
+
+/// Enable encryption, printing the ✓ progress lines unless `ctx` is quiet.
+pub fn enable_encryption_with_context(
+    project_dir: &Path,
+    ctx: &CommandContext,
+) -> Result<(), EncryptionCommandError> {
+    enable_encryption_with_recipients(project_dir, ctx, None)
+}
+
+/// Enable encryption, also adding the public keys in `recipients_file` to
+/// `[encryption].recipients` so new values are encrypted to the whole team.
+///
+/// The file is read with [`read_recipients_file`] before anything is written; a
+/// malformed key rejects the whole operation. A key pair for the current user is
+/// generated either way.
+pub fn enable_encryption_with_recipients(
+    project_dir: &Path,
+    ctx: &CommandContext,
+    recipients_file: Option<&Path>,
+) -> Result<(), EncryptionCommandError> {
+    warn_keys_file_security(project_dir);
+
     let config_path = project_dir.join(CONFIG_FILE);
     let keys_path = project_dir.join(KEYS_FILE);
 
@@ -37,6 +63,11 @@ pub fn enable_encryption(project_dir: &Path) -> Result<(), EncryptionCommandErro
         return Err(EncryptionCommandError::AlreadyEnabled);
     }
 
+    let recipients = match recipients_file {
+        Some(path) => read_recipients_file(path)?,
+        None => Vec::new(),
+    };
+
     // Generate key pair
     let key_pair = generate_key_pair();
 
@@ -46,15 +77,19 @@ pub fn enable_encryption(project_dir: &Path) -> Result<(), EncryptionCommandErro
         .map_err(EncryptionCommandError::Crypto)?;
 
     // Add .stand.keys to .gitignore before writing config
-    add_to_gitignore(project_dir, KEYS_FILE)?;
+    let gitignore_updated = add_to_gitignore(project_dir, KEYS_FILE)?;
 
     // Add [encryption] section to config using toml_edit
     let mut encryption_table = toml_edit::Table::new();
     encryption_table.insert("public_key", toml_edit::value(&key_pair.public_key));
+    if !recipients.is_empty() {
+        let keys: toml_edit::Array = recipients.iter().map(String::as_str).collect();
+        encryption_table.insert("recipients", toml_edit::value(keys));
+    }
     doc.insert("encryption", Item::Table(encryption_table));
 
     // Write config LAST. If this fails, clean up the key file.
-    if let Err(e) = fs::write(&config_path, doc.to_string()) {
+    if let Err(e) = write_atomic(&config_path, doc.to_string()) {
         // Roll back: remove the key file we just created
         if let Err(cleanup_err) = fs::remove_file(&keys_path) {
             eprintln!(
@@ -69,23 +104,89 @@ pub fn enable_encryption(project_dir: &Path) -> Result<(), EncryptionCommandErro
         return Err(e.into());
     }
 
-    println!("{} Generated key pair", "✓".green());
+    if ctx.is_quiet() {
+        return Ok(());
+    }
+
+    if gitignore_updated {
+        println!("{} Added {} to .gitignore", success_mark(), KEYS_FILE);
+    }
+    println!("{} Generated key pair", success_mark());
     println!(
         "{} Added [encryption] section to {}",
-        "✓".green(),
+        success_mark(),
         CONFIG_FILE
     );
-    println!("{} Created {}", "✓".green(), KEYS_FILE);
+    println!("{} Created {}", success_mark(), KEYS_FILE);
+    if !recipients.is_empty() {
+        println!(
+            "{} Added {} recipient(s) to [encryption].recipients",
+            success_mark(),
+            recipients.len()
+        );
+    }
 
     Ok(())
 }
 
+/// Read age public keys from `path`, one per line
+///
+/// Blank lines and `#` comments (whole-line or trailing) are skipped, and a key
+/// listed twice is kept once.
+///
+/// # Errors
+/// Returns [`EncryptionCommandError::InvalidRecipient`] for the first line that
+/// is not a valid age public key.
+pub fn read_recipients_file(path: &Path) -> Result<Vec<String>, EncryptionCommandError> {
+    let content = fs::read_to_string(path)?;
+    let mut recipients: Vec<String> = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let key = line.split('#').next().unwrap_or_default().trim();
+        if key.is_empty() {
+            continue;
+        }
+        crate::crypto::keys::parse_public_key(key).map_err(|e| {
+            EncryptionCommandError::InvalidRecipient {
+                path: path.display().to_string(),
+                line: index + 1,
+                content: key.to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+        if !recipients.iter().any(|existing| existing == key) {
+            recipients.push(key.to_string());
+        }
+    }
+
+    Ok(recipients)
+}
+
 /// Disable encryption for the project.
 ///
 /// Prompts for user confirmation, then decrypts all encrypted values
 /// and removes encryption configuration. If the user declines, returns Ok(())
 /// without making changes.
 pub fn disable_encryption(project_dir: &Path) -> Result<(), EncryptionCommandError> {
+    disable_encryption_with_context(project_dir, &CommandContext::default())
+}
+
+/// Disable encryption, printing the ✓ result lines unless `ctx` is quiet.
+///
+/// The confirmation prompt is always shown.
+pub fn disable_encryption_with_context(
+    project_dir: &Path,
+    ctx: &CommandContext,
+) -> Result<(), EncryptionCommandError> {
+    disable_encryption_with_confirm(project_dir, ctx, &StdinConfirm::default())
+}
+
+/// Like [`disable_encryption_with_context`], asking for confirmation through `confirmer`
+pub fn disable_encryption_with_confirm(
+    project_dir: &Path,
+    ctx: &CommandContext,
+    confirmer: &dyn Confirm,
+) -> Result<(), EncryptionCommandError> {
     let config_path = project_dir.join(CONFIG_FILE);
 
     // Check if config file exists
@@ -107,14 +208,9 @@ pub fn disable_encryption(project_dir: &Path) -> Result<(), EncryptionCommandErr
     // Prompt for confirmation
     println!(
         "{} This will decrypt all encrypted values and remove encryption.",
-        "⚠".yellow()
+        warning_mark()
     );
-    print!("Continue? [y/N]: ");
-    std::io::stdout().flush()?;
-
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input)?;
-    if !input.trim().eq_ignore_ascii_case("y") {
+    if !confirmer.confirm("Continue?", false)? {
         println!("Aborted.");
         return Ok(());
     }
@@ -122,15 +218,19 @@ pub fn disable_encryption(project_dir: &Path) -> Result<(), EncryptionCommandErr
     // Perform the actual disable operation
     let result = disable_encryption_internal(project_dir)?;
 
+    if ctx.is_quiet() {
+        return Ok(());
+    }
+
     if result.decrypted_count > 0 {
         println!(
             "{} Decrypted {} value(s)",
-            "✓".green(),
+            success_mark(),
             result.decrypted_count
         );
     }
-    println!("{} Removed [encryption] section", "✓".green());
-    println!("{} Encryption disabled", "✓".green());
+    println!("{} Removed [encryption] section", success_mark());
+    println!("{} Encryption disabled", success_mark());
 
     Ok(())
 }
@@ -177,7 +277,7 @@ pub fn disable_encryption_internal(
         // Decrypt all encrypted values in environments section
         if let Some(environments) = doc.get_mut("environments") {
             if let Some(env_table) = environments.as_table_mut() {
-                for (_env_name, env_config) in env_table.iter_mut() {
+                for (env_name, env_config) in env_table.iter_mut() {
                     if let Some(env_tbl) = env_config.as_table_mut() {
                         for (key, value) in env_tbl.iter_mut() {
                             if let Some(val_str) = value.as_str() {
@@ -189,6 +289,11 @@ pub fn disable_encryption_internal(
                                         variable: key.to_string(),
                                         reason: e.to_string(),
                                     })?;
+                                    crate::log_verbose!(
+                                        "Decrypted {}.{}",
+                                        env_name,
+                                        format_secret_summary(&key, &decrypted)
+                                    );
                                     *value = Item::Value(Value::from(decrypted));
                                     result.decrypted_count += 1;
                                 }
@@ -210,6 +315,10 @@ pub fn disable_encryption_internal(
                                     variable: key.to_string(),
                                     reason: e.to_string(),
                                 })?;
+                            crate::log_verbose!(
+                                "Decrypted common.{}",
+                                format_secret_summary(&key, &decrypted)
+                            );
                             *value = Item::Value(Value::from(decrypted));
                             result.decrypted_count += 1;
                         }
@@ -223,7 +332,7 @@ pub fn disable_encryption_internal(
     doc.remove("encryption");
 
     // Write back preserving formatting
-    fs::write(&config_path, doc.to_string())?;
+    write_atomic(&config_path, doc.to_string())?;
 
     // Remove .stand.keys file if it exists
     if keys_path.exists() {
@@ -268,14 +377,23 @@ fn has_encrypted_values_in_doc(doc: &DocumentMut) -> bool {
     false
 }
 
+/// Prints a warning to stderr for each security problem with `.stand.keys`.
+pub fn warn_keys_file_security(project_dir: &Path) {
+    for warning in crate::crypto::keys::check_keys_file_security(project_dir) {
+        eprintln!("{} Warning: {}", warning_mark(), warning);
+    }
+}
+
 /// Adds a file to .gitignore if not already present.
-fn add_to_gitignore(project_dir: &Path, filename: &str) -> Result<(), std::io::Error> {
+///
+/// Returns true if .gitignore was changed.
+pub(crate) fn add_to_gitignore(project_dir: &Path, filename: &str) -> Result<bool, std::io::Error> {
     let gitignore_path = project_dir.join(".gitignore");
 
     if gitignore_path.exists() {
         let content = fs::read_to_string(&gitignore_path)?;
         if content.lines().any(|line| line.trim() == filename) {
-            return Ok(()); // Already in .gitignore
+            return Ok(false); // Already in .gitignore
         }
         // Append to existing .gitignore
         let mut file = fs::OpenOptions::new().append(true).open(&gitignore_path)?;
@@ -285,8 +403,7 @@ fn add_to_gitignore(project_dir: &Path, filename: &str) -> Result<(), std::io::E
         fs::write(&gitignore_path, format!("{}\n", filename))?;
     }
 
-    println!("{} Added {} to .gitignore", "✓".green(), filename);
-    Ok(())
+    Ok(true)
 }
 
 /// Error type for encryption commands.
@@ -307,16 +424,28 @@ pub enum EncryptionCommandError {
     #[error("TOML parsing error: {0}")]
     TomlParse(String),
 
+    #[error("Invalid recipient '{content}' on line {line} of {path}: {reason}")]
+    InvalidRecipient {
+        path: String,
+        line: usize,
+        content: String,
+        reason: String,
+    },
+
     #[error("Failed to decrypt variable '{variable}': {reason}. All values must be decryptable to disable encryption.")]
     DecryptionFailed { variable: String, reason: String },
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Prompt(#[from] anyhow::Error),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::confirm::MockConfirm;
     use tempfile::tempdir;
 
     #[test]
@@ -358,6 +487,94 @@ description = "Development"
         assert!(keys_path.exists());
     }
 
+    #[test]
+    fn test_enable_encryption_with_recipients_file() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        fs::write(
+            &config_path,
+            "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development\"\n",
+        )
+        .unwrap();
+        let alice = generate_key_pair();
+        let bob = generate_key_pair();
+        let recipients_path = dir.path().join("team.txt");
+        fs::write(
+            &recipients_path,
+            format!(
+                "# Team keys\n{}  # alice\n\n{}\n{}\n",
+                alice.public_key, bob.public_key, alice.public_key
+            ),
+        )
+        .unwrap();
+
+        enable_encryption_with_recipients(
+            dir.path(),
+            &CommandContext::default(),
+            Some(&recipients_path),
+        )
+        .unwrap();
+
+        let config = crate::config::loader::load_config_toml(dir.path()).unwrap();
+        let encryption = config.encryption.unwrap();
+        assert_eq!(
+            encryption.recipients,
+            [alice.public_key.clone(), bob.public_key.clone()]
+        );
+        assert!(dir.path().join(KEYS_FILE).exists());
+
+        // New values can be decrypted by the local key and by every teammate
+        crate::commands::set::set_variable(
+            dir.path(),
+            "dev",
+            "TOKEN",
+            Some("secret".to_string()),
+            true,
+        )
+        .unwrap();
+        let config = crate::config::loader::load_config_toml(dir.path()).unwrap();
+        let token = &config.environments["dev"].variables["TOKEN"];
+        let local = load_private_key_for_decryption(dir.path()).unwrap();
+        for private_key in [local.as_str(), &alice.private_key, &bob.private_key] {
+            let identity = crate::crypto::keys::parse_private_key(private_key).unwrap();
+            assert_eq!(
+                crate::crypto::decrypt_value(token, &identity).unwrap(),
+                "secret"
+            );
+        }
+    }
+
+    #[test]
+    fn test_enable_encryption_rejects_malformed_recipient() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        let original = "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development\"\n";
+        fs::write(&config_path, original).unwrap();
+        let recipients_path = dir.path().join("team.txt");
+        fs::write(
+            &recipients_path,
+            format!("{}\nage1notakey\n", generate_key_pair().public_key),
+        )
+        .unwrap();
+
+        let result = enable_encryption_with_recipients(
+            dir.path(),
+            &CommandContext::default(),
+            Some(&recipients_path),
+        );
+
+        match result {
+            Err(EncryptionCommandError::InvalidRecipient { line, content, .. }) => {
+                assert_eq!(line, 2);
+                assert_eq!(content, "age1notakey");
+            }
+            other => panic!("expected InvalidRecipient, got {:?}", other),
+        }
+        // Nothing was written
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+        assert!(!dir.path().join(KEYS_FILE).exists());
+    }
+
     #[test]
     fn test_enable_encryption_already_enabled() {
         let dir = tempdir().unwrap();
@@ -384,6 +601,31 @@ description = "Development"
         ));
     }
 
+    #[test]
+    fn test_disable_encryption_follows_confirmation_answer() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        fs::write(
+            &config_path,
+            "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development\"\n",
+        )
+        .unwrap();
+        enable_encryption(dir.path()).unwrap();
+        let enabled = fs::read_to_string(&config_path).unwrap();
+        let ctx = CommandContext::new(true);
+
+        let decline = MockConfirm::answering(false);
+        disable_encryption_with_confirm(dir.path(), &ctx, &decline).unwrap();
+        assert_eq!(decline.prompts.borrow().as_slice(), ["Continue?"]);
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), enabled);
+
+        let accept = MockConfirm::answering(true);
+        disable_encryption_with_confirm(dir.path(), &ctx, &accept).unwrap();
+        assert!(!fs::read_to_string(&config_path)
+            .unwrap()
+            .contains("[encryption]"));
+    }
+
     // === Issue 2: Tests for disable_encryption_internal ===
 
     #[test]
@@ -1,24 +1,45 @@
 use crate::config::loader;
-use crate::crypto::decrypt_variables;
+use crate::crypto::{decrypt_variables, is_encrypted};
 use crate::shell::{get_active_environment, is_stand_shell_active};
+use crate::utils::colors::mask_value;
 use anyhow::{anyhow, Result};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::path::Path;
 
+/// Output format for `stand env`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum EnvFormat {
+    /// `KEY=value` lines grouped under comment headers
+    #[default]
+    Plain,
+    /// Pretty-printed JSON
+    Json,
+    /// Single-line JSON, for log ingestion
+    JsonCompact,
+    /// YAML document
+    Yaml,
+    /// `export KEY='value'` lines suitable for `eval`
+    Export,
+}
+
 /// Options for controlling `stand env` command output format and filtering.
 ///
 /// # Field Interactions
 /// - `stand_only` and `user_only` are mutually exclusive (enforced by CLI)
 /// - When both are `false`, both Stand markers and user variables are displayed
+/// - The filters apply to every `format`
 #[derive(Debug, Clone, Default)]
 pub struct EnvOptions {
-    /// Output in JSON format instead of plain text
-    pub json: bool,
+    /// Output format
+    pub format: EnvFormat,
     /// Show only Stand marker variables (STAND_*)
     pub stand_only: bool,
     /// Show only user-defined environment variables
     pub user_only: bool,
+    /// Compare user variables with the current process environment instead
+    /// of printing them in `format`
+    pub diff_system: bool,
 }
 
 /// Stand marker environment variable names used to identify and configure
@@ -46,7 +67,9 @@ fn get_stand_markers() -> HashMap<String, String> {
     markers
 }
 
-/// Get user-defined variables for the current environment (with decryption)
+/// Get user-defined variables for the current environment
+///
+/// Encrypted values are decrypted unless `settings.auto_decrypt` is false.
 fn get_user_variables(project_path: &Path, env_name: &str) -> Result<HashMap<String, String>> {
     let config = loader::load_config_toml_with_inheritance(project_path)?;
 
@@ -55,6 +78,10 @@ fn get_user_variables(project_path: &Path, env_name: &str) -> Result<HashMap<Str
         .get(env_name)
         .ok_or_else(|| anyhow!("Environment '{}' not found in configuration", env_name))?;
 
+    if config.settings.auto_decrypt == Some(false) {
+        return Ok(env.variables.clone());
+    }
+
     // Decrypt any encrypted values
     let decrypted = decrypt_variables(env.variables.clone(), project_path)
         .map_err(|e| anyhow!("Failed to decrypt variables: {}", e))?;
@@ -94,52 +121,166 @@ fn format_plain(
     output
 }
 
-/// Format output as JSON
-fn format_json(
+/// How a user variable relates to the same name in the current process environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SystemDiff {
+    /// Not set in the process environment
+    Added,
+    /// Set to a different value, kept here (masked if it looks secret)
+    Overridden(String),
+    /// Set to the same value
+    Unchanged,
+}
+
+impl std::fmt::Display for SystemDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SystemDiff::Added => write!(f, "added"),
+            SystemDiff::Overridden(previous) => write!(f, "overridden (was: {})", previous),
+            SystemDiff::Unchanged => write!(f, "unchanged"),
+        }
+    }
+}
+
+/// Names containing any of these (case-insensitively) are treated as secrets
+const SECRET_NAME_PARTS: &[&str] = &[
+    "SECRET",
+    "TOKEN",
+    "PASSWORD",
+    "PASSWD",
+    "KEY",
+    "CREDENTIAL",
+    "PRIVATE",
+    "AUTH",
+];
+
+/// Whether a variable's value should be masked when it is not the value Stand set
+fn looks_secret(name: &str, value: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    is_encrypted(value) || SECRET_NAME_PARTS.iter().any(|part| name.contains(part))
+}
+
+/// Compare user variables with `system`, sorted by name
+fn diff_against_system(
+    user_vars: &HashMap<String, String>,
+    system: &HashMap<String, String>,
+) -> Vec<(String, SystemDiff)> {
+    let mut diff: Vec<_> = user_vars
+        .iter()
+        .map(|(key, value)| {
+            let change = match system.get(key) {
+                None => SystemDiff::Added,
+                Some(previous) if previous == value => SystemDiff::Unchanged,
+                Some(previous) if looks_secret(key, previous) => {
+                    SystemDiff::Overridden(mask_value(previous, false))
+                }
+                Some(previous) => SystemDiff::Overridden(previous.clone()),
+            };
+            (key.clone(), change)
+        })
+        .collect();
+    diff.sort_by(|(a, _), (b, _)| a.cmp(b));
+    diff
+}
+
+/// Format user variables annotated with how they differ from `system`
+fn format_system_diff(
+    user_vars: &HashMap<String, String>,
+    system: &HashMap<String, String>,
+) -> String {
+    let mut output = String::from("# User Variables (compared with the current environment)\n");
+    for (key, change) in diff_against_system(user_vars, system) {
+        output.push_str(&format!("{}={}  # {}\n", key, user_vars[&key], change));
+    }
+    output
+}
+
+/// Quote a value for POSIX shells using single quotes
+///
+/// Embedded single quotes are written as `'\''` (close, escaped quote, reopen).
+fn shell_single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Format output as shell `export` statements
+fn format_export(
     stand_markers: &HashMap<String, String>,
     user_vars: &HashMap<String, String>,
     options: &EnvOptions,
-) -> Result<String> {
-    use std::collections::BTreeMap;
+) -> String {
+    let mut vars: Vec<(&String, &String)> = Vec::new();
+    if !options.user_only {
+        vars.extend(stand_markers.iter());
+    }
+    if !options.stand_only {
+        vars.extend(user_vars.iter());
+    }
+    vars.sort_by_key(|(k, _)| *k);
 
-    #[derive(serde::Serialize)]
-    struct EnvOutput {
-        #[serde(skip_serializing_if = "Option::is_none")]
-        stand: Option<BTreeMap<String, String>>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        user: Option<BTreeMap<String, String>>,
+    let mut output = String::new();
+    for (key, value) in vars {
+        output.push_str(&format!("export {}={}\n", key, shell_single_quote(value)));
     }
+    output
+}
 
-    let stand = if options.user_only {
-        None
-    } else {
-        Some(
-            stand_markers
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect(),
-        )
+/// Structured output shared by the JSON and YAML formats
+#[derive(serde::Serialize)]
+struct EnvOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stand: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<BTreeMap<String, String>>,
+}
+
+/// Build the structured output, omitting the filtered-out section
+fn build_structured(
+    stand_markers: &HashMap<String, String>,
+    user_vars: &HashMap<String, String>,
+    options: &EnvOptions,
+) -> EnvOutput {
+    let sorted = |vars: &HashMap<String, String>| {
+        vars.iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<BTreeMap<_, _>>()
     };
 
-    let user = if options.stand_only {
-        None
+    EnvOutput {
+        stand: (!options.user_only).then(|| sorted(stand_markers)),
+        user: (!options.stand_only).then(|| sorted(user_vars)),
+    }
+}
+
+/// Format output as JSON (pretty, or single-line when `compact`)
+fn format_json(
+    stand_markers: &HashMap<String, String>,
+    user_vars: &HashMap<String, String>,
+    options: &EnvOptions,
+    compact: bool,
+) -> Result<String> {
+    let output = build_structured(stand_markers, user_vars, options);
+    if compact {
+        Ok(format!("{}\n", serde_json::to_string(&output)?))
     } else {
-        Some(
-            user_vars
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect(),
-        )
-    };
+        Ok(serde_json::to_string_pretty(&output)?)
+    }
+}
 
-    let output = EnvOutput { stand, user };
-    Ok(serde_json::to_string_pretty(&output)?)
+/// Format output as YAML
+fn format_yaml(
+    stand_markers: &HashMap<String, String>,
+    user_vars: &HashMap<String, String>,
+    options: &EnvOptions,
+) -> Result<String> {
+    let output = build_structured(stand_markers, user_vars, options);
+    Ok(serde_yaml::to_string(&output)?)
 }
 
 /// Display environment variables for the current Stand subshell session.
 ///
 /// This function retrieves and formats both Stand marker variables (STAND_*)
-/// and user-defined variables from the configuration for display.
+/// and user-defined variables from the configuration for display. Outside a
+/// subshell, the active (`stand switch`) or default environment is shown instead.
 ///
 /// # Arguments
 ///
@@ -148,26 +289,31 @@ fn format_json(
 ///
 /// # Returns
 ///
-/// Formatted string containing environment variables (plain text or JSON)
+/// Formatted string containing environment variables in the requested format
 ///
 /// # Errors
 ///
-/// - Returns an error if not currently inside a Stand subshell
+/// - Returns an error if not inside a Stand subshell and no active or default environment is set
 /// - Returns an error if STAND_ENVIRONMENT is not set (should not happen in valid session)
-/// - JSON serialization errors are propagated when using JSON output format
+/// - Serialization errors are propagated when using the JSON or YAML formats
 pub fn show_env(project_path: &Path, options: EnvOptions) -> Result<String> {
-    // Check if we're inside a Stand subshell
-    if !is_stand_shell_active() {
-        return Err(anyhow!(
-            "Not inside a Stand subshell.\n\
-             Use 'stand shell <environment>' to start a subshell first."
-        ));
-    }
-
-    // Get current environment name
-    let env_name = get_active_environment().ok_or_else(|| {
-        anyhow!("STAND_ENVIRONMENT is not set. This should not happen inside a Stand subshell.")
-    })?;
+    let env_name = if is_stand_shell_active() {
+        get_active_environment().ok_or_else(|| {
+            anyhow!("STAND_ENVIRONMENT is not set. This should not happen inside a Stand subshell.")
+        })?
+    } else {
+        // Outside a subshell, fall back to the active (`stand switch`) or default environment
+        let not_in_subshell = || {
+            anyhow!(
+                "Not inside a Stand subshell.\n\
+                 Use 'stand shell <environment>' to start a subshell first."
+            )
+        };
+        let config = loader::load_config_toml(project_path).map_err(|_| not_in_subshell())?;
+        config
+            .resolve_default_environment(project_path)?
+            .ok_or_else(not_in_subshell)?
+    };
 
     // Get Stand markers
     let stand_markers = get_stand_markers();
@@ -179,11 +325,18 @@ pub fn show_env(project_path: &Path, options: EnvOptions) -> Result<String> {
         get_user_variables(project_path, &env_name)?
     };
 
+    if options.diff_system {
+        let system: HashMap<String, String> = env::vars().collect();
+        return Ok(format_system_diff(&user_vars, &system));
+    }
+
     // Format output
-    if options.json {
-        format_json(&stand_markers, &user_vars, &options)
-    } else {
-        Ok(format_plain(&stand_markers, &user_vars, &options))
+    match options.format {
+        EnvFormat::Plain => Ok(format_plain(&stand_markers, &user_vars, &options)),
+        EnvFormat::Json => format_json(&stand_markers, &user_vars, &options, false),
+        EnvFormat::JsonCompact => format_json(&stand_markers, &user_vars, &options, true),
+        EnvFormat::Yaml => format_yaml(&stand_markers, &user_vars, &options),
+        EnvFormat::Export => Ok(format_export(&stand_markers, &user_vars, &options)),
     }
 }
 
@@ -363,7 +516,7 @@ API_KEY = "test-key"
         fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
 
         let options = EnvOptions {
-            json: true,
+            format: EnvFormat::Json,
             ..Default::default()
         };
         let result = show_env(dir.path(), options);
@@ -395,4 +548,239 @@ API_KEY = "test-key"
         assert!(output.contains("# User Variables"));
         assert!(output.contains("API_KEY=secret"));
     }
+
+    #[test]
+    fn test_shell_single_quote_escapes_quotes() {
+        assert_eq!(shell_single_quote("plain"), "'plain'");
+        assert_eq!(shell_single_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_single_quote(""), "''");
+    }
+
+    #[test]
+    fn test_format_export_output() {
+        let mut stand_markers = HashMap::new();
+        stand_markers.insert("STAND_ENVIRONMENT".to_string(), "dev".to_string());
+
+        let mut user_vars = HashMap::new();
+        user_vars.insert("API_KEY".to_string(), "secret".to_string());
+
+        let output = format_export(&stand_markers, &user_vars, &EnvOptions::default());
+        assert_eq!(
+            output,
+            "export API_KEY='secret'\nexport STAND_ENVIRONMENT='dev'\n"
+        );
+
+        let options = EnvOptions {
+            user_only: true,
+            ..Default::default()
+        };
+        let output = format_export(&stand_markers, &user_vars, &options);
+        assert_eq!(output, "export API_KEY='secret'\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_format_export_round_trips_through_sh() {
+        let value = "it's a \"quoted\" value with  spaces $HOME `cmd`";
+        let mut user_vars = HashMap::new();
+        user_vars.insert("TRICKY".to_string(), value.to_string());
+
+        let output = format_export(&HashMap::new(), &user_vars, &EnvOptions::default());
+        let result = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("{}printf '%s' \"$TRICKY\"", output))
+            .output()
+            .unwrap();
+
+        assert!(result.status.success());
+        assert_eq!(String::from_utf8(result.stdout).unwrap(), value);
+    }
+
+    fn sample_vars() -> (HashMap<String, String>, HashMap<String, String>) {
+        let mut stand_markers = HashMap::new();
+        stand_markers.insert("STAND_ACTIVE".to_string(), "1".to_string());
+        stand_markers.insert("STAND_ENVIRONMENT".to_string(), "dev".to_string());
+
+        let mut user_vars = HashMap::new();
+        user_vars.insert("API_KEY".to_string(), "secret".to_string());
+        user_vars.insert("MULTILINE".to_string(), "a\nb".to_string());
+
+        (stand_markers, user_vars)
+    }
+
+    #[test]
+    fn test_format_json_compact_is_single_line() {
+        let (stand_markers, user_vars) = sample_vars();
+
+        let output = format_json(&stand_markers, &user_vars, &EnvOptions::default(), true).unwrap();
+
+        assert_eq!(output.trim_end().lines().count(), 1);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["stand"]["STAND_ENVIRONMENT"], "dev");
+        assert_eq!(parsed["user"]["API_KEY"], "secret");
+        assert_eq!(parsed["user"]["MULTILINE"], "a\nb");
+    }
+
+    #[test]
+    fn test_format_yaml_round_trips() {
+        let (stand_markers, user_vars) = sample_vars();
+
+        let output = format_yaml(&stand_markers, &user_vars, &EnvOptions::default()).unwrap();
+
+        let parsed: BTreeMap<String, BTreeMap<String, String>> =
+            serde_yaml::from_str(&output).unwrap();
+        assert_eq!(parsed["stand"]["STAND_ACTIVE"], "1");
+        assert_eq!(parsed["user"]["API_KEY"], "secret");
+        assert_eq!(parsed["user"]["MULTILINE"], "a\nb");
+    }
+
+    #[test]
+    fn test_structured_formats_respect_filters() {
+        let (stand_markers, user_vars) = sample_vars();
+        let user_only = EnvOptions {
+            user_only: true,
+            ..Default::default()
+        };
+        let stand_only = EnvOptions {
+            stand_only: true,
+            ..Default::default()
+        };
+
+        for compact in [false, true] {
+            let output = format_json(&stand_markers, &user_vars, &user_only, compact).unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+            assert!(parsed.get("stand").is_none());
+            assert!(parsed.get("user").is_some());
+        }
+
+        let output = format_yaml(&stand_markers, &user_vars, &stand_only).unwrap();
+        let parsed: BTreeMap<String, BTreeMap<String, String>> =
+            serde_yaml::from_str(&output).unwrap();
+        assert!(parsed.contains_key("stand"));
+        assert!(!parsed.contains_key("user"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_show_env_outside_subshell_uses_default_environment() {
+        env::remove_var("STAND_ACTIVE");
+        env::remove_var("STAND_ENVIRONMENT");
+
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            r#"
+version = "2.0"
+
+[settings]
+default_environment = "dev"
+
+[environments.dev]
+description = "Development"
+API_KEY = "dev-key"
+"#,
+        )
+        .unwrap();
+
+        let output = show_env(dir.path(), EnvOptions::default()).unwrap();
+
+        assert!(output.contains("API_KEY=dev-key"));
+        assert!(!output.contains("# Stand Environment"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_show_env_diff_system_annotates_changes() {
+        env::set_var("STAND_ACTIVE", "1");
+        env::set_var("STAND_ENVIRONMENT", "dev");
+        env::set_var("STAND_DIFF_API_TOKEN", "system-token-value");
+        env::set_var("STAND_DIFF_REGION", "us-east-1");
+        env::set_var("STAND_DIFF_SAME", "same");
+        env::remove_var("STAND_DIFF_NEW");
+
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+STAND_DIFF_API_TOKEN = "dev-token"
+STAND_DIFF_REGION = "eu-west-1"
+STAND_DIFF_SAME = "same"
+STAND_DIFF_NEW = "new"
+"#,
+        )
+        .unwrap();
+
+        let options = EnvOptions {
+            diff_system: true,
+            ..EnvOptions::default()
+        };
+        let output = show_env(dir.path(), options);
+
+        env::remove_var("STAND_ACTIVE");
+        env::remove_var("STAND_ENVIRONMENT");
+        env::remove_var("STAND_DIFF_API_TOKEN");
+        env::remove_var("STAND_DIFF_REGION");
+        env::remove_var("STAND_DIFF_SAME");
+
+        let output = output.unwrap();
+        assert!(output.contains("STAND_DIFF_API_TOKEN=dev-token  # overridden (was: ********)\n"));
+        assert!(!output.contains("system-token-value"));
+        assert!(output.contains("STAND_DIFF_REGION=eu-west-1  # overridden (was: us-east-1)\n"));
+        assert!(output.contains("STAND_DIFF_SAME=same  # unchanged\n"));
+        assert!(output.contains("STAND_DIFF_NEW=new  # added\n"));
+        assert!(!output.contains("STAND_ACTIVE="));
+    }
+
+    #[test]
+    fn test_looks_secret() {
+        assert!(looks_secret("GITHUB_TOKEN", "abc"));
+        assert!(looks_secret("db_password", "abc"));
+        assert!(looks_secret("API_KEY", "abc"));
+        assert!(looks_secret("PLAIN", "encrypted:abc"));
+        assert!(!looks_secret("REGION", "us-east-1"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_show_env_auto_decrypt() {
+        use crate::crypto::{encrypt_value, keys};
+
+        env::set_var("STAND_ACTIVE", "1");
+        env::set_var("STAND_ENVIRONMENT", "dev");
+        env::remove_var("STAND_PRIVATE_KEY");
+
+        let dir = tempdir().unwrap();
+        let key_pair = keys::generate_key_pair();
+        keys::save_private_key(&dir.path().join(".stand.keys"), &key_pair.private_key).unwrap();
+        let encrypted = encrypt_value("dev-secret", &key_pair.to_recipient().unwrap()).unwrap();
+        let config = |settings: &str| {
+            format!(
+                "version = \"2.0\"\n\n[settings]\n{}\n\n[environments.dev]\ndescription = \"Development\"\nAPI_KEY = \"{}\"\n\n[encryption]\npublic_key = \"{}\"\n",
+                settings, encrypted, key_pair.public_key
+            )
+        };
+        let options = EnvOptions {
+            user_only: true,
+            ..EnvOptions::default()
+        };
+
+        fs::write(dir.path().join(".stand.toml"), config("")).unwrap();
+        let decrypted = show_env(dir.path(), options.clone());
+        fs::write(
+            dir.path().join(".stand.toml"),
+            config("auto_decrypt = false"),
+        )
+        .unwrap();
+        let kept = show_env(dir.path(), options);
+
+        env::remove_var("STAND_ACTIVE");
+        env::remove_var("STAND_ENVIRONMENT");
+
+        assert!(decrypted.unwrap().contains("API_KEY=dev-secret\n"));
+        assert!(kept.unwrap().contains(&format!("API_KEY={}\n", encrypted)));
+    }
 }
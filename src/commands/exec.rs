@@ -1,39 +1,55 @@
 // exec.rs command implementation
 
+use crate::commands::export::quote_dotenv_value;
 use crate::config::loader;
-use crate::crypto::decrypt_variables;
-use crate::process::executor::CommandExecutor;
-use anyhow::{anyhow, Result};
-use std::io::{self, IsTerminal, Write};
-use std::path::Path;
+use crate::config::types::{Configuration, Environment};
+use crate::crypto::{decrypt_variables, is_encrypted};
+use crate::environment::resolver::{
+    EnvironmentResolver, ResolutionOptions, SourceKind, UndefinedVariableBehavior, VariableSource,
+};
+use crate::process::executor::{CapturedOutput, CommandExecutor, ExecOutcome};
+use crate::shell::detector::detect_user_shell;
+use crate::utils::colors::format_secret_summary;
+use crate::utils::confirm::ensure_confirmed;
+use crate::utils::paths::find_project_root_from;
+use anyhow::{anyhow, Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-/// Check if stdin is an interactive terminal
-///
-/// Returns false if:
-/// - stdin is not a TTY
-/// - STAND_FORCE_NON_TTY environment variable is set (for testing)
-fn is_interactive_terminal() -> bool {
-    if std::env::var("STAND_FORCE_NON_TTY").is_ok() {
-        return false;
-    }
-    io::stdin().is_terminal()
-}
-
-/// Prompt user for confirmation before executing in a protected environment
-///
-/// Returns true if the user confirms, false otherwise
-fn prompt_confirmation(env_name: &str) -> Result<bool> {
-    print!(
-        "Environment '{}' requires confirmation.\nAre you sure you want to proceed? [y/N]: ",
-        env_name
-    );
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-
-    let response = input.trim().to_lowercase();
-    Ok(response == "y" || response == "yes")
+/// Options for the exec command
+#[derive(Debug, Clone, Default)]
+pub struct ExecOptions {
+    /// Skip confirmation for environments with requires_confirmation=true
+    pub skip_confirmation: bool,
+    /// Start the command with a cleared environment (also enabled by `isolated = true`)
+    pub isolated: bool,
+    /// Read additional dotenv variables from stdin; they override configured values
+    pub stdin_env: bool,
+    /// Run the command in this directory instead of the current one
+    pub cwd: Option<PathBuf>,
+    /// Kill the command after this long and exit with [`TIMEOUT_EXIT_CODE`](crate::process::executor::TIMEOUT_EXIT_CODE)
+    pub timeout: Option<Duration>,
+    /// Write the resolved variables as dotenv to this file (`-` for stderr) before running
+    pub print_env_to: Option<PathBuf>,
+    /// Pass `encrypted:` values to the command as stored instead of decrypting them
+    pub no_decrypt: bool,
+    /// Run the command joined into one string with `$SHELL -c`, so pipes and globs work
+    pub use_shell: bool,
+    /// Re-run a command that exits non-zero or times out up to this many more times
+    pub retry: u32,
+    /// Pause between attempts when retrying
+    pub retry_delay: Duration,
+    /// Only pass the variables written in the environment's own section, without
+    /// `[common]` or `extends`
+    pub no_inherit: bool,
+    /// Find the project root above `project_path` and run the command there
+    /// (ignored when `cwd` is set)
+    pub chdir_to_root: bool,
+    /// Fail before running if a resolved value still contains `${`
+    pub strict: bool,
 }
 
 /// Execute a command with the specified environment
@@ -41,7 +57,7 @@ fn prompt_confirmation(env_name: &str) -> Result<bool> {
 /// # Arguments
 /// * `project_path` - Path to the project directory containing .stand.toml
 /// * `env_name` - Name of the environment to use
-/// * `command` - Command and arguments to execute
+/// * `command` - Command and arguments to execute; empty runs the environment's `default_command`
 /// * `skip_confirmation` - If true, skip confirmation for environments with requires_confirmation=true
 pub fn execute_with_environment(
     project_path: &Path,
@@ -49,37 +65,125 @@ pub fn execute_with_environment(
     command: Vec<String>,
     skip_confirmation: bool,
 ) -> Result<i32> {
+    let options = ExecOptions {
+        skip_confirmation,
+        ..ExecOptions::default()
+    };
+    execute_with_options(project_path, env_name, command, &options)
+}
+
+/// Execute a command with the specified environment and exec options
+///
+/// # Arguments
+/// * `project_path` - Path to the project directory containing .stand.toml
+/// * `env_name` - Name of the environment to use
+/// * `command` - Command and arguments to execute; empty runs the environment's `default_command`
+/// * `options` - Confirmation, isolation, working directory, timeout and retry behavior
+///
+/// With `options.retry`, a failed attempt is announced on stderr and the command is
+/// run again after `options.retry_delay`; the exit code of the last attempt is
+/// returned. Commands terminated by a signal are not retried.
+pub fn execute_with_options(
+    project_path: &Path,
+    env_name: &str,
+    command: Vec<String>,
+    options: &ExecOptions,
+) -> Result<i32> {
+    let executor = build_executor(project_path, env_name, command, options)?;
+    let attempts = options.retry.saturating_add(1);
+
+    let mut attempt = 1;
+    loop {
+        let outcome = executor.clone().execute_detailed()?;
+        if outcome == ExecOutcome::Timeout {
+            if let Some(timeout) = options.timeout {
+                eprintln!("Error: Command timed out after {}s", timeout.as_secs_f64());
+            }
+        }
+
+        let retryable = matches!(outcome, ExecOutcome::Exited(code) if code != 0)
+            || outcome == ExecOutcome::Timeout;
+        if !retryable || attempt >= attempts {
+            return Ok(outcome.exit_code());
+        }
+        eprintln!(
+            "Attempt {}/{} failed with exit code {}; retrying in {}s",
+            attempt,
+            attempts,
+            outcome.exit_code(),
+            options.retry_delay.as_secs_f64()
+        );
+        std::thread::sleep(options.retry_delay);
+        attempt += 1;
+    }
+}
+
+/// Execute a command like [`execute_with_options`] and capture its stdout and stderr
+///
+/// The command's output is returned instead of being written to the terminal, and
+/// it does not read from stdin (`options.stdin_env` still reads Stand's stdin).
+/// A timed-out command reports [`TIMEOUT_EXIT_CODE`](crate::process::executor::TIMEOUT_EXIT_CODE).
+pub fn execute_captured_with_options(
+    project_path: &Path,
+    env_name: &str,
+    command: Vec<String>,
+    options: &ExecOptions,
+) -> Result<CapturedOutput> {
+    build_executor(project_path, env_name, command, options)?.execute_captured()
+}
+
+/// Load and decrypt the environment and set up the executor for `command`
+pub(crate) fn build_executor(
+    project_path: &Path,
+    env_name: &str,
+    command: Vec<String>,
+    options: &ExecOptions,
+) -> Result<CommandExecutor> {
+    let project_root;
+    let project_path = if options.chdir_to_root {
+        project_root = find_project_root_from(project_path)?;
+        project_root.as_path()
+    } else {
+        project_path
+    };
+
     // Load configuration with inheritance applied
     let config = loader::load_config_toml_with_inheritance(project_path)?;
 
     // Check if environment exists
-    let env = config.environments.get(env_name).ok_or_else(|| {
-        let mut available: Vec<_> = config.environments.keys().cloned().collect();
-        available.sort();
-        anyhow!(
-            "Environment '{}' not found. Available: {}",
-            env_name,
-            available.join(", ")
-        )
-    })?;
+    let env = find_environment(&config, env_name)?;
+    // Settings such as requires_confirmation still follow `extends` with --no-inherit
+    let raw_config;
+    let variables = if options.no_inherit {
+        raw_config = loader::load_config_toml(project_path)?;
+        &find_environment(&raw_config, env_name)?.variables
+    } else {
+        &env.variables
+    };
 
     // Check if confirmation is required
-    if env.requires_confirmation.unwrap_or(false) && !skip_confirmation {
-        // Check if stdin is a terminal - fail fast in non-interactive environments
-        if !is_interactive_terminal() {
-            return Err(anyhow!(
-                "Environment '{}' requires confirmation but stdin is not a terminal.\n\
-                 Use -y or --yes to skip confirmation in non-interactive environments.",
-                env_name
-            ));
-        }
-        // Prompt user for confirmation
-        if !prompt_confirmation(env_name)? {
-            return Err(anyhow!(
-                "Execution cancelled. Use -y or --yes to skip confirmation."
-            ));
+    ensure_confirmed(
+        env_name,
+        env.requires_confirmation.unwrap_or(false),
+        options.skip_confirmation,
+    )?;
+
+    // Fall back to the environment's default_command when no command is given
+    let command = match (command.is_empty(), &env.default_command) {
+        (false, _) if options.use_shell => shell_command(command.join(" ")),
+        (false, _) => command,
+        (true, Some(default_command)) if options.use_shell => {
+            shell_command(default_command.clone())
         }
-    }
+        (true, Some(default_command)) => shell_words::split(default_command).map_err(|e| {
+            anyhow!(
+                "Invalid default_command for environment '{}': {}",
+                env_name,
+                e
+            )
+        })?,
+        (true, None) => Vec::new(),
+    };
 
     // Validate command is not empty
     if command.is_empty() {
@@ -91,11 +195,132 @@ pub fn execute_with_environment(
     let args = command[1..].to_vec();
 
     // Decrypt any encrypted variables
-    let decrypted_vars = decrypt_variables(env.variables.clone(), project_path)
-        .map_err(|e| anyhow!("Failed to decrypt variables: {}", e))?;
+    let decrypted_vars = if options.no_decrypt {
+        variables.clone()
+    } else {
+        decrypt_variables(variables.clone(), project_path)
+            .map_err(|e| anyhow!("Failed to decrypt variables: {}", e))?
+    };
 
     // Execute command with environment variables
-    let executor = CommandExecutor::new(program, args).with_env(decrypted_vars);
+    let decrypted_vars = if options.stdin_env {
+        merge_stdin_variables(decrypted_vars)?
+    } else {
+        decrypted_vars
+    };
+
+    if options.strict {
+        EnvironmentResolver::ensure_fully_expanded(&decrypted_vars)?;
+    }
+
+    if let Some(target) = &options.print_env_to {
+        let dump = format_env_dump(env_name, &decrypted_vars, |key| {
+            variables.get(key).is_some_and(|value| is_encrypted(value))
+        });
+        write_env_dump(target, &dump)?;
+    }
+
+    let isolated = options.isolated || env.isolated.unwrap_or(false);
+    let mut executor = CommandExecutor::new(program, args)
+        .with_env(decrypted_vars)
+        .with_clear_env(isolated)
+        .with_signal_forwarding(true);
+    if let Some(dir) = &options.cwd {
+        executor = executor.with_current_dir(dir);
+    } else if options.chdir_to_root {
+        executor = executor.with_current_dir(project_path);
+    }
+    if let Some(timeout) = options.timeout {
+        executor = executor.with_timeout(timeout);
+    }
+
+    Ok(executor)
+}
 
-    executor.execute()
+/// Look up `env_name`, listing the available environments if it does not exist
+fn find_environment<'a>(config: &'a Configuration, env_name: &str) -> Result<&'a Environment> {
+    config.environments.get(env_name).ok_or_else(|| {
+        let mut available: Vec<_> = config.environments.keys().cloned().collect();
+        available.sort();
+        anyhow!(
+            "Environment '{}' not found. Available: {}",
+            env_name,
+            available.join(", ")
+        )
+    })
+}
+
+/// Wrap `script` as `<user shell> -c <script>`
+fn shell_command(script: String) -> Vec<String> {
+    vec![detect_user_shell(), "-c".to_string(), script]
+}
+
+/// Format the variables a command will receive as dotenv, sorted by key
+///
+/// Values for which `was_encrypted` returns true are masked.
+fn format_env_dump(
+    env_name: &str,
+    variables: &HashMap<String, String>,
+    was_encrypted: impl Fn(&str) -> bool,
+) -> String {
+    let sorted: BTreeMap<_, _> = variables.iter().collect();
+
+    let mut output = format!("# Resolved Stand environment '{}'\n", env_name);
+    for (key, value) in sorted {
+        let line = if was_encrypted(key) {
+            format_secret_summary(key, value)
+        } else {
+            format!("{}={}", key, quote_dotenv_value(value))
+        };
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Write an environment dump to `target`, or to stderr when it is `-`
+fn write_env_dump(target: &Path, dump: &str) -> Result<()> {
+    if target == Path::new("-") {
+        io::stderr()
+            .write_all(dump.as_bytes())
+            .context("Failed to write the environment to stderr")
+    } else {
+        fs::write(target, dump)
+            .with_context(|| format!("Failed to write the environment to {}", target.display()))
+    }
+}
+
+/// Layer dotenv variables read from stdin over the configured variables
+///
+/// Only the stdin values are expanded; they may reference configured variables.
+/// Configured values are already interpolated and are kept exactly as they are.
+fn merge_stdin_variables(
+    mut variables: HashMap<String, String>,
+) -> Result<HashMap<String, String>> {
+    let mut resolver = EnvironmentResolver::new();
+    // Escape `$` so a configured value pulled into a stdin value by reference stays
+    // literal, e.g. `${X}` written as `$${X}` in .stand.toml
+    let configured = variables
+        .iter()
+        .map(|(key, value)| (key.clone(), value.replace('$', "$$")))
+        .collect();
+    resolver.add_source(VariableSource::Default(configured));
+    resolver.add_source(VariableSource::Stdin);
+
+    let mut resolved = resolver.resolve_with_options(&ResolutionOptions {
+        undefined_variable_behavior: UndefinedVariableBehavior::LeaveUnexpanded,
+        // stdin overrides the configured values
+        priority: vec![SourceKind::Default, SourceKind::Stdin],
+        ..ResolutionOptions::default()
+    })?;
+
+    for key in resolver
+        .load_source_variables(&VariableSource::Stdin)?
+        .keys()
+    {
+        if let Some(value) = resolved.swap_remove(key) {
+            variables.insert(key.clone(), value);
+        }
+    }
+    Ok(variables)
 }
@@ -0,0 +1,226 @@
+// export.rs command implementation
+//
+// Write a resolved, decrypted environment to a dotenv file.
+
+use crate::commands::init::set_secure_permissions;
+use crate::config::loader;
+use crate::crypto::decrypt_variables;
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Export an environment to a `.env` file
+///
+/// Resolves common and inherited variables, decrypts encrypted values and writes one
+/// `KEY=value` line per variable, sorted by key. The file is created with 0600
+/// permissions on Unix because it contains decrypted secrets.
+///
+/// # Arguments
+/// * `project_dir` - Directory containing .stand.toml
+/// * `env_name` - Environment to export
+/// * `output_path` - Destination file
+/// * `force` - Overwrite `output_path` if it already exists
+///
+/// Returns the number of variables written.
+pub fn export_to_file(
+    project_dir: &Path,
+    env_name: &str,
+    output_path: &Path,
+    force: bool,
+) -> Result<usize> {
+    if output_path.exists() && !force {
+        bail!(
+            "{} already exists. Use --force to overwrite it.",
+            output_path.display()
+        );
+    }
+
+    let config = loader::load_config_toml_with_inheritance(project_dir)?;
+    let env = config.environments.get(env_name).ok_or_else(|| {
+        let mut available: Vec<_> = config.environments.keys().cloned().collect();
+        available.sort();
+        anyhow!(
+            "Environment '{}' not found. Available: {}",
+            env_name,
+            available.join(", ")
+        )
+    })?;
+
+    let variables = decrypt_variables(env.variables.clone(), project_dir)
+        .map_err(|e| anyhow!("Failed to decrypt variables: {}", e))?;
+
+    fs::write(output_path, format_dotenv(env_name, &variables))
+        .with_context(|| format!("Failed to write {}", output_path.display()))?;
+    set_secure_permissions(output_path)?;
+
+    Ok(variables.len())
+}
+
+/// Format variables as dotenv content, sorted by key
+fn format_dotenv(env_name: &str, variables: &HashMap<String, String>) -> String {
+    let sorted: BTreeMap<_, _> = variables.iter().collect();
+
+    let mut output = format!("# Exported from Stand environment '{}'\n", env_name);
+    for (key, value) in sorted {
+        output.push_str(&format!("{}={}\n", key, quote_dotenv_value(value)));
+    }
+    output
+}
+
+/// Quote a value so it reads back unchanged through the dotenv parser
+///
+/// Plain values are written bare. Values with whitespace, `#`, `$`, quotes, backslashes
+/// or line breaks are double-quoted with `\\`, `\"`, `\$`, `\n`, `\r` and `\t` escapes,
+/// so that `$` is never expanded when the file is read back.
+pub(crate) fn quote_dotenv_value(value: &str) -> String {
+    let needs_quotes = value
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '#' | '$' | '"' | '\'' | '\\' | '='));
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '$' => quoted.push_str("\\$"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            other => quoted.push(other),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::loader::load_env_file;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"
+version = "2.0"
+
+[common]
+APP_NAME = "demo"
+
+[environments.dev]
+description = "Development"
+PLAIN = "value"
+EMPTY = ""
+WITH_SPACES = "hello world"
+WITH_HASH = "abc#def"
+WITH_QUOTES = "say \"hi\" and 'bye'"
+WITH_BACKSLASH = 'C:\path\to'
+MULTILINE = """
+line one
+line two"""
+WITH_EQUALS = "a=b=c"
+PADDED = "  padded  "
+UNICODE = "日本語"
+BCRYPT = '$2b$10$abcdef'
+LITERAL_PLACEHOLDER = "$${USER} costs $5"
+
+[environments.copy]
+description = "Imported from dev"
+"#;
+
+    #[test]
+    fn test_export_round_trips_through_parser() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        let output = dir.path().join("dev.env");
+
+        let count = export_to_file(dir.path(), "dev", &output, false).unwrap();
+
+        let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+        let expected: BTreeMap<String, String> = config.environments["dev"]
+            .variables
+            .clone()
+            .into_iter()
+            .collect();
+        let parsed: BTreeMap<String, String> =
+            load_env_file(&output).unwrap().into_iter().collect();
+
+        assert_eq!(count, expected.len());
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed["APP_NAME"], "demo");
+    }
+
+    #[test]
+    fn test_export_refuses_to_overwrite_without_force() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        let output = dir.path().join("dev.env");
+        fs::write(&output, "KEEP=me\n").unwrap();
+
+        let result = export_to_file(dir.path(), "dev", &output, false);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("--force"));
+        assert_eq!(fs::read_to_string(&output).unwrap(), "KEEP=me\n");
+
+        export_to_file(dir.path(), "dev", &output, true).unwrap();
+        assert!(fs::read_to_string(&output).unwrap().contains("PLAIN=value"));
+    }
+
+    #[test]
+    fn test_export_nonexistent_environment() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let result = export_to_file(dir.path(), "prod", &dir.path().join("prod.env"), false);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not found"));
+        assert!(!dir.path().join("prod.env").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_export_sets_secure_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        let output = dir.path().join("dev.env");
+
+        export_to_file(dir.path(), "dev", &output, false).unwrap();
+
+        let mode = fs::metadata(&output).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_quote_dotenv_value() {
+        assert_eq!(quote_dotenv_value("simple"), "simple");
+        assert_eq!(quote_dotenv_value("a b"), "\"a b\"");
+        assert_eq!(quote_dotenv_value("a\nb"), "\"a\\nb\"");
+        assert_eq!(quote_dotenv_value("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_dotenv_value("$2b$10"), "\"\\$2b\\$10\"");
+    }
+
+    #[test]
+    fn test_export_then_import_keeps_values() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        let output = dir.path().join("dev.env");
+
+        export_to_file(dir.path(), "dev", &output, false).unwrap();
+        crate::commands::import::import_env_file(dir.path(), "copy", &output, false, false)
+            .unwrap();
+
+        let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+        let dev = &config.environments["dev"].variables;
+        let copy = &config.environments["copy"].variables;
+        assert_eq!(copy["BCRYPT"], "$2b$10$abcdef");
+        assert_eq!(copy["LITERAL_PLACEHOLDER"], "${USER} costs $5");
+        assert_eq!(copy, dev);
+    }
+}
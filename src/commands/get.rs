@@ -2,10 +2,24 @@
 //!
 //! Retrieves a variable value from the configuration, decrypting if necessary.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use crate::config::{loader, ConfigError};
-use crate::crypto::{decrypt_value, is_encrypted, load_private_key_for_decryption, CryptoError};
+use crate::crypto::{
+    decrypt_value, decrypt_variables, is_encrypted, load_private_key_for_decryption, CryptoError,
+};
+use crate::utils::colors::{mask_value_with_options, MaskOptions};
+
+/// Options for `stand get`.
+#[derive(Debug, Clone, Default)]
+pub struct GetOptions {
+    /// Only look at the environment's own variables (no `[common]` or `extends`)
+    pub raw: bool,
+    /// Mask values in [`get_all_variables`], revealing `settings.mask_visible_chars`
+    /// leading characters
+    pub mask: bool,
+}
 
 /// Get a variable value from the configuration.
 ///
@@ -16,8 +30,28 @@ pub fn get_variable(
     environment: &str,
     key: &str,
 ) -> Result<String, GetCommandError> {
-    // Load configuration with inheritance applied (common + extends)
-    let config = loader::load_config_toml_with_inheritance(project_dir)?;
+    get_variable_with_options(project_dir, environment, key, &GetOptions::default())
+}
+
+/// Get a variable value with explicit options.
+///
+/// With `options.raw`, keys defined only in `[common]` or a parent environment
+/// return `VariableNotFound`.
+pub fn get_variable_with_options(
+    project_dir: &Path,
+    environment: &str,
+    key: &str,
+    options: &GetOptions,
+) -> Result<String, GetCommandError> {
+    let config = if options.raw {
+        loader::load_config_toml(project_dir)?
+    } else {
+        // Load configuration with inheritance applied (common + extends)
+        loader::load_config_toml_with_inheritance(project_dir)?
+    };
+    if config.encryption.is_some() {
+        crate::commands::encrypt::warn_keys_file_security(project_dir);
+    }
 
     // Find the environment
     let env = config
@@ -42,6 +76,47 @@ pub fn get_variable(
     }
 }
 
+/// Get every variable of an environment as `KEY=VALUE` lines, sorted by key.
+///
+/// Values are decrypted and printed as is, without quoting, so the output is meant
+/// for scripts rather than for sourcing; use `stand export` for the latter.
+pub fn get_all_variables(
+    project_dir: &Path,
+    environment: &str,
+    options: &GetOptions,
+) -> Result<String, GetCommandError> {
+    let config = if options.raw {
+        loader::load_config_toml(project_dir)?
+    } else {
+        loader::load_config_toml_with_inheritance(project_dir)?
+    };
+    if config.encryption.is_some() {
+        crate::commands::encrypt::warn_keys_file_security(project_dir);
+    }
+
+    let env = config
+        .environments
+        .get(environment)
+        .ok_or_else(|| GetCommandError::EnvironmentNotFound(environment.to_string()))?;
+    let variables: BTreeMap<_, _> = decrypt_variables(env.variables.clone(), project_dir)?
+        .into_iter()
+        .collect();
+
+    let mask = options.mask.then(|| MaskOptions {
+        visible_prefix: config.settings.mask_visible_chars.unwrap_or(0),
+        ..MaskOptions::default()
+    });
+    let mut output = String::new();
+    for (key, value) in &variables {
+        let value = match &mask {
+            Some(mask) => mask_value_with_options(value, mask),
+            None => value.clone(),
+        };
+        output.push_str(&format!("{}={}\n", key, value));
+    }
+    Ok(output)
+}
+
 /// Error type for get command.
 #[derive(Debug, thiserror::Error)]
 pub enum GetCommandError {
@@ -234,4 +309,115 @@ DEV_ONLY = "dev-only-value"
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "dev-only-value");
     }
+
+    #[test]
+    fn test_get_variable_raw_ignores_parent_and_common() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            r#"version = "1.0"
+
+[common]
+SHARED_VALUE = "common-value"
+
+[environments.base]
+description = "Base environment"
+BASE_URL = "https://base.example.com"
+
+[environments.dev]
+description = "Development"
+extends = "base"
+DEV_ONLY = "dev-only-value"
+"#,
+        )
+        .unwrap();
+        let raw = GetOptions {
+            raw: true,
+            ..GetOptions::default()
+        };
+
+        for key in ["BASE_URL", "SHARED_VALUE"] {
+            assert!(get_variable(dir.path(), "dev", key).is_ok());
+            assert!(matches!(
+                get_variable_with_options(dir.path(), "dev", key, &raw),
+                Err(GetCommandError::VariableNotFound(_))
+            ));
+        }
+        assert_eq!(
+            get_variable_with_options(dir.path(), "dev", "DEV_ONLY", &raw).unwrap(),
+            "dev-only-value"
+        );
+    }
+
+    #[test]
+    fn test_get_variable_errors_do_not_leak_secret() {
+        let dir = tempdir().unwrap();
+        let secret = "s3cr3t-api-key";
+        let key_pair = crate::crypto::keys::generate_key_pair();
+        let encrypted =
+            crate::crypto::encrypt_value(secret, &key_pair.to_recipient().unwrap()).unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            format!(
+                "version = \"1.0\"\n\n[encryption]\npublic_key = \"{}\"\n\n\
+                 [environments.dev]\ndescription = \"Development\"\nAPI_KEY = \"{}\"\n\
+                 PLAIN = \"encrypted:{}\"\n",
+                key_pair.public_key, encrypted, secret
+            ),
+        )
+        .unwrap();
+        // A key that cannot decrypt the value
+        crate::crypto::keys::save_private_key(
+            &dir.path().join(".stand.keys"),
+            &crate::crypto::keys::generate_key_pair().private_key,
+        )
+        .unwrap();
+
+        for key in ["API_KEY", "PLAIN"] {
+            let err = get_variable(dir.path(), "dev", key).unwrap_err();
+            crate::crypto::assert_error_hides_secret(&err, secret);
+        }
+    }
+
+    #[test]
+    fn test_get_all_variables() {
+        let dir = tempdir().unwrap();
+        let key_pair = crate::crypto::keys::generate_key_pair();
+        crate::crypto::keys::save_private_key(
+            &dir.path().join(".stand.keys"),
+            &key_pair.private_key,
+        )
+        .unwrap();
+        let encrypted =
+            crate::crypto::encrypt_value("secret-api-key", &key_pair.to_recipient().unwrap())
+                .unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            format!(
+                "version = \"1.0\"\n\n[encryption]\npublic_key = \"{}\"\n\n\
+                 [common]\nAPP_NAME = \"demo\"\n\n\
+                 [environments.dev]\ndescription = \"Development\"\nAPI_KEY = \"{}\"\n\
+                 API_URL = \"https://api.example.com\"\n",
+                key_pair.public_key, encrypted
+            ),
+        )
+        .unwrap();
+
+        let output = get_all_variables(dir.path(), "dev", &GetOptions::default()).unwrap();
+        assert_eq!(
+            output,
+            "API_KEY=secret-api-key\nAPI_URL=https://api.example.com\nAPP_NAME=demo\n"
+        );
+
+        let masked = GetOptions {
+            mask: true,
+            ..GetOptions::default()
+        };
+        let output = get_all_variables(dir.path(), "dev", &masked).unwrap();
+        assert_eq!(
+            output,
+            "API_KEY=********\nAPI_URL=********\nAPP_NAME=********\n"
+        );
+        assert!(!output.contains("secret"));
+    }
 }
@@ -0,0 +1,94 @@
+// history.rs command implementation
+//
+// Show the recorded environment switches for the project.
+
+use crate::state::history::load_history_from;
+use crate::utils::paths::find_project_root_from;
+use anyhow::Result;
+use std::path::Path;
+
+/// Number of entries shown when `--limit` is not given
+pub const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// Format the newest `limit` history entries, oldest first
+///
+/// # Arguments
+/// * `project_path` - Path inside the project directory containing .stand.toml
+/// * `limit` - Maximum number of entries to show
+pub fn show_history(project_path: &Path, limit: usize) -> Result<String> {
+    let project_root = find_project_root_from(project_path)?;
+    let entries = load_history_from(&project_root)?;
+
+    if entries.is_empty() {
+        return Ok("No environment switches recorded yet".to_string());
+    }
+
+    let start = entries.len().saturating_sub(limit);
+    let lines: Vec<String> = entries[start..]
+        .iter()
+        .map(|entry| format!("{}  {}", entry.format_timestamp(), entry.environment))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::switch::switch_environment;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+
+[environments.staging]
+description = "Staging environment"
+
+[environments.prod]
+description = "Production environment"
+"#;
+
+    fn environments(output: &str) -> Vec<&str> {
+        output
+            .lines()
+            .map(|line| line.rsplit("  ").next().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_history_lists_switches_in_order() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        for env_name in ["dev", "staging", "prod", "dev"] {
+            switch_environment(dir.path(), env_name, true).unwrap();
+        }
+
+        let output = show_history(dir.path(), DEFAULT_HISTORY_LIMIT).unwrap();
+        assert_eq!(environments(&output), vec!["dev", "staging", "prod", "dev"]);
+        assert!(output.lines().all(|line| line.contains(" UTC  ")));
+
+        let output = show_history(dir.path(), 2).unwrap();
+        assert_eq!(environments(&output), vec!["prod", "dev"]);
+    }
+
+    #[test]
+    fn test_history_ignores_failed_switches() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        assert_eq!(
+            show_history(dir.path(), DEFAULT_HISTORY_LIMIT).unwrap(),
+            "No environment switches recorded yet"
+        );
+
+        switch_environment(dir.path(), "dev", true).unwrap();
+        assert!(switch_environment(dir.path(), "missing", true).is_err());
+
+        let output = show_history(dir.path(), DEFAULT_HISTORY_LIMIT).unwrap();
+        assert_eq!(environments(&output), vec!["dev"]);
+    }
+}
@@ -0,0 +1,269 @@
+//! Import command implementation.
+//!
+//! Bulk-loads the variables of a `.env` file into an environment of the configuration.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::utils::write_atomic;
+use toml_edit::DocumentMut;
+
+use crate::config::types::ENVIRONMENT_META_KEYS;
+use crate::config::{loader, ConfigError};
+use crate::crypto::{encrypt_value_for_recipients, CryptoError};
+use crate::environment::loader::{load_env_file, LoadError};
+
+/// Outcome of importing a `.env` file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ImportSummary {
+    /// Keys that were newly added to the environment
+    pub imported: Vec<String>,
+    /// Existing keys that were replaced (only with `overwrite`)
+    pub overwritten: Vec<String>,
+    /// Existing keys that were left untouched
+    pub skipped: Vec<String>,
+}
+
+/// Import every variable of a `.env` file into `[environments.<environment>]`.
+///
+/// Keys that already exist in the environment are skipped unless `overwrite` is true.
+/// A file containing an environment property such as `isolated` is rejected before
+/// anything is written.
+/// If `encrypt` is true, each value is encrypted with the configured public key;
+/// otherwise `$` is written as `$$` so `${...}` in a value is not interpolated.
+/// Uses toml_edit so comments and formatting of the configuration are preserved.
+pub fn import_env_file(
+    project_dir: &Path,
+    environment: &str,
+    file_path: &Path,
+    encrypt: bool,
+    overwrite: bool,
+) -> Result<ImportSummary, ImportCommandError> {
+    let config_path = project_dir.join(".stand.toml");
+    let config = loader::load_config_toml(project_dir)?;
+
+    if !config.environments.contains_key(environment) {
+        return Err(ImportCommandError::EnvironmentNotFound(
+            environment.to_string(),
+        ));
+    }
+
+    let recipients = if encrypt {
+        let encryption = config
+            .encryption
+            .as_ref()
+            .ok_or(ImportCommandError::EncryptionNotEnabled)?;
+        Some(encryption.all_recipients()?)
+    } else {
+        None
+    };
+
+    let variables = load_env_file(file_path)?;
+    if let Some(key) = variables
+        .keys()
+        .find(|key| ENVIRONMENT_META_KEYS.contains(&key.as_str()))
+    {
+        return Err(ImportCommandError::NotAVariable(key.clone()));
+    }
+
+    let content = fs::read_to_string(&config_path)?;
+    let mut doc: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| ImportCommandError::TomlParse(e.to_string()))?;
+
+    let env_table = doc
+        .get_mut("environments")
+        .and_then(|e| e.get_mut(environment))
+        .and_then(|e| e.as_table_mut())
+        .ok_or_else(|| ImportCommandError::EnvironmentNotFound(environment.to_string()))?;
+
+    let mut summary = ImportSummary::default();
+    for (key, value) in variables {
+        if env_table.contains_key(&key) {
+            if !overwrite {
+                summary.skipped.push(key);
+                continue;
+            }
+            summary.overwritten.push(key.clone());
+        } else {
+            summary.imported.push(key.clone());
+        }
+
+        let final_value = match &recipients {
+            Some(recipients) => encrypt_value_for_recipients(&value, recipients)?,
+            // Escape `$` so interpolation keeps the imported value literal
+            None => value.replace('$', "$$"),
+        };
+        env_table.insert(&key, toml_edit::value(final_value));
+    }
+
+    write_atomic(&config_path, doc.to_string())?;
+
+    Ok(summary)
+}
+
+/// Error type for import command.
+#[derive(Debug, thiserror::Error)]
+pub enum ImportCommandError {
+    #[error("Environment not found: {0}")]
+    EnvironmentNotFound(String),
+
+    #[error("Encryption is not enabled. Run 'stand encrypt enable' first.")]
+    EncryptionNotEnabled,
+
+    #[error("'{0}' is an environment property, not a variable; remove it from the file")]
+    NotAVariable(String),
+
+    #[error("Failed to read env file: {0}")]
+    Load(#[from] LoadError),
+
+    #[error("Cryptographic error: {0}")]
+    Crypto(#[from] CryptoError),
+
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("TOML parsing error: {0}")]
+    TomlParse(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"version = "2.0"
+
+# Development settings
+[environments.dev]
+description = "Development"
+EXISTING = "keep-me"
+"#;
+
+    const ENV_FILE: &str = r#"# Exported from the legacy setup
+DATABASE_URL=postgres://localhost:5432/dev
+API_KEY="abc123"
+EXISTING=replaced
+GREETING="line one
+line two"
+"#;
+
+    fn setup(dir: &Path) -> std::path::PathBuf {
+        fs::write(dir.join(".stand.toml"), CONFIG).unwrap();
+        let env_file = dir.join("legacy.env");
+        fs::write(&env_file, ENV_FILE).unwrap();
+        env_file
+    }
+
+    #[test]
+    fn test_import_adds_all_keys_and_skips_existing() {
+        let dir = tempdir().unwrap();
+        let env_file = setup(dir.path());
+
+        let summary = import_env_file(dir.path(), "dev", &env_file, false, false).unwrap();
+
+        assert_eq!(
+            summary.imported,
+            vec!["DATABASE_URL", "API_KEY", "GREETING"]
+        );
+        assert_eq!(summary.skipped, vec!["EXISTING"]);
+        assert!(summary.overwritten.is_empty());
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        let vars = &config.environments["dev"].variables;
+        assert_eq!(vars["DATABASE_URL"], "postgres://localhost:5432/dev");
+        assert_eq!(vars["API_KEY"], "abc123");
+        assert_eq!(vars["GREETING"], "line one\nline two");
+        assert_eq!(vars["EXISTING"], "keep-me");
+
+        // Comments are preserved
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert!(content.contains("# Development settings"));
+    }
+
+    #[test]
+    fn test_import_overwrite_replaces_existing() {
+        let dir = tempdir().unwrap();
+        let env_file = setup(dir.path());
+
+        let summary = import_env_file(dir.path(), "dev", &env_file, false, true).unwrap();
+
+        assert_eq!(summary.overwritten, vec!["EXISTING"]);
+        assert!(summary.skipped.is_empty());
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        assert_eq!(config.environments["dev"].variables["EXISTING"], "replaced");
+    }
+
+    #[test]
+    fn test_import_rejects_environment_properties() {
+        let dir = tempdir().unwrap();
+        setup(dir.path());
+        let env_file = dir.path().join("props.env");
+        fs::write(&env_file, "NEW_VAR=value\nisolated=true\n").unwrap();
+        let before = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+
+        let result = import_env_file(dir.path(), "dev", &env_file, false, true);
+
+        match result {
+            Err(ImportCommandError::NotAVariable(key)) => assert_eq!(key, "isolated"),
+            other => panic!("expected NotAVariable, got {:?}", other),
+        }
+        // Nothing is written, so the configuration still loads
+        let after = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert_eq!(after, before);
+        assert!(loader::load_config_toml(dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_import_env_not_found() {
+        let dir = tempdir().unwrap();
+        let env_file = setup(dir.path());
+
+        let result = import_env_file(dir.path(), "prod", &env_file, false, false);
+
+        assert!(matches!(
+            result,
+            Err(ImportCommandError::EnvironmentNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_encrypt_requires_encryption() {
+        let dir = tempdir().unwrap();
+        let env_file = setup(dir.path());
+
+        let result = import_env_file(dir.path(), "dev", &env_file, true, false);
+
+        assert!(matches!(
+            result,
+            Err(ImportCommandError::EncryptionNotEnabled)
+        ));
+    }
+
+    #[test]
+    fn test_import_encrypted_values() {
+        let dir = tempdir().unwrap();
+        let key_pair = crate::crypto::keys::generate_key_pair();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            format!(
+                "version = \"2.0\"\n\n[encryption]\npublic_key = \"{}\"\n\n[environments.dev]\ndescription = \"Development\"\n",
+                key_pair.public_key
+            ),
+        )
+        .unwrap();
+        let env_file = dir.path().join("secrets.env");
+        fs::write(&env_file, "API_KEY=super-secret\n").unwrap();
+
+        import_env_file(dir.path(), "dev", &env_file, true, false).unwrap();
+
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert!(content.contains("encrypted:"));
+        assert!(!content.contains("super-secret"));
+    }
+}
@@ -1,5 +1,9 @@
 // init.rs - Initialize Stand configuration
 
+use crate::commands::encrypt::add_to_gitignore;
+use crate::commands::rename::is_valid_environment_name;
+use crate::commands::CommandContext;
+use crate::config::loader::LOCAL_CONFIG_FILE;
 use anyhow::{bail, Context, Result};
 use std::fs;
 use std::path::Path;
@@ -15,6 +19,42 @@ use std::path::Path;
 /// - .stand.toml already exists and force is false
 /// - Failed to write the configuration file
 pub fn handle_init(current_dir: &Path, force: bool) -> Result<()> {
+    handle_init_with_context(current_dir, force, &CommandContext::default())
+}
+
+/// Handle the init command, printing the success message and next steps unless
+/// `ctx` is quiet
+pub fn handle_init_with_context(
+    current_dir: &Path,
+    force: bool,
+    ctx: &CommandContext,
+) -> Result<()> {
+    handle_init_with_environments(current_dir, force, &[], ctx)
+}
+
+/// Handle the init command with the given environments instead of dev/prod
+///
+/// An empty `environments` writes the default template. Otherwise the template has
+/// exactly these environments, in order, and the first one becomes
+/// `settings.default_environment`.
+pub fn handle_init_with_environments(
+    current_dir: &Path,
+    force: bool,
+    environments: &[String],
+    ctx: &CommandContext,
+) -> Result<()> {
+    for (i, name) in environments.iter().enumerate() {
+        if !is_valid_environment_name(name) {
+            bail!(
+                "Invalid environment name '{}': use letters, digits, '-' or '_'",
+                name
+            );
+        }
+        if environments[..i].contains(name) {
+            bail!("Environment '{}' is listed more than once", name);
+        }
+    }
+
     let config_path = current_dir.join(".stand.toml");
 
     // Check if config already exists
@@ -27,18 +67,33 @@ pub fn handle_init(current_dir: &Path, force: bool) -> Result<()> {
     }
 
     // Generate and write template
-    let template = generate_default_template();
+    let template = if environments.is_empty() {
+        generate_default_template()
+    } else {
+        generate_template(environments)
+    };
     fs::write(&config_path, &template)
         .with_context(|| format!("Failed to write .stand.toml to {}", config_path.display()))?;
 
     // Set secure permissions (0600) on Unix systems
     set_secure_permissions(&config_path)?;
 
+    // Keep machine-specific overrides out of version control
+    let gitignore_updated =
+        add_to_gitignore(current_dir, LOCAL_CONFIG_FILE).context("Failed to update .gitignore")?;
+
+    if ctx.is_quiet() {
+        return Ok(());
+    }
+
     if existed {
         println!("✓ Overwritten existing .stand.toml");
     } else {
         println!("✓ Created .stand.toml");
     }
+    if gitignore_updated {
+        println!("✓ Added {} to .gitignore", LOCAL_CONFIG_FILE);
+    }
 
     println!("\nNext steps:");
     println!("  1. Edit .stand.toml to add your environment variables");
@@ -49,7 +104,7 @@ pub fn handle_init(current_dir: &Path, force: bool) -> Result<()> {
 }
 
 /// Set secure file permissions (0600) for configuration files
-fn set_secure_permissions(path: &Path) -> Result<()> {
+pub(crate) fn set_secure_permissions(path: &Path) -> Result<()> {
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
@@ -103,6 +158,54 @@ requires_confirmation = true
     .to_string()
 }
 
+/// Colors assigned to `stand init --env` environments, in turn
+const TEMPLATE_COLORS: &[&str] = &["green", "blue", "yellow", "purple", "cyan", "red"];
+
+/// Generate a .stand.toml template with the given environments
+///
+/// Same layout as [`generate_default_template`], with one table per environment,
+/// colors taken from [`TEMPLATE_COLORS`] in turn, and the first environment as
+/// `settings.default_environment`. Names must already be validated.
+fn generate_template(environments: &[String]) -> String {
+    let mut template = format!(
+        r#"version = "2.0"
+
+# Common variables shared across all environments
+# Uncomment and add your shared variables here:
+# [common]
+# APP_NAME = "MyApp"
+
+[settings]
+# Environment used when none is specified
+default_environment = "{}"
+"#,
+        environments[0]
+    );
+
+    for (i, name) in environments.iter().enumerate() {
+        template.push_str(&format!(
+            r#"
+# {name} environment
+[environments.{name}]
+description = "{name} environment"
+"#
+        ));
+        if i == 0 {
+            template
+                .push_str("# Display color in terminal (red, green, blue, yellow, purple, cyan)\n");
+        }
+        template.push_str(&format!(
+            r#"color = "{}"
+# Add your environment variables here:
+# DATABASE_URL = "postgres://localhost/myapp_{}"
+"#,
+            TEMPLATE_COLORS[i % TEMPLATE_COLORS.len()],
+            name.replace('-', "_")
+        ));
+    }
+    template
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,6 +280,25 @@ mod tests {
         assert!(content.contains("[environments.prod]"));
     }
 
+    #[test]
+    fn test_init_gitignores_local_config() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+
+        handle_init(dir.path(), false).unwrap();
+        handle_init(dir.path(), true).unwrap();
+
+        let gitignore = fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert!(gitignore.starts_with("target/\n"));
+        assert_eq!(
+            gitignore
+                .lines()
+                .filter(|line| *line == ".stand.local.toml")
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn test_init_fails_when_config_exists_without_force() {
         let dir = tempdir().unwrap();
@@ -226,4 +348,62 @@ mod tests {
             "Config file should have 0600 permissions"
         );
     }
+
+    fn init_with(environments: &[&str]) -> Result<crate::config::types::Configuration> {
+        let dir = tempdir().unwrap();
+        let environments: Vec<String> = environments.iter().map(|s| s.to_string()).collect();
+        handle_init_with_environments(
+            dir.path(),
+            false,
+            &environments,
+            &CommandContext::default(),
+        )?;
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        Ok(toml::from_str(&content).expect("template should be valid TOML"))
+    }
+
+    #[test]
+    fn test_init_with_single_environment() {
+        let config = init_with(&["staging"]).unwrap();
+
+        let names: Vec<_> = config.environments.keys().collect();
+        assert_eq!(names, ["staging"]);
+        assert_eq!(
+            config.settings.default_environment.as_deref(),
+            Some("staging")
+        );
+        assert_eq!(
+            config.environments["staging"].color.as_deref(),
+            Some("green")
+        );
+    }
+
+    #[test]
+    fn test_init_with_three_environments() {
+        let config = init_with(&["local", "qa", "live-eu"]).unwrap();
+
+        let mut names: Vec<_> = config.environments.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, ["live-eu", "local", "qa"]);
+        assert_eq!(
+            config.settings.default_environment.as_deref(),
+            Some("local")
+        );
+        let colors: Vec<_> = ["local", "qa", "live-eu"]
+            .iter()
+            .map(|name| config.environments[*name].color.as_deref().unwrap())
+            .collect();
+        assert_eq!(colors, ["green", "blue", "yellow"]);
+    }
+
+    #[test]
+    fn test_init_rejects_invalid_environment_names() {
+        let err = init_with(&["dev", "bad name"]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid environment name 'bad name'"));
+
+        let err = init_with(&["dev", "dev"]).unwrap_err();
+        assert!(err.to_string().contains("listed more than once"));
+    }
 }
@@ -0,0 +1,334 @@
+//! Key management commands.
+//!
+//! Shows the project's public key and moves the private key between machines.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use toml_edit::DocumentMut;
+
+use crate::commands::encrypt::add_to_gitignore;
+use crate::crypto::keys::{
+    extract_private_key, parse_private_key, parse_public_key, public_key_fingerprint,
+    save_private_key,
+};
+use crate::crypto::{load_private_key_for_decryption, CryptoError};
+use crate::utils::confirm::{Confirm, StdinConfirm};
+
+const KEYS_FILE: &str = ".stand.keys";
+
+/// The project's public key and its fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKeyInfo {
+    pub public_key: String,
+    pub fingerprint: String,
+}
+
+impl PublicKeyInfo {
+    /// Format for `stand keys show`.
+    pub fn format(&self) -> String {
+        format!(
+            "Public key:  {}\nFingerprint: {}\n",
+            self.public_key, self.fingerprint
+        )
+    }
+}
+
+/// Read `[encryption].public_key` from `.stand.toml`.
+///
+/// Fails if encryption is not enabled or the key is not a valid age public key.
+pub fn show_public_key(project_dir: &Path) -> Result<PublicKeyInfo, KeysCommandError> {
+    let config_path = project_dir.join(".stand.toml");
+    if !config_path.exists() {
+        return Err(KeysCommandError::ConfigNotFound);
+    }
+
+    let public_key =
+        read_public_key(&config_path)?.ok_or(KeysCommandError::EncryptionNotEnabled)?;
+    parse_public_key(&public_key)?;
+
+    Ok(PublicKeyInfo {
+        fingerprint: public_key_fingerprint(&public_key),
+        public_key,
+    })
+}
+
+/// Return the private key (`STAND_PRIVATE_KEY` or `.stand.keys`) for printing.
+///
+/// Asks for confirmation on stderr unless `skip_confirmation` is true, and refuses to
+/// run without `-y` when stdin is not a terminal.
+pub fn export_private_key(
+    project_dir: &Path,
+    skip_confirmation: bool,
+) -> Result<String, KeysCommandError> {
+    export_private_key_with_confirm(project_dir, skip_confirmation, &StdinConfirm::stderr())
+}
+
+/// Like [`export_private_key`], asking for confirmation through `confirmer`
+pub fn export_private_key_with_confirm(
+    project_dir: &Path,
+    skip_confirmation: bool,
+    confirmer: &dyn Confirm,
+) -> Result<String, KeysCommandError> {
+    let private_key = load_private_key_for_decryption(project_dir)?;
+    parse_private_key(&private_key)?;
+
+    if !skip_confirmation {
+        if !confirmer.is_interactive() {
+            return Err(KeysCommandError::Cancelled(
+                "stdin is not a terminal. Use -y or --yes to export the key.".to_string(),
+            ));
+        }
+        let question = "This prints your PRIVATE key in plain text. Anyone who sees it can \
+                        decrypt every secret in this project.\nContinue?";
+        if !confirmer.confirm(question, false)? {
+            return Err(KeysCommandError::Cancelled(
+                "the private key was not printed.".to_string(),
+            ));
+        }
+    }
+
+    Ok(private_key)
+}
+
+/// Write `.stand.keys` from exported key material.
+///
+/// `content` is either a bare `AGE-SECRET-KEY-...` line or a `.stand.keys` file. The
+/// key is validated, and must match `[encryption].public_key` when encryption is
+/// enabled. An existing `.stand.keys` is only replaced with `force`.
+pub fn import_private_key(
+    project_dir: &Path,
+    content: &str,
+    force: bool,
+) -> Result<(), KeysCommandError> {
+    let private_key = extract_private_key(content).ok_or_else(|| {
+        KeysCommandError::Crypto(CryptoError::InvalidPrivateKey(
+            "no private key found in input".to_string(),
+        ))
+    })?;
+    let identity = parse_private_key(private_key)?;
+
+    let config_path = project_dir.join(".stand.toml");
+    if config_path.exists() {
+        if let Some(public_key) = read_public_key(&config_path)? {
+            if identity.to_public().to_string() != public_key {
+                return Err(KeysCommandError::KeyMismatch);
+            }
+        }
+    }
+
+    let keys_path = project_dir.join(KEYS_FILE);
+    if keys_path.exists() && !force {
+        return Err(KeysCommandError::KeysFileExists);
+    }
+
+    save_private_key(&keys_path, private_key)?;
+    add_to_gitignore(project_dir, KEYS_FILE)?;
+    Ok(())
+}
+
+/// Read `[encryption].public_key`, or `None` if encryption is not enabled.
+fn read_public_key(config_path: &Path) -> Result<Option<String>, KeysCommandError> {
+    let content = fs::read_to_string(config_path)?;
+    let doc: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| KeysCommandError::TomlParse(e.to_string()))?;
+
+    Ok(doc
+        .get("encryption")
+        .and_then(|e| e.get("public_key"))
+        .and_then(|k| k.as_str())
+        .map(str::to_string))
+}
+
+/// Error type for keys commands.
+#[derive(Debug, thiserror::Error)]
+pub enum KeysCommandError {
+    #[error("Configuration file not found. Run 'stand init' first.")]
+    ConfigNotFound,
+
+    #[error("Encryption is not enabled. Run 'stand encrypt enable' first.")]
+    EncryptionNotEnabled,
+
+    #[error("The private key does not match [encryption].public_key in .stand.toml")]
+    KeyMismatch,
+
+    #[error(".stand.keys already exists. Use --force to replace it.")]
+    KeysFileExists,
+
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
+    #[error("Cryptographic error: {0}")]
+    Crypto(#[from] CryptoError),
+
+    #[error("TOML parsing error: {0}")]
+    TomlParse(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("{0}")]
+    Prompt(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::keys::{generate_key_pair, load_private_key};
+    use crate::utils::confirm::MockConfirm;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    fn encrypted_project(public_key: &str) -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            format!(
+                "version = \"2.0\"\n\n[encryption]\npublic_key = \"{}\"\n",
+                public_key
+            ),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_import_round_trip() {
+        std::env::remove_var("STAND_PRIVATE_KEY");
+        let key_pair = generate_key_pair();
+        let source = encrypted_project(&key_pair.public_key);
+        save_private_key(&source.path().join(KEYS_FILE), &key_pair.private_key).unwrap();
+        let target = encrypted_project(&key_pair.public_key);
+
+        let exported = export_private_key(source.path(), true).unwrap();
+        import_private_key(target.path(), &format!("{}\n", exported), false).unwrap();
+
+        let imported = load_private_key(&target.path().join(KEYS_FILE)).unwrap();
+        assert_eq!(imported, key_pair.private_key);
+
+        // A copied .stand.keys file is accepted as well
+        let keys_file = fs::read_to_string(source.path().join(KEYS_FILE)).unwrap();
+        import_private_key(target.path(), &keys_file, true).unwrap();
+        assert_eq!(
+            load_private_key(&target.path().join(KEYS_FILE)).unwrap(),
+            key_pair.private_key
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_import_writes_secure_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let key_pair = generate_key_pair();
+        let dir = encrypted_project(&key_pair.public_key);
+
+        import_private_key(dir.path(), &key_pair.private_key, false).unwrap();
+
+        let mode = fs::metadata(dir.path().join(KEYS_FILE))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_import_rejects_malformed_or_mismatched_key() {
+        let key_pair = generate_key_pair();
+        let dir = encrypted_project(&key_pair.public_key);
+
+        for input in ["", "# only a comment\n", "AGE-SECRET-KEY-NOPE"] {
+            assert!(matches!(
+                import_private_key(dir.path(), input, false),
+                Err(KeysCommandError::Crypto(CryptoError::InvalidPrivateKey(_)))
+            ));
+        }
+        assert!(matches!(
+            import_private_key(dir.path(), &generate_key_pair().private_key, false),
+            Err(KeysCommandError::KeyMismatch)
+        ));
+        assert!(!dir.path().join(KEYS_FILE).exists());
+    }
+
+    #[test]
+    fn test_import_refuses_to_overwrite_without_force() {
+        let key_pair = generate_key_pair();
+        let dir = encrypted_project(&key_pair.public_key);
+        fs::write(dir.path().join(KEYS_FILE), "keep").unwrap();
+
+        assert!(matches!(
+            import_private_key(dir.path(), &key_pair.private_key, false),
+            Err(KeysCommandError::KeysFileExists)
+        ));
+        assert_eq!(
+            fs::read_to_string(dir.path().join(KEYS_FILE)).unwrap(),
+            "keep"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_requires_yes_without_tty() {
+        std::env::remove_var("STAND_PRIVATE_KEY");
+        std::env::set_var("STAND_FORCE_NON_TTY", "1");
+        let key_pair = generate_key_pair();
+        let dir = encrypted_project(&key_pair.public_key);
+        save_private_key(&dir.path().join(KEYS_FILE), &key_pair.private_key).unwrap();
+
+        let result = export_private_key(dir.path(), false);
+
+        std::env::remove_var("STAND_FORCE_NON_TTY");
+        assert!(matches!(result, Err(KeysCommandError::Cancelled(_))));
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_follows_confirmation_answer() {
+        std::env::remove_var("STAND_PRIVATE_KEY");
+        let key_pair = generate_key_pair();
+        let dir = encrypted_project(&key_pair.public_key);
+        save_private_key(&dir.path().join(KEYS_FILE), &key_pair.private_key).unwrap();
+
+        let decline = MockConfirm::answering(false);
+        let result = export_private_key_with_confirm(dir.path(), false, &decline);
+        assert!(matches!(result, Err(KeysCommandError::Cancelled(_))));
+
+        let accept = MockConfirm::answering(true);
+        let exported = export_private_key_with_confirm(dir.path(), false, &accept).unwrap();
+        assert_eq!(exported, key_pair.private_key);
+        assert!(accept.prompts.borrow()[0].contains("PRIVATE key"));
+    }
+
+    #[test]
+    fn test_show_public_key_without_encryption() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development\"\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            show_public_key(dir.path()),
+            Err(KeysCommandError::EncryptionNotEnabled)
+        ));
+    }
+
+    #[test]
+    fn test_show_public_key_rejects_invalid_key() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\n\n[encryption]\npublic_key = \"not-a-key\"\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            show_public_key(dir.path()),
+            Err(KeysCommandError::Crypto(CryptoError::InvalidPublicKey(_)))
+        ));
+    }
+}
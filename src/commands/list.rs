@@ -1,19 +1,65 @@
 use crate::config::loader;
+use crate::config::types::{Configuration, Environment};
+use crate::utils::colors::{colorize_environment, format_default_marker};
 use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 /// Lists all available environments from the configuration file
 pub fn list_environments(project_path: &Path) -> Result<String> {
-    let config = loader::load_config_toml(project_path)?;
+    list_environments_matching(project_path, None)
+}
+
+/// Options for the list command
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    /// Only list environments whose name or description contains this (case-insensitive)
+    pub filter: Option<String>,
+    /// Also list environments marked `hidden = true`
+    pub all: bool,
+}
+
+/// Lists the environments whose name or description contains `filter`
+///
+/// Matching is a case-insensitive substring match; `None` lists every
+/// environment that is not hidden. When nothing matches, a message saying so is
+/// returned instead of an error. Each line ends with the environment's variable
+/// count after inheritance, i.e. the number of variables `exec` would set.
+pub fn list_environments_matching(project_path: &Path, filter: Option<&str>) -> Result<String> {
+    let options = ListOptions {
+        filter: filter.map(String::from),
+        ..ListOptions::default()
+    };
+    list_environments_with_options(project_path, &options)
+}
+
+/// Lists environments like [`list_environments_matching`], including hidden ones
+/// when `options.all` is set
+pub fn list_environments_with_options(
+    project_path: &Path,
+    options: &ListOptions,
+) -> Result<String> {
+    let config = loader::load_config_toml_with_inheritance(project_path)?;
+    let filter = options.filter.as_deref();
 
     if config.environments.is_empty() {
         return Err(anyhow!("No environments defined"));
     }
 
     // Sort environments alphabetically
-    let mut env_names: Vec<_> = config.environments.keys().collect();
+    let mut env_names: Vec<_> = config
+        .environments
+        .iter()
+        .filter(|(_, env)| options.all || !env.hidden.unwrap_or(false))
+        .filter(|(name, env)| filter.is_none_or(|pattern| matches_filter(name, env, pattern)))
+        .map(|(name, _)| name)
+        .collect();
     env_names.sort();
 
+    if let (Some(pattern), true) = (filter, env_names.is_empty()) {
+        return Ok(format!("No environments match '{}'\n", pattern));
+    }
+
     let mut output = String::from("Available environments:\n");
 
     for env_name in env_names {
@@ -25,8 +71,14 @@ pub fn list_environments(project_path: &Path) -> Result<String> {
     Ok(output)
 }
 
+/// Whether the environment's name or description contains `pattern`, ignoring case
+fn matches_filter(name: &str, env: &Environment, pattern: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    name.to_lowercase().contains(&pattern) || env.description.to_lowercase().contains(&pattern)
+}
+
 /// Formats a single environment line for display
-fn format_environment_line(name: &str, env: &crate::config::types::Environment) -> String {
+fn format_environment_line(name: &str, env: &Environment) -> String {
     let color_part = env
         .color
         .as_ref()
@@ -39,8 +91,180 @@ fn format_environment_line(name: &str, env: &crate::config::types::Environment)
         ""
     };
 
+    let count = env.variable_count();
+    let count_part = format!(" ({} {})", count, if count == 1 { "var" } else { "vars" });
+
     format!(
-        "  {}     {}{}{}\n",
-        name, env.description, color_part, confirmation_part
+        "  {}     {}{}{}{}\n",
+        name, env.description, color_part, confirmation_part, count_part
     )
 }
+
+/// Lists environments as a tree following their `extends` relationships
+///
+/// Roots (environments without a parent, or whose parents do not exist) are shown
+/// first with their descendants indented below them. An environment with several
+/// parents is shown under each of them. Environments caught in an
+/// inheritance cycle are listed afterwards and the repeated entry is marked with
+/// `(cycle)` instead of being expanded again. Hidden environments are left out
+/// (see [`list_environment_tree_with_options`]).
+pub fn list_environment_tree(project_path: &Path) -> Result<String> {
+    list_environment_tree_with_options(project_path, &ListOptions::default())
+}
+
+/// Lists environments as a tree like [`list_environment_tree`], including hidden
+/// ones when `options.all` is set
+///
+/// Without `options.all`, a hidden environment is still shown when a visible one
+/// extends it, so the hierarchy stays intact. Hidden environments are marked with
+/// `(hidden)`. `options.filter` is ignored.
+pub fn list_environment_tree_with_options(
+    project_path: &Path,
+    options: &ListOptions,
+) -> Result<String> {
+    let config = loader::load_config_toml(project_path)?;
+
+    if config.environments.is_empty() {
+        return Err(anyhow!("No environments defined"));
+    }
+
+    let shown = shown_in_tree(&config, options.all);
+    let mut roots = Vec::new();
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (name, env) in &config.environments {
+        if !shown.contains(name.as_str()) {
+            continue;
+        }
+        let parents: Vec<_> = env
+            .parents()
+            .iter()
+            .filter(|parent| config.environments.contains_key(*parent))
+            .collect();
+        if parents.is_empty() {
+            roots.push(name.as_str());
+        }
+        for parent in parents {
+            children.entry(parent.as_str()).or_default().push(name);
+        }
+    }
+    roots.sort();
+    for list in children.values_mut() {
+        list.sort();
+    }
+
+    let tree = TreeRenderer {
+        config: &config,
+        children,
+    };
+    let mut visited = HashSet::new();
+    let mut output = String::from("Environment hierarchy:\n");
+    for root in roots {
+        tree.render(root, 0, &mut Vec::new(), &mut visited, &mut output);
+    }
+
+    // Anything not reached from a root is part of (or hangs off) a cycle
+    let mut remaining: Vec<_> = config
+        .environments
+        .keys()
+        .map(String::as_str)
+        .filter(|name| shown.contains(name) && !visited.contains(name))
+        .collect();
+    remaining.sort();
+    for name in remaining {
+        if !visited.contains(name) {
+            tree.render(name, 0, &mut Vec::new(), &mut visited, &mut output);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Environments that appear in the tree: every one with `all`, otherwise the visible
+/// ones and the hidden ancestors they extend
+fn shown_in_tree(config: &Configuration, all: bool) -> HashSet<&str> {
+    let mut shown = HashSet::new();
+    let mut pending: Vec<&str> = config
+        .environments
+        .iter()
+        .filter(|(_, env)| all || !env.hidden.unwrap_or(false))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    while let Some(name) = pending.pop() {
+        if !shown.insert(name) {
+            continue;
+        }
+        pending.extend(
+            config.environments[name]
+                .parents()
+                .iter()
+                .filter(|parent| config.environments.contains_key(*parent))
+                .map(String::as_str),
+        );
+    }
+    shown
+}
+
+/// Renders `list --tree` lines
+struct TreeRenderer<'a> {
+    config: &'a Configuration,
+    children: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> TreeRenderer<'a> {
+    fn render(
+        &self,
+        name: &'a str,
+        depth: usize,
+        path: &mut Vec<&'a str>,
+        visited: &mut HashSet<&'a str>,
+        output: &mut String,
+    ) {
+        let env = &self.config.environments[name];
+        let is_default = self.config.settings.default_environment.as_deref() == Some(name);
+        let branch = if depth == 0 {
+            String::new()
+        } else {
+            format!("{}└─ ", "   ".repeat(depth - 1))
+        };
+        let label = colorize_environment(name, env.color.as_deref());
+
+        visited.insert(name);
+        if path.contains(&name) {
+            output.push_str(&format!(
+                "{} {}{} (cycle)\n",
+                format_default_marker(is_default),
+                branch,
+                label
+            ));
+            return;
+        }
+
+        let confirmation_part = if env.requires_confirmation.unwrap_or(false) {
+            " (requires confirmation)"
+        } else {
+            ""
+        };
+        let hidden_part = if env.hidden.unwrap_or(false) {
+            " (hidden)"
+        } else {
+            ""
+        };
+        output.push_str(&format!(
+            "{} {}{}  {}{}{}\n",
+            format_default_marker(is_default),
+            branch,
+            label,
+            env.description,
+            confirmation_part,
+            hidden_part
+        ));
+
+        if let Some(children) = self.children.get(name) {
+            path.push(name);
+            for child in children {
+                self.render(child, depth + 1, path, visited, output);
+            }
+            path.pop();
+        }
+    }
+}
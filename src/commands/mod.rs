@@ -1,11 +1,29 @@
+pub mod check;
+pub mod complete;
+pub mod config;
+pub mod context;
+pub mod copy;
 pub mod current;
+pub mod deps;
+pub mod doctor;
 pub mod encrypt;
 pub mod env;
 pub mod exec;
+pub mod export;
 pub mod get;
+pub mod history;
+pub mod import;
 pub mod init;
+pub mod keys;
 pub mod list;
+pub mod projects;
+pub mod rename;
 pub mod set;
 pub mod shell;
 pub mod show;
+pub mod switch;
+pub mod unset;
 pub mod validate;
+pub mod watch;
+
+pub use context::{CommandContext, Verbosity};
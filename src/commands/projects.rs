@@ -0,0 +1,186 @@
+// projects.rs command implementation
+//
+// Find every Stand project below a directory, e.g. the services of a monorepo.
+
+use crate::config::loader::parse_config_toml;
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Depth searched when `--max-depth` is not given
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
+/// Directories that are never searched
+pub const SKIPPED_DIRS: &[&str] = &[".git", "node_modules", "target"];
+
+/// A `.stand.toml` found by [`find_projects`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    /// Directory containing `.stand.toml`, relative to the search root
+    pub path: PathBuf,
+    /// Environment names and descriptions, sorted by name
+    pub environments: Vec<(String, String)>,
+    /// Why the configuration could not be parsed, if it could not
+    pub error: Option<String>,
+}
+
+/// Find every `.stand.toml` at most `max_depth` directories below `root`
+///
+/// `max_depth = 0` only looks at `root` itself. Directories in [`SKIPPED_DIRS`]
+/// and symbolic links are not followed, and unreadable directories are skipped.
+/// Projects are returned sorted by path.
+pub fn find_projects(root: &Path, max_depth: usize) -> Vec<Project> {
+    let mut projects = Vec::new();
+    let mut pending = vec![(PathBuf::new(), 0)];
+
+    while let Some((relative, depth)) = pending.pop() {
+        let dir = root.join(&relative);
+        if dir.join(".stand.toml").is_file() {
+            projects.push(read_project(&dir, relative.clone()));
+        }
+        if depth == max_depth {
+            continue;
+        }
+
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+            let name = entry.file_name();
+            if is_dir && !SKIPPED_DIRS.iter().any(|skipped| name == *skipped) {
+                pending.push((relative.join(name), depth + 1));
+            }
+        }
+    }
+
+    projects.sort_by(|a, b| a.path.cmp(&b.path));
+    projects
+}
+
+/// Parse the project's `.stand.toml` as written (no overlay or interpolation)
+fn read_project(dir: &Path, path: PathBuf) -> Project {
+    match parse_config_toml(dir) {
+        Ok(config) => {
+            let mut environments: Vec<_> = config
+                .environments
+                .into_iter()
+                .map(|(name, env)| (name, env.description))
+                .collect();
+            environments.sort();
+            Project {
+                path,
+                environments,
+                error: None,
+            }
+        }
+        Err(e) => Project {
+            path,
+            environments: Vec::new(),
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// List the projects below `root` with their environments, grouped by project
+pub fn list_projects(root: &Path, max_depth: usize) -> Result<String> {
+    let projects = find_projects(root, max_depth);
+    if projects.is_empty() {
+        return Ok(format!(
+            "No Stand projects found under {}\n",
+            root.display()
+        ));
+    }
+
+    let mut output = String::new();
+    for project in &projects {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&format!("{}\n", project.path.join(".stand.toml").display()));
+        if let Some(error) = &project.error {
+            output.push_str(&format!("  (invalid: {})\n", error));
+        }
+        for (name, description) in &project.environments {
+            output.push_str(&format!("  {}     {}\n", name, description));
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_config(dir: &Path, environments: &[&str]) {
+        fs::create_dir_all(dir).unwrap();
+        let mut content = String::from("version = \"2.0\"\n");
+        for name in environments {
+            content.push_str(&format!(
+                "\n[environments.{}]\ndescription = \"{} environment\"\n",
+                name, name
+            ));
+        }
+        fs::write(dir.join(".stand.toml"), content).unwrap();
+    }
+
+    #[test]
+    fn test_find_nested_projects() {
+        let dir = tempdir().unwrap();
+        write_config(dir.path(), &["dev", "prod"]);
+        write_config(&dir.path().join("services/api"), &["staging", "dev"]);
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+
+        let projects = find_projects(dir.path(), DEFAULT_MAX_DEPTH);
+
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].path, PathBuf::new());
+        assert_eq!(projects[1].path, Path::new("services/api"));
+        let names: Vec<_> = projects[1]
+            .environments
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, ["dev", "staging"]);
+
+        let output = list_projects(dir.path(), DEFAULT_MAX_DEPTH).unwrap();
+        assert_eq!(
+            output,
+            ".stand.toml\n\
+             \x20 dev     dev environment\n\
+             \x20 prod     prod environment\n\
+             \n\
+             services/api/.stand.toml\n\
+             \x20 dev     dev environment\n\
+             \x20 staging     staging environment\n"
+        );
+    }
+
+    #[test]
+    fn test_find_projects_skips_ignored_dirs_and_respects_depth() {
+        let dir = tempdir().unwrap();
+        write_config(&dir.path().join("node_modules/pkg"), &["dev"]);
+        write_config(&dir.path().join("target/debug"), &["dev"]);
+        write_config(&dir.path().join("a/b/c"), &["dev"]);
+
+        let projects = find_projects(dir.path(), DEFAULT_MAX_DEPTH);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].path, Path::new("a/b/c"));
+        assert!(find_projects(dir.path(), 2).is_empty());
+        assert_eq!(find_projects(dir.path(), 3).len(), 1);
+    }
+
+    #[test]
+    fn test_list_projects_reports_invalid_and_missing() {
+        let dir = tempdir().unwrap();
+
+        let output = list_projects(dir.path(), DEFAULT_MAX_DEPTH).unwrap();
+        assert!(output.starts_with("No Stand projects found under "));
+
+        fs::create_dir_all(dir.path().join("broken")).unwrap();
+        fs::write(dir.path().join("broken/.stand.toml"), "not = [valid").unwrap();
+        let output = list_projects(dir.path(), DEFAULT_MAX_DEPTH).unwrap();
+        assert!(output.starts_with("broken/.stand.toml\n  (invalid: TOML parsing error"));
+    }
+}
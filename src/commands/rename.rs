@@ -0,0 +1,373 @@
+//! Rename command implementation.
+//!
+//! Renames an environment and rewrites the references to it.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::state::persistence::{load_state_from, save_state_to};
+use crate::utils::write_atomic;
+use toml_edit::{DocumentMut, Item, Table};
+
+/// What `rename_environment` changed besides the environment table itself.
+#[derive(Debug, Default)]
+pub struct RenameSummary {
+    /// Environments whose `extends` pointed at the old name, in file order
+    pub updated_extends: Vec<String>,
+    /// Whether `settings.default_environment` was updated
+    pub default_environment_updated: bool,
+    /// Aliases in `settings.aliases` that pointed at the old name, in file order
+    pub updated_aliases: Vec<String>,
+    /// Whether the active environment (`stand switch`) was the old name and was updated
+    pub active_environment_updated: bool,
+}
+
+/// Rename `[environments.<old>]` to `[environments.<new>]`.
+///
+/// Every `extends` reference to `<old>`, a matching `settings.default_environment` and
+/// the aliases pointing at `<old>` are rewritten to the new name, and so is the
+/// active environment recorded by `stand switch`. The table keeps its
+/// position, comments and formatting. `<new>` may not be an existing alias.
+pub fn rename_environment(
+    project_dir: &Path,
+    old: &str,
+    new: &str,
+) -> Result<RenameSummary, RenameCommandError> {
+    if !is_valid_environment_name(new) {
+        return Err(RenameCommandError::InvalidName(new.to_string()));
+    }
+
+    let config_path = project_dir.join(".stand.toml");
+    let content = fs::read_to_string(&config_path)?;
+    let mut doc: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| RenameCommandError::TomlParse(e.to_string()))?;
+
+    if aliases(&doc).is_some_and(|aliases| aliases.contains_key(new)) {
+        return Err(RenameCommandError::AliasExists(new.to_string()));
+    }
+
+    let environments = doc
+        .get_mut("environments")
+        .and_then(Item::as_table_mut)
+        .ok_or_else(|| RenameCommandError::EnvironmentNotFound(old.to_string()))?;
+
+    if !environments.contains_key(old) {
+        return Err(RenameCommandError::EnvironmentNotFound(old.to_string()));
+    }
+    if environments.contains_key(new) {
+        return Err(RenameCommandError::AlreadyExists(new.to_string()));
+    }
+
+    // The removed table keeps its position, so it is written back in the same place
+    let table = environments.remove(old).expect("checked above");
+    environments.insert(new, table);
+
+    let mut summary = RenameSummary {
+        updated_extends: rewrite_extends(environments, old, new),
+        ..Default::default()
+    };
+
+    if let Some(settings) = doc.get_mut("settings").and_then(Item::as_table_mut) {
+        if settings.get("default_environment").and_then(Item::as_str) == Some(old) {
+            replace_str_value(settings, "default_environment", new);
+            summary.default_environment_updated = true;
+        }
+        if let Some(aliases) = settings
+            .get_mut("aliases")
+            .and_then(Item::as_table_like_mut)
+        {
+            summary.updated_aliases = rewrite_aliases(aliases, old, new);
+        }
+    }
+
+    write_atomic(&config_path, doc.to_string())?;
+
+    // Only look for a state file if `stand switch` has created one
+    if project_dir.join(".stand").is_dir() {
+        let mut state = load_state_from(project_dir)?;
+        if state.get_current_environment() == Some(old) {
+            state.set_current_environment(new.to_string());
+            save_state_to(project_dir, &state)?;
+            summary.active_environment_updated = true;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Point every `extends` reference to `<old>` (single or in a list) at `new`, returning the names of the changed environments.
+fn rewrite_extends(environments: &mut Table, old: &str, new: &str) -> Vec<String> {
+    let mut updated = Vec::new();
+    for (name, item) in environments.iter_mut() {
+        let Some(env_table) = item.as_table_mut() else {
+            continue;
+        };
+        if env_table.get("extends").and_then(Item::as_str) == Some(old) {
+            replace_str_value(env_table, "extends", new);
+            updated.push(name.to_string());
+        } else if let Some(parents) = env_table.get_mut("extends").and_then(Item::as_array_mut) {
+            let mut changed = false;
+            for parent in parents.iter_mut() {
+                if parent.as_str() == Some(old) {
+                    let decor = parent.decor().clone();
+                    *parent = new.into();
+                    *parent.decor_mut() = decor;
+                    changed = true;
+                }
+            }
+            if changed {
+                updated.push(name.to_string());
+            }
+        }
+    }
+    updated
+}
+
+/// `settings.aliases`, written either inline or as a `[settings.aliases]` table
+fn aliases(doc: &DocumentMut) -> Option<&dyn toml_edit::TableLike> {
+    doc.get("settings")?.get("aliases")?.as_table_like()
+}
+
+/// Point every alias targeting `<old>` at `new`, returning the names of the changed aliases.
+fn rewrite_aliases(aliases: &mut dyn toml_edit::TableLike, old: &str, new: &str) -> Vec<String> {
+    let mut updated = Vec::new();
+    for (alias, target) in aliases.iter_mut() {
+        if let Some(value) = target.as_value_mut().filter(|v| v.as_str() == Some(old)) {
+            let decor = value.decor().clone();
+            *value = new.into();
+            *value.decor_mut() = decor;
+            updated.push(alias.get().to_string());
+        }
+    }
+    updated
+}
+
+/// Replace a string value while keeping its surrounding whitespace and comments.
+fn replace_str_value(table: &mut Table, key: &str, value: &str) {
+    if let Some(Item::Value(existing)) = table.get_mut(key) {
+        let decor = existing.decor().clone();
+        *existing = value.into();
+        *existing.decor_mut() = decor;
+    }
+}
+
+/// Environment names are limited to ASCII letters, digits, `-` and `_` so they can be
+/// used as bare TOML keys and shell arguments.
+pub(crate) fn is_valid_environment_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Error type for rename command.
+#[derive(Debug, thiserror::Error)]
+pub enum RenameCommandError {
+    #[error("Environment not found: {0}")]
+    EnvironmentNotFound(String),
+
+    #[error("Environment already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error(
+        "'{0}' is already an alias in settings.aliases; pick another name or remove the alias"
+    )]
+    AliasExists(String),
+
+    #[error("Invalid environment name '{0}': use letters, digits, '-' or '_'")]
+    InvalidName(String),
+
+    #[error("TOML parsing error: {0}")]
+    TomlParse(String),
+
+    #[error("Failed to update the active environment: {0}")]
+    State(#[from] anyhow::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::loader;
+    use crate::state::types::State;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"version = "2.0"
+
+[settings]
+default_environment = "staging" # used by stand get
+
+[environments.base]
+description = "Base"
+LOG_LEVEL = "info"
+
+[environments.staging]
+description = "Staging"
+extends = "base"
+API_URL = "https://staging.example.com"
+
+[environments.qa]
+description = "QA"
+extends = "staging" # share the staging API
+
+[environments.preview]
+description = "Preview"
+extends = "staging"
+"#;
+
+    #[test]
+    fn test_rename_rewrites_extends_references() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let summary = rename_environment(dir.path(), "staging", "stage").unwrap();
+
+        assert_eq!(summary.updated_extends, vec!["qa", "preview"]);
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert!(content.contains("[environments.stage]"));
+        assert!(!content.contains("staging\""));
+        assert!(content.contains("extends = \"stage\" # share the staging API"));
+
+        let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+        assert_eq!(
+            config.environments["stage"].variables["API_URL"],
+            "https://staging.example.com"
+        );
+        assert_eq!(config.environments["qa"].parents(), ["stage"]);
+        assert_eq!(config.environments["qa"].variables["LOG_LEVEL"], "info");
+    }
+
+    #[test]
+    fn test_rename_updates_default_environment() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let summary = rename_environment(dir.path(), "staging", "stage").unwrap();
+
+        assert!(summary.default_environment_updated);
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert!(content.contains("default_environment = \"stage\" # used by stand get"));
+
+        let summary = rename_environment(dir.path(), "base", "root").unwrap();
+        assert!(!summary.default_environment_updated);
+        assert_eq!(summary.updated_extends, vec!["stage"]);
+    }
+
+    #[test]
+    fn test_rename_rewrites_extends_lists() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            format!(
+                "{}\n[environments.combined]\ndescription = \"Combined\"\nextends = [\"base\", \"staging\"]\n",
+                CONFIG
+            ),
+        )
+        .unwrap();
+
+        let summary = rename_environment(dir.path(), "staging", "stage").unwrap();
+
+        assert_eq!(summary.updated_extends, vec!["qa", "preview", "combined"]);
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert!(content.contains("extends = [\"base\", \"stage\"]"));
+    }
+
+    #[test]
+    fn test_rename_keeps_table_position() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        rename_environment(dir.path(), "base", "root").unwrap();
+
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        let root = content.find("[environments.root]").unwrap();
+        let staging = content.find("[environments.staging]").unwrap();
+        assert!(root < staging);
+    }
+
+    #[test]
+    fn test_rename_rewrites_aliases() {
+        let dir = tempdir().unwrap();
+        let config = CONFIG.replace(
+            "# used by stand get\n",
+            "# used by stand get\naliases = { b = \"base\", s = \"staging\" }\n",
+        );
+        fs::write(dir.path().join(".stand.toml"), config).unwrap();
+
+        let summary = rename_environment(dir.path(), "base", "core").unwrap();
+
+        assert_eq!(summary.updated_aliases, vec!["b"]);
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert!(content.contains("aliases = { b = \"core\", s = \"staging\" }"));
+        loader::load_config_toml_with_validation(dir.path()).unwrap();
+
+        assert!(matches!(
+            rename_environment(dir.path(), "qa", "s"),
+            Err(RenameCommandError::AliasExists(_))
+        ));
+    }
+
+    #[test]
+    fn test_rename_updates_active_environment() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        let mut state = State::new();
+        state.set_current_environment("base".to_string());
+        save_state_to(dir.path(), &state).unwrap();
+
+        let summary = rename_environment(dir.path(), "base", "core").unwrap();
+
+        assert!(summary.active_environment_updated);
+        let state = load_state_from(dir.path()).unwrap();
+        assert_eq!(state.get_current_environment(), Some("core"));
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        assert_eq!(
+            config.resolve_default_environment(dir.path()).unwrap(),
+            Some("core".to_string())
+        );
+
+        // Another environment being active leaves the state alone
+        let summary = rename_environment(dir.path(), "qa", "test").unwrap();
+        assert!(!summary.active_environment_updated);
+        let state = load_state_from(dir.path()).unwrap();
+        assert_eq!(state.get_current_environment(), Some("core"));
+    }
+
+    #[test]
+    fn test_rename_without_state_does_not_create_it() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let summary = rename_environment(dir.path(), "base", "core").unwrap();
+
+        assert!(!summary.active_environment_updated);
+        assert!(!dir.path().join(".stand").exists());
+    }
+
+    #[test]
+    fn test_rename_errors() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        assert!(matches!(
+            rename_environment(dir.path(), "prod", "production"),
+            Err(RenameCommandError::EnvironmentNotFound(_))
+        ));
+        assert!(matches!(
+            rename_environment(dir.path(), "qa", "base"),
+            Err(RenameCommandError::AlreadyExists(_))
+        ));
+        for name in ["", "has space", "dot.ted", "quote\""] {
+            assert!(matches!(
+                rename_environment(dir.path(), "qa", name),
+                Err(RenameCommandError::InvalidName(_))
+            ));
+        }
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert_eq!(content, CONFIG);
+    }
+}
@@ -3,35 +3,181 @@
 //! Sets a variable in the configuration file, optionally encrypting the value.
 
 use std::fs;
-use std::io;
+use std::io::{self, Read};
 use std::path::Path;
 
-use colored::Colorize;
+use crate::utils::colors::format_secret_summary;
+use crate::utils::write_atomic;
 use toml_edit::DocumentMut;
 
+use crate::config::types::EncryptionSettings;
 use crate::config::{loader, ConfigError};
-use crate::crypto::{encrypt_value, CryptoError};
+use crate::crypto::keys::parse_private_key;
+use crate::crypto::{
+    decrypt_value, encrypt_value_for_recipients, is_encrypted, load_private_key_for_decryption,
+    CryptoError,
+};
+
+/// Table a variable is written to
+#[derive(Debug, Clone, Copy)]
+enum Target<'a> {
+    Environment(&'a str),
+    Common,
+}
+
+impl std::fmt::Display for Target<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Environment(name) => write!(f, "[environments.{}]", name),
+            Target::Common => f.write_str("[common]"),
+        }
+    }
+}
+
+/// Separator used by `--append`/`--prepend` when none is given (like `PATH`)
+pub const DEFAULT_SEPARATOR: &str = if cfg!(windows) { ";" } else { ":" };
+
+/// How a new value is combined with the variable's current value
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SetMode {
+    /// Replace the current value
+    #[default]
+    Replace,
+    /// Add the value after the current one, joined with the separator
+    Append(String),
+    /// Add the value before the current one, joined with the separator
+    Prepend(String),
+}
+
+impl SetMode {
+    /// Combine `current` (if the variable exists and is not empty) with `value`
+    fn combine(&self, current: Option<&str>, value: &str) -> String {
+        match (self, current) {
+            (SetMode::Append(separator), Some(current)) if !current.is_empty() => {
+                format!("{}{}{}", current, separator, value)
+            }
+            (SetMode::Prepend(separator), Some(current)) if !current.is_empty() => {
+                format!("{}{}{}", value, separator, current)
+            }
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// Options for the set command
+#[derive(Debug, Clone, Default)]
+pub struct SetOptions {
+    /// Encrypt the value before storing
+    pub encrypt: bool,
+    /// Replace the value, or append/prepend to the current one
+    pub mode: SetMode,
+}
 
 /// Set a variable in the configuration file.
 ///
 /// If `encrypt` is true, the value will be encrypted before storing.
 /// If `value` is None and `encrypt` is true, prompts for password input.
+/// Returns a one-line summary of the change for the caller to print.
 pub fn set_variable(
     project_dir: &Path,
     environment: &str,
     key: &str,
     value: Option<String>,
     encrypt: bool,
-) -> Result<(), SetCommandError> {
+) -> Result<String, SetCommandError> {
+    set_variable_with_options(
+        project_dir,
+        environment,
+        key,
+        value,
+        &SetOptions {
+            encrypt,
+            ..SetOptions::default()
+        },
+    )
+}
+
+/// Set a variable in the configuration file with set options
+///
+/// With [`SetMode::Append`] or [`SetMode::Prepend`], the value already stored for
+/// `key` in the environment's own table of `.stand.toml` is extended; a missing key
+/// is created with just `value`. An encrypted current value is decrypted, extended
+/// and encrypted again.
+pub fn set_variable_with_options(
+    project_dir: &Path,
+    environment: &str,
+    key: &str,
+    value: Option<String>,
+    options: &SetOptions,
+) -> Result<String, SetCommandError> {
+    set_in(
+        project_dir,
+        Target::Environment(environment),
+        key,
+        value,
+        options,
+    )
+}
+
+/// Set a variable in the `[common]` section, creating it if absent.
+///
+/// Common variables are inherited by every environment. A `key` that names an
+/// environment is rejected as ambiguous (`stand set --common` takes no environment).
+pub fn set_common_variable(
+    project_dir: &Path,
+    key: &str,
+    value: Option<String>,
+    encrypt: bool,
+) -> Result<String, SetCommandError> {
+    set_common_variable_with_options(
+        project_dir,
+        key,
+        value,
+        &SetOptions {
+            encrypt,
+            ..SetOptions::default()
+        },
+    )
+}
+
+/// Set a variable in the `[common]` section with set options
+///
+/// See [`set_variable_with_options`] for appending and prepending.
+pub fn set_common_variable_with_options(
+    project_dir: &Path,
+    key: &str,
+    value: Option<String>,
+    options: &SetOptions,
+) -> Result<String, SetCommandError> {
+    set_in(project_dir, Target::Common, key, value, options)
+}
+
+fn set_in(
+    project_dir: &Path,
+    target: Target,
+    key: &str,
+    value: Option<String>,
+    options: &SetOptions,
+) -> Result<String, SetCommandError> {
+    let encrypt = options.encrypt;
     // Load configuration
     let config_path = project_dir.join(".stand.toml");
     let config = loader::load_config_toml(project_dir)?;
+    if config.encryption.is_some() {
+        crate::commands::encrypt::warn_keys_file_security(project_dir);
+    }
 
-    // Verify environment exists
-    if !config.environments.contains_key(environment) {
-        return Err(SetCommandError::EnvironmentNotFound(
-            environment.to_string(),
-        ));
+    match target {
+        // Verify environment exists
+        Target::Environment(environment) if !config.environments.contains_key(environment) => {
+            return Err(SetCommandError::EnvironmentNotFound(
+                environment.to_string(),
+            ));
+        }
+        Target::Common if config.environments.contains_key(key) => {
+            return Err(SetCommandError::AmbiguousCommon(key.to_string()));
+        }
+        _ => {}
     }
 
     // Get the value (prompt if not provided and encrypting)
@@ -41,36 +187,59 @@ pub fn set_variable(
         None => return Err(SetCommandError::ValueRequired),
     };
 
+    // Extend the current value, decrypting it first and keeping it encrypted
+    let (plain_value, encrypt) = match &options.mode {
+        SetMode::Replace => (plain_value, encrypt),
+        mode => match read_current_value(&config_path, target, key)? {
+            Some(current) if is_encrypted(&current) => {
+                let identity = parse_private_key(&load_private_key_for_decryption(project_dir)?)?;
+                let current = decrypt_value(&current, &identity)?;
+                (mode.combine(Some(&current), &plain_value), true)
+            }
+            current => (mode.combine(current.as_deref(), &plain_value), encrypt),
+        },
+    };
+
+    // Encrypted values are only ever summarized masked
+    let secret_summary = encrypt.then(|| format_secret_summary(key, &plain_value));
+
     // Encrypt if requested
     let final_value = if encrypt {
         // Check if encryption is enabled
-        let public_key = get_public_key(&config_path)?;
-        let recipient = crate::crypto::keys::parse_public_key(&public_key)?;
-        encrypt_value(&plain_value, &recipient)?
+        let recipients = get_encryption_settings(&config_path)?.all_recipients()?;
+        encrypt_value_for_recipients(&plain_value, &recipients)?
     } else {
         plain_value
     };
 
     // Update the TOML file
-    update_toml_variable(&config_path, environment, key, &final_value)?;
-
-    if encrypt {
-        println!(
-            "{} Set {} in [environments.{}] (encrypted)",
-            "✓".green(),
-            key,
-            environment
-        );
-    } else {
-        println!(
-            "{} Set {} in [environments.{}]",
-            "✓".green(),
-            key,
-            environment
-        );
-    }
+    update_toml_variable(&config_path, target, key, &final_value)?;
 
-    Ok(())
+    let action = match options.mode {
+        SetMode::Replace => "Set",
+        SetMode::Append(_) => "Appended to",
+        SetMode::Prepend(_) => "Prepended to",
+    };
+    Ok(match secret_summary {
+        Some(summary) => format!("{} {} in {} (encrypted)", action, summary, target),
+        None => format!("{} {} in {}", action, key, target),
+    })
+}
+
+/// Set a variable to everything read from `reader` (until EOF).
+///
+/// Keeps secrets out of shell history and `ps` output. The value is stored exactly as
+/// read, newlines included; a multi-line plain value is written as a TOML multi-line string.
+pub fn set_variable_from_reader(
+    project_dir: &Path,
+    environment: &str,
+    key: &str,
+    mut reader: impl Read,
+    encrypt: bool,
+) -> Result<String, SetCommandError> {
+    let mut value = String::new();
+    reader.read_to_string(&mut value)?;
+    set_variable(project_dir, environment, key, Some(value), encrypt)
 }
 
 /// Prompts for a secret value without echoing to the terminal.
@@ -82,8 +251,8 @@ fn prompt_for_secret(key: &str) -> Result<String, SetCommandError> {
     rpassword::prompt_password(prompt).map_err(SetCommandError::Io)
 }
 
-/// Get the public key from the configuration.
-fn get_public_key(config_path: &Path) -> Result<String, SetCommandError> {
+/// Get the public key and additional recipients from the configuration.
+fn get_encryption_settings(config_path: &Path) -> Result<EncryptionSettings, SetCommandError> {
     let content = fs::read_to_string(config_path)?;
 
     // Parse TOML to find public_key
@@ -91,11 +260,52 @@ fn get_public_key(config_path: &Path) -> Result<String, SetCommandError> {
         .parse()
         .map_err(|e: toml_edit::TomlError| SetCommandError::TomlParse(e.to_string()))?;
 
-    doc.get("encryption")
-        .and_then(|e| e.get("public_key"))
+    let encryption = doc
+        .get("encryption")
+        .ok_or(SetCommandError::EncryptionNotEnabled)?;
+    let public_key = encryption
+        .get("public_key")
         .and_then(|k| k.as_str())
         .map(|s| s.to_string())
-        .ok_or(SetCommandError::EncryptionNotEnabled)
+        .ok_or(SetCommandError::EncryptionNotEnabled)?;
+    let recipients = encryption
+        .get("recipients")
+        .and_then(|r| r.as_array())
+        .map(|keys| {
+            keys.iter()
+                .filter_map(|k| k.as_str())
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(EncryptionSettings {
+        public_key,
+        recipients,
+    })
+}
+
+/// The value stored for `key` in the target's own table of `.stand.toml`, if any
+fn read_current_value(
+    config_path: &Path,
+    target: Target,
+    key: &str,
+) -> Result<Option<String>, SetCommandError> {
+    let content = fs::read_to_string(config_path)?;
+    let doc: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| SetCommandError::TomlParse(e.to_string()))?;
+
+    let table = match target {
+        Target::Environment(environment) => {
+            doc.get("environments").and_then(|e| e.get(environment))
+        }
+        Target::Common => doc.get("common"),
+    };
+    Ok(table
+        .and_then(|t| t.get(key))
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string()))
 }
 
 /// Update a variable in the TOML file.
@@ -104,7 +314,7 @@ fn get_public_key(config_path: &Path) -> Result<String, SetCommandError> {
 /// Variables are stored directly in the environment section due to `#[serde(flatten)]`.
 fn update_toml_variable(
     config_path: &Path,
-    environment: &str,
+    target: Target,
     key: &str,
     value: &str,
 ) -> Result<(), SetCommandError> {
@@ -115,18 +325,25 @@ fn update_toml_variable(
         .parse()
         .map_err(|e: toml_edit::TomlError| SetCommandError::TomlParse(e.to_string()))?;
 
-    // Navigate to environments.<env>
-    let env_table = doc
-        .get_mut("environments")
-        .and_then(|e| e.get_mut(environment))
-        .and_then(|e| e.as_table_mut())
-        .ok_or_else(|| SetCommandError::EnvironmentNotFound(environment.to_string()))?;
+    let table = match target {
+        // Navigate to environments.<env>
+        Target::Environment(environment) => doc
+            .get_mut("environments")
+            .and_then(|e| e.get_mut(environment))
+            .and_then(|e| e.as_table_mut())
+            .ok_or_else(|| SetCommandError::EnvironmentNotFound(environment.to_string()))?,
+        Target::Common => doc
+            .entry("common")
+            .or_insert_with(toml_edit::table)
+            .as_table_mut()
+            .ok_or_else(|| SetCommandError::TomlParse("[common] is not a table".to_string()))?,
+    };
 
-    // Set the variable directly in the environment section (due to #[serde(flatten)])
-    env_table.insert(key, toml_edit::value(value));
+    // Set the variable directly in the section (due to #[serde(flatten)])
+    table.insert(key, toml_edit::value(value));
 
     // Write back preserving formatting
-    fs::write(config_path, doc.to_string())?;
+    write_atomic(config_path, doc.to_string())?;
 
     Ok(())
 }
@@ -140,6 +357,9 @@ pub enum SetCommandError {
     #[error("Value is required when not encrypting")]
     ValueRequired,
 
+    #[error("'{0}' is an environment; --common takes a variable name (stand set --common <KEY> [VALUE])")]
+    AmbiguousCommon(String),
+
     #[error("Encryption is not enabled. Run 'stand encrypt enable' first.")]
     EncryptionNotEnabled,
 
@@ -287,4 +507,245 @@ description = "Development"
             "Plain value should not appear in config file"
         );
     }
+
+    #[test]
+    fn test_set_variable_from_reader_keeps_newlines() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        fs::write(
+            &config_path,
+            "version = \"1.0\"\n\n[environments.dev]\ndescription = \"Development\"\n",
+        )
+        .unwrap();
+        let cert = "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n";
+
+        set_variable_from_reader(dir.path(), "dev", "TLS_CERT", io::Cursor::new(cert), false)
+            .unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.contains("TLS_CERT = \"\"\"\n-----BEGIN CERTIFICATE-----\nMIIB\n"));
+        let value = crate::commands::get::get_variable(dir.path(), "dev", "TLS_CERT").unwrap();
+        assert_eq!(value, cert);
+    }
+
+    #[test]
+    fn test_set_common_variable_is_inherited() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development\"\n\n[environments.prod]\ndescription = \"Production\"\nLOG_LEVEL = \"warn\"\n",
+        )
+        .unwrap();
+
+        // Creates the section when absent, then adds to it
+        set_common_variable(dir.path(), "APP_NAME", Some("myapp".into()), false).unwrap();
+        set_common_variable(dir.path(), "LOG_LEVEL", Some("info".into()), false).unwrap();
+
+        let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+        for env in ["dev", "prod"] {
+            assert_eq!(config.environments[env].variables["APP_NAME"], "myapp");
+        }
+        assert_eq!(config.environments["dev"].variables["LOG_LEVEL"], "info");
+        assert_eq!(config.environments["prod"].variables["LOG_LEVEL"], "warn");
+    }
+
+    #[test]
+    fn test_set_common_variable_encrypted() {
+        let dir = tempdir().unwrap();
+        let key_pair = crate::crypto::keys::generate_key_pair();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            format!(
+                "version = \"2.0\"\n\n[common]\nAPP_NAME = \"myapp\"\n\n[environments.dev]\ndescription = \"Development\"\n\n[encryption]\npublic_key = \"{}\"\n",
+                key_pair.public_key
+            ),
+        )
+        .unwrap();
+
+        set_common_variable(dir.path(), "API_KEY", Some("secret-value".into()), true).unwrap();
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        let common = config.common.unwrap();
+        assert_eq!(common["APP_NAME"], "myapp");
+        assert!(crate::crypto::is_encrypted(&common["API_KEY"]));
+    }
+
+    #[test]
+    fn test_set_common_variable_rejects_environment_name() {
+        let dir = tempdir().unwrap();
+        let content = "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development\"\n";
+        fs::write(dir.path().join(".stand.toml"), content).unwrap();
+
+        let result = set_common_variable(dir.path(), "dev", Some("API_KEY".into()), false);
+
+        assert!(matches!(result, Err(SetCommandError::AmbiguousCommon(_))));
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".stand.toml")).unwrap(),
+            content
+        );
+    }
+
+    #[test]
+    fn test_set_variable_errors_do_not_leak_secret() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        let secret = "s3cr3t-value";
+        fs::write(
+            &config_path,
+            "version = \"1.0\"\n\n[environments.dev]\ndescription = \"Development\"\n",
+        )
+        .unwrap();
+
+        // Encryption not enabled, and an unknown environment
+        let err =
+            set_variable(dir.path(), "dev", "API_KEY", Some(secret.into()), true).unwrap_err();
+        crate::crypto::assert_error_hides_secret(&err, secret);
+        let err =
+            set_variable(dir.path(), "prod", "API_KEY", Some(secret.into()), true).unwrap_err();
+        crate::crypto::assert_error_hides_secret(&err, secret);
+
+        // Invalid public key
+        fs::write(
+            &config_path,
+            "version = \"1.0\"\n\n[encryption]\npublic_key = \"age1invalid\"\n\n\
+             [environments.dev]\ndescription = \"Development\"\n",
+        )
+        .unwrap();
+        let err =
+            set_variable(dir.path(), "dev", "API_KEY", Some(secret.into()), true).unwrap_err();
+        assert!(matches!(err, SetCommandError::Crypto(_)));
+        crate::crypto::assert_error_hides_secret(&err, secret);
+        assert!(!fs::read_to_string(&config_path).unwrap().contains(secret));
+    }
+
+    fn append(separator: &str) -> SetOptions {
+        SetOptions {
+            encrypt: false,
+            mode: SetMode::Append(separator.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_set_append_and_prepend_existing_value() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        fs::write(
+            &config_path,
+            "version = \"1.0\"\n\n[environments.dev]\ndescription = \"Development\"\nPATH = \"/usr/bin\"\n",
+        )
+        .unwrap();
+
+        set_variable_with_options(
+            dir.path(),
+            "dev",
+            "PATH",
+            Some("/opt/tool/bin".to_string()),
+            &append(DEFAULT_SEPARATOR),
+        )
+        .unwrap();
+        let prepend = SetOptions {
+            encrypt: false,
+            mode: SetMode::Prepend(":".to_string()),
+        };
+        set_variable_with_options(
+            dir.path(),
+            "dev",
+            "PATH",
+            Some("/home/me/bin".to_string()),
+            &prepend,
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        let expected = format!("/home/me/bin:/usr/bin{}/opt/tool/bin", DEFAULT_SEPARATOR);
+        assert!(content.contains(&format!("PATH = \"{}\"", expected)));
+    }
+
+    #[test]
+    fn test_set_append_creates_missing_variable() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        fs::write(
+            &config_path,
+            "version = \"1.0\"\n\n[common]\n\n[environments.dev]\ndescription = \"Development\"\n",
+        )
+        .unwrap();
+
+        set_common_variable_with_options(
+            dir.path(),
+            "NODE_PATH",
+            Some("lib".to_string()),
+            &append(":"),
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.contains("NODE_PATH = \"lib\""));
+    }
+
+    #[test]
+    fn test_set_append_with_windows_separator() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        fs::write(
+            &config_path,
+            "version = \"1.0\"\n\n[environments.dev]\ndescription = \"Development\"\nPATH = 'C:\\Windows'\n",
+        )
+        .unwrap();
+
+        set_variable_with_options(
+            dir.path(),
+            "dev",
+            "PATH",
+            Some("C:\\Tools".to_string()),
+            &append(";"),
+        )
+        .unwrap();
+
+        let config = crate::config::loader::parse_config_toml(dir.path()).unwrap();
+        assert_eq!(
+            config.environments["dev"].variables["PATH"],
+            "C:\\Windows;C:\\Tools"
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_set_append_to_encrypted_value_reencrypts() {
+        std::env::remove_var("STAND_PRIVATE_KEY");
+        let dir = tempdir().unwrap();
+        let key_pair = crate::crypto::keys::generate_key_pair();
+        crate::crypto::keys::save_private_key(
+            &dir.path().join(".stand.keys"),
+            &key_pair.private_key,
+        )
+        .unwrap();
+        let recipient = key_pair.to_recipient().unwrap();
+        let encrypted = crate::crypto::encrypt_value("a,b", &recipient).unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        fs::write(
+            &config_path,
+            format!(
+                "version = \"1.0\"\n\n[encryption]\npublic_key = \"{}\"\n\n\
+                 [environments.dev]\ndescription = \"Development\"\nHOSTS = \"{}\"\n",
+                key_pair.public_key, encrypted
+            ),
+        )
+        .unwrap();
+
+        set_variable_with_options(
+            dir.path(),
+            "dev",
+            "HOSTS",
+            Some("c".to_string()),
+            &append(","),
+        )
+        .unwrap();
+
+        let config = crate::config::loader::parse_config_toml(dir.path()).unwrap();
+        let value = &config.environments["dev"].variables["HOSTS"];
+        assert!(is_encrypted(value));
+        let identity = parse_private_key(&key_pair.private_key).unwrap();
+        assert_eq!(decrypt_value(value, &identity).unwrap(), "a,b,c");
+    }
 }
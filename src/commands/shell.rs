@@ -2,42 +2,34 @@
 //
 // Start an interactive subshell with environment variables loaded.
 
+use crate::commands::CommandContext;
 use crate::config::loader;
-use crate::config::types::NestedBehavior;
+use crate::config::types::{NestedBehavior, ShellCwd};
 use crate::crypto::decrypt_variables;
+use crate::environment::resolver::{
+    EnvironmentResolver, ResolutionOptions, UndefinedVariableBehavior, VariableSource,
+};
 use crate::shell::{
-    build_shell_environment, detect_user_shell, get_active_environment, is_stand_shell_active,
-    spawn_shell,
+    build_shell_environment, detect_user_shell, get_active_environment, is_executable_file,
+    is_stand_shell_active, spawn_shell,
 };
+use crate::utils::colors::warning_mark;
+use crate::utils::confirm::ensure_confirmed;
 use anyhow::{anyhow, Result};
-use std::io::{self, IsTerminal, Write};
-use std::path::Path;
-
-/// Check if stdin is an interactive terminal
-///
-/// Returns false if:
-/// - stdin is not a TTY
-/// - STAND_FORCE_NON_TTY environment variable is set (for testing)
-fn is_interactive_terminal() -> bool {
-    if std::env::var("STAND_FORCE_NON_TTY").is_ok() {
-        return false;
-    }
-    io::stdin().is_terminal()
-}
-
-/// Prompt user for confirmation before executing in a protected environment
-fn prompt_confirmation(env_name: &str) -> Result<bool> {
-    print!(
-        "Environment '{}' requires confirmation.\nAre you sure you want to proceed? [y/N]: ",
-        env_name
-    );
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-
-    let response = input.trim().to_lowercase();
-    Ok(response == "y" || response == "yes")
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Options for the shell command
+#[derive(Debug, Clone, Default)]
+pub struct ShellOptions {
+    /// Skip confirmation for environments with requires_confirmation=true
+    pub skip_confirmation: bool,
+    /// Use this shell instead of `settings.shell` and `$SHELL`
+    pub shell_override: Option<String>,
+    /// Extra dotenv files layered over the configured variables, later files winning
+    pub env_files: Vec<PathBuf>,
+    /// Export `encrypted:` values as stored instead of decrypting them
+    pub no_decrypt: bool,
 }
 
 /// Check if nesting is allowed based on configuration
@@ -63,6 +55,22 @@ fn check_nesting_allowed(behavior: Option<NestedBehavior>, current_env: &str) ->
     }
 }
 
+/// The shell from `settings.shell`, if it is set and executable
+///
+/// An unusable path prints a warning and returns `None` so `$SHELL` is used instead.
+fn configured_shell(shell: Option<&str>) -> Option<String> {
+    let shell = shell?;
+    if is_executable_file(Path::new(shell)) {
+        return Some(shell.to_string());
+    }
+    eprintln!(
+        "{} Warning: settings.shell '{}' is not an executable file; using $SHELL instead",
+        warning_mark(),
+        shell
+    );
+    None
+}
+
 /// Result of validating shell environment before spawning
 #[derive(Debug)]
 pub struct ValidatedShellEnv {
@@ -72,6 +80,10 @@ pub struct ValidatedShellEnv {
     pub env_vars: std::collections::HashMap<String, String>,
     /// Name of the environment
     pub env_name: String,
+    /// Whether informational banners are suppressed by `settings.quiet`
+    pub quiet: bool,
+    /// Directory the shell starts in (`settings.shell_cwd`); `None` keeps the current one
+    pub working_dir: Option<PathBuf>,
 }
 
 /// Validate and prepare shell environment without spawning
@@ -88,6 +100,23 @@ pub fn validate_shell_environment(
     env_name: &str,
     skip_confirmation: bool,
     shell_override: Option<String>,
+) -> Result<ValidatedShellEnv> {
+    let options = ShellOptions {
+        skip_confirmation,
+        shell_override,
+        ..ShellOptions::default()
+    };
+    validate_shell_environment_with_options(project_path, env_name, &options)
+}
+
+/// Validate and prepare the shell environment with explicit options
+///
+/// Like [`validate_shell_environment`], and also layers `options.env_files` over the
+/// configured variables. A missing env file is an error.
+pub fn validate_shell_environment_with_options(
+    project_path: &Path,
+    env_name: &str,
+    options: &ShellOptions,
 ) -> Result<ValidatedShellEnv> {
     // Load configuration with inheritance applied
     let config = loader::load_config_toml_with_inheritance(project_path)?;
@@ -110,29 +139,31 @@ pub fn validate_shell_environment(
     })?;
 
     // Check if confirmation is required
-    if env.requires_confirmation.unwrap_or(false) && !skip_confirmation {
-        // Check if stdin is a terminal - fail fast in non-interactive environments
-        if !is_interactive_terminal() {
-            return Err(anyhow!(
-                "Environment '{}' requires confirmation but stdin is not a terminal.\n\
-                 Use -y or --yes to skip confirmation in non-interactive environments.",
-                env_name
-            ));
-        }
-        // Prompt user for confirmation
-        if !prompt_confirmation(env_name)? {
-            return Err(anyhow!(
-                "Execution cancelled. Use -y or --yes to skip confirmation."
-            ));
-        }
-    }
-
-    // Get shell path (use override if provided, otherwise detect from $SHELL)
-    let shell_path = shell_override.unwrap_or_else(detect_user_shell);
+    ensure_confirmed(
+        env_name,
+        env.requires_confirmation.unwrap_or(false),
+        options.skip_confirmation,
+    )?;
+
+    // Get shell path: --shell, then settings.shell, then $SHELL
+    let shell_path = options
+        .shell_override
+        .clone()
+        .or_else(|| configured_shell(config.settings.shell.as_deref()))
+        .unwrap_or_else(detect_user_shell);
 
     // Decrypt any encrypted variables
-    let decrypted_vars = decrypt_variables(env.variables.clone(), project_path)
-        .map_err(|e| anyhow!("Failed to decrypt variables: {}", e))?;
+    let decrypted_vars = if options.no_decrypt {
+        env.variables.clone()
+    } else {
+        decrypt_variables(env.variables.clone(), project_path)
+            .map_err(|e| anyhow!("Failed to decrypt variables: {}", e))?
+    };
+    let decrypted_vars = if options.env_files.is_empty() {
+        decrypted_vars
+    } else {
+        layer_env_files(decrypted_vars, &options.env_files)?
+    };
 
     // Build environment with Stand markers
     let project_root = project_path
@@ -155,33 +186,69 @@ pub fn validate_shell_environment(
         shell_path,
         env_vars: shell_env,
         env_name: env_name.to_string(),
+        quiet: config.settings.quiet.unwrap_or(false),
+        working_dir: (config.settings.shell_cwd == Some(ShellCwd::ProjectRoot))
+            .then(|| project_path.to_path_buf()),
     })
 }
 
+/// Layer dotenv files over the configured variables (later files win)
+fn layer_env_files(
+    variables: HashMap<String, String>,
+    env_files: &[PathBuf],
+) -> Result<HashMap<String, String>> {
+    let mut resolver = EnvironmentResolver::new();
+    // Configured values are final; escape them so a literal `${VAR}` (written as
+    // `$${VAR}` in .stand.toml) is not expanded a second time
+    let configured = variables
+        .into_iter()
+        .map(|(key, value)| (key, value.replace('$', "$$")))
+        .collect();
+    resolver.add_source(VariableSource::Default(configured));
+    for path in env_files {
+        resolver.add_source(VariableSource::EnvFile(path.clone()));
+    }
+
+    let resolved = resolver.resolve_with_options(&ResolutionOptions {
+        undefined_variable_behavior: UndefinedVariableBehavior::LeaveUnexpanded,
+        ..ResolutionOptions::default()
+    })?;
+
+    Ok(resolved.into_iter().collect())
+}
+
 /// Start an interactive shell with the specified environment
 ///
 /// # Arguments
 /// * `project_path` - Path to the project directory containing .stand.toml
 /// * `env_name` - Name of the environment to use
-/// * `skip_confirmation` - If true, skip confirmation for environments with requires_confirmation=true
-/// * `shell_override` - If provided, use this shell instead of $SHELL
+/// * `options` - Confirmation, shell override and extra env files
+/// * `ctx` - Global options; a quiet context suppresses the start banner (as does `settings.quiet`)
+/// * `command` - If given, run this command in the shell and exit with its exit code
 pub fn start_shell_with_environment(
     project_path: &Path,
     env_name: &str,
-    skip_confirmation: bool,
-    shell_override: Option<String>,
+    options: &ShellOptions,
+    ctx: &CommandContext,
+    command: Option<&str>,
 ) -> Result<i32> {
-    let validated =
-        validate_shell_environment(project_path, env_name, skip_confirmation, shell_override)?;
+    let validated = validate_shell_environment_with_options(project_path, env_name, options)?;
 
-    // Print info message
-    eprintln!(
-        "Starting shell with environment '{}'. Type 'exit' to return.",
-        validated.env_name
-    );
+    // Print info message unless suppressed or there is no interactive session
+    if !(ctx.is_quiet() || validated.quiet || command.is_some()) {
+        eprintln!(
+            "Starting shell with environment '{}'. Type 'exit' to return.",
+            validated.env_name
+        );
+    }
 
     // Spawn the shell
-    spawn_shell(&validated.shell_path, validated.env_vars)
+    spawn_shell(
+        &validated.shell_path,
+        validated.env_vars,
+        command,
+        validated.working_dir.as_deref(),
+    )
 }
 
 #[cfg(test)]
@@ -385,6 +452,51 @@ DATABASE_URL = "postgres://prod:5432/prod"
         assert_eq!(validated.env_name, "prod");
     }
 
+    #[test]
+    #[serial]
+    fn test_shell_env_files_override_config_values() {
+        env::remove_var("STAND_ACTIVE");
+        env::remove_var("STAND_ENVIRONMENT");
+
+        let dir = tempdir().unwrap();
+        let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_URL = "http://localhost:3000"
+API_TOKEN = "from-config"
+"#;
+        fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+        let env_file = dir.path().join(".env");
+        fs::write(
+            &env_file,
+            "API_TOKEN=local-secret\nEXTRA=${API_URL}/extra\n",
+        )
+        .unwrap();
+
+        let options = ShellOptions {
+            env_files: vec![env_file],
+            ..ShellOptions::default()
+        };
+        let validated =
+            validate_shell_environment_with_options(dir.path(), "dev", &options).unwrap();
+
+        assert_eq!(validated.env_vars["API_TOKEN"], "local-secret");
+        assert_eq!(validated.env_vars["API_URL"], "http://localhost:3000");
+        assert_eq!(validated.env_vars["EXTRA"], "http://localhost:3000/extra");
+        assert_eq!(validated.env_vars["STAND_ENVIRONMENT"], "dev");
+
+        let options = ShellOptions {
+            env_files: vec![dir.path().join(".env.missing")],
+            ..ShellOptions::default()
+        };
+        let error = validate_shell_environment_with_options(dir.path(), "dev", &options)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(".env.missing"));
+    }
+
     #[test]
     #[serial]
     fn test_shell_auto_exit_sets_env_var_when_enabled() {
@@ -446,6 +558,56 @@ description = "Development environment"
         assert!(!validated.env_vars.contains_key("STAND_AUTO_EXIT"));
     }
 
+    #[test]
+    #[serial]
+    fn test_shell_quiet_setting_is_propagated() {
+        // Ensure we're not in a Stand shell
+        env::remove_var("STAND_ACTIVE");
+        env::remove_var("STAND_ENVIRONMENT");
+
+        let dir = tempdir().unwrap();
+        let config_content = r#"
+version = "2.0"
+
+[settings]
+quiet = true
+
+[environments.dev]
+description = "Development environment"
+"#;
+
+        let config_path = dir.path().join(".stand.toml");
+        fs::write(&config_path, config_content).unwrap();
+
+        let validated = validate_shell_environment(dir.path(), "dev", false, None).unwrap();
+        assert!(validated.quiet);
+    }
+
+    #[test]
+    #[serial]
+    fn test_shell_cwd_setting_sets_working_dir() {
+        env::remove_var("STAND_ACTIVE");
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(".stand.toml");
+        let config = |shell_cwd: &str| {
+            format!(
+                "version = \"2.0\"\n\n[settings]\n{}\n\n[environments.dev]\ndescription = \"Dev\"\n",
+                shell_cwd
+            )
+        };
+
+        fs::write(&config_path, config("shell_cwd = \"project_root\"")).unwrap();
+        let project_root = validate_shell_environment(dir.path(), "dev", false, None).unwrap();
+        fs::write(&config_path, config("shell_cwd = \"current\"")).unwrap();
+        let current = validate_shell_environment(dir.path(), "dev", false, None).unwrap();
+        fs::write(&config_path, config("")).unwrap();
+        let default = validate_shell_environment(dir.path(), "dev", false, None).unwrap();
+
+        assert_eq!(project_root.working_dir.as_deref(), Some(dir.path()));
+        assert_eq!(current.working_dir, None);
+        assert_eq!(default.working_dir, None);
+    }
+
     #[test]
     #[serial]
     fn test_shell_auto_exit_enabled_by_default() {
@@ -474,4 +636,63 @@ description = "Development environment"
             Some(&"1".to_string())
         );
     }
+
+    fn restore_shell(original: Option<String>) {
+        match original {
+            Some(shell) => env::set_var("SHELL", shell),
+            None => env::remove_var("SHELL"),
+        }
+    }
+
+    fn write_shell_config(dir: &Path, shell: &str) {
+        fs::write(
+            dir.join(".stand.toml"),
+            format!(
+                "version = \"2.0\"\n\n[settings]\nshell = \"{}\"\n\n\
+                 [environments.dev]\ndescription = \"Development environment\"\n",
+                shell
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_shell_uses_settings_shell() {
+        env::remove_var("STAND_ACTIVE");
+        let original_shell = env::var("SHELL").ok();
+        env::set_var("SHELL", "/bin/sh");
+        let dir = tempdir().unwrap();
+        write_shell_config(dir.path(), "/bin/bash");
+
+        let validated = validate_shell_environment(dir.path(), "dev", false, None).unwrap();
+        let overridden =
+            validate_shell_environment(dir.path(), "dev", false, Some("/bin/sh".into())).unwrap();
+
+        restore_shell(original_shell);
+
+        assert_eq!(validated.shell_path, "/bin/bash");
+        // --shell takes precedence over settings.shell
+        assert_eq!(overridden.shell_path, "/bin/sh");
+    }
+
+    #[test]
+    #[serial]
+    fn test_shell_falls_back_when_settings_shell_unusable() {
+        env::remove_var("STAND_ACTIVE");
+        let original_shell = env::var("SHELL").ok();
+        env::set_var("SHELL", "/bin/sh");
+        let dir = tempdir().unwrap();
+
+        write_shell_config(dir.path(), "/nonexistent/bash");
+        let missing = validate_shell_environment(dir.path(), "dev", false, None).unwrap();
+        // A directory is not an executable file
+        write_shell_config(dir.path(), &dir.path().display().to_string());
+        let directory = validate_shell_environment(dir.path(), "dev", false, None).unwrap();
+
+        restore_shell(original_shell);
+
+        assert_eq!(missing.shell_path, "/bin/sh");
+        assert_eq!(directory.shell_path, "/bin/sh");
+    }
 }
@@ -1,48 +1,156 @@
-use crate::config::{loader, ConfigError};
+use crate::config::context::ConfigContext;
+use crate::config::types::{Configuration, Environment};
+use crate::config::ConfigError;
 use crate::crypto::is_encrypted;
+use crate::utils::colors::{
+    colorize_environment, colors_enabled, mask_value_with_options, MaskOptions,
+};
 use anyhow::{anyhow, Result};
 use std::collections::HashMap;
 use std::path::Path;
 
+/// Options for the inspect command
+#[derive(Debug, Default, Clone)]
+pub struct ShowOptions {
+    /// Show actual values
+    pub show_values: bool,
+    /// Show values partially masked according to `settings.mask_visible_chars`
+    pub mask: bool,
+    /// Disable colored output (also disabled by the `NO_COLOR` environment variable)
+    pub no_color: bool,
+    /// Show `${VAR}` references to unset system variables instead of failing, and list them
+    pub resolve_system: bool,
+    /// With `show_values`, annotate values that differ in this environment
+    pub values_from: Option<String>,
+    /// Only show variables written in the environment's own section, without
+    /// `[common]` or `extends`
+    pub no_inherit: bool,
+}
+
+/// How variable values are rendered
+#[derive(Debug, Clone)]
+enum ValueDisplay {
+    Hidden,
+    Masked(MaskOptions),
+    Plain,
+    /// Like `Plain`, but encrypted values are printed as stored (`settings.auto_decrypt = false`)
+    Raw,
+}
+
 /// Shows environment variables for the specified environment
 pub fn show_environment(project_path: &Path, env_name: &str, show_values: bool) -> Result<String> {
-    // Load configuration with inheritance applied
-    let config_with_inheritance = loader::load_config_toml_with_inheritance(project_path)?;
+    show_environment_with_options(
+        project_path,
+        env_name,
+        &ShowOptions {
+            show_values,
+            ..ShowOptions::default()
+        },
+    )
+}
 
-    // Load raw configuration for source detection
-    let raw_config = loader::load_config_toml(project_path)?;
+/// Shows environment variables for the specified environment with display options
+pub fn show_environment_with_options(
+    project_path: &Path,
+    env_name: &str,
+    options: &ShowOptions,
+) -> Result<String> {
+    let context = if options.resolve_system {
+        ConfigContext::load_leaving_unset(project_path)?
+    } else {
+        ConfigContext::load(project_path)?
+    };
+    show_environment_with_config(&context, env_name, options)
+}
+
+/// Shows environment variables from an already loaded configuration
+///
+/// Values come from the inheritance-applied view and sources from the raw one.
+/// With `options.no_inherit`, values come from the raw view as well.
+pub fn show_environment_with_config(
+    context: &ConfigContext,
+    env_name: &str,
+    options: &ShowOptions,
+) -> Result<String> {
+    let config_with_inheritance = context.resolved();
+    let raw_config = context.raw();
+    let values_config = if options.no_inherit {
+        raw_config
+    } else {
+        config_with_inheritance
+    };
 
     // Check if environment exists
-    let env = config_with_inheritance
-        .environments
-        .get(env_name)
-        .ok_or_else(|| {
-            let mut available: Vec<_> = config_with_inheritance
-                .environments
-                .keys()
-                .cloned()
-                .collect();
-            available.sort();
-            anyhow!(
-                "Environment '{}' not found. Available: {}",
-                env_name,
-                available.join(", ")
-            )
-        })?;
+    let env = find_environment(config_with_inheritance, env_name)?;
+    let variables = &find_environment(values_config, env_name)?.variables;
 
     // Detect variable sources
-    let sources = detect_variable_sources(&raw_config, env_name).map_err(anyhow::Error::from)?;
+    let sources = detect_variable_sources(raw_config, env_name).map_err(anyhow::Error::from)?;
+
+    let display = if options.show_values && raw_config.settings.auto_decrypt == Some(false) {
+        ValueDisplay::Raw
+    } else if options.show_values {
+        ValueDisplay::Plain
+    } else if options.mask {
+        ValueDisplay::Masked(MaskOptions {
+            visible_prefix: raw_config.settings.mask_visible_chars.unwrap_or(0),
+            ..MaskOptions::default()
+        })
+    } else {
+        ValueDisplay::Hidden
+    };
 
     // Format output
-    let output = format_variables(env_name, &env.variables, &sources, show_values);
+    let style = FormatStyle {
+        color: !options.no_color && colors_enabled(),
+        env_color: env.color.as_deref(),
+    };
+    let comparison = match &options.values_from {
+        Some(other) if options.show_values => Some(Comparison {
+            env_name: other,
+            variables: &find_environment(values_config, other)?.variables,
+        }),
+        _ => None,
+    };
+    let mut output = format_variables(
+        env_name,
+        variables,
+        &sources,
+        &display,
+        &style,
+        comparison.as_ref(),
+    );
+
+    if options.resolve_system {
+        let unset = context.unset_variables(env_name);
+        let summary = if unset.is_empty() {
+            "none".to_string()
+        } else {
+            unset.join(", ")
+        };
+        output.push_str(&format!("\nUnset system variables: {}\n", summary));
+    }
 
     Ok(output)
 }
 
+/// Look up `env_name`, listing the available environments if it does not exist
+fn find_environment<'a>(config: &'a Configuration, env_name: &str) -> Result<&'a Environment> {
+    config.environments.get(env_name).ok_or_else(|| {
+        anyhow!(
+            "Environment '{}' not found. Available: {}",
+            env_name,
+            config.sorted_environment_names().join(", ")
+        )
+    })
+}
+
 /// Enum to represent the source of a variable
 #[derive(Debug, Clone, PartialEq)]
 enum VarSource {
     Local,
+    /// Defined locally and also in `[common]`, which it shadows
+    OverridesCommon,
     Inherited(String),
     Common,
 }
@@ -86,7 +194,12 @@ fn detect_variable_sources(
     for var_name in all_vars.keys() {
         // Check if variable is defined locally in the target environment
         if env.variables.contains_key(var_name) {
-            sources.insert(var_name.clone(), VarSource::Local);
+            let source = if common_vars.contains_key(var_name) {
+                VarSource::OverridesCommon
+            } else {
+                VarSource::Local
+            };
+            sources.insert(var_name.clone(), source);
         } else {
             // Check inheritance chain (excluding the target environment itself)
             let mut found_in_ancestor = false;
@@ -114,39 +227,90 @@ fn detect_variable_sources(
 }
 
 /// Get inheritance chain from environment to root (including the environment itself)
+///
+/// Ancestors are listed in precedence order: later parents before earlier ones, each
+/// followed by its own ancestors. Shared ancestors appear once.
 fn get_inheritance_chain(
     config: &crate::config::types::Configuration,
     env_name: &str,
 ) -> Result<Vec<String>, ConfigError> {
     let mut chain = Vec::new();
-    let mut current = env_name;
+    let mut pending = vec![env_name];
 
-    loop {
+    while let Some(current) = pending.pop() {
+        if chain.iter().any(|name| name == current) {
+            continue;
+        }
         chain.push(current.to_string());
 
         if let Some(env) = config.environments.get(current) {
-            if let Some(parent) = &env.extends {
-                current = parent;
-            } else {
-                break;
-            }
-        } else {
-            break;
+            // Pushed in order so the last parent is visited first
+            pending.extend(env.parents().iter().map(String::as_str));
         }
     }
 
     Ok(chain)
 }
 
+/// Coloring applied to the inspect output
+#[derive(Debug, Clone, Default)]
+struct FormatStyle<'a> {
+    /// Whether ANSI colors are emitted at all
+    color: bool,
+    /// Configured color of the environment, used for the header
+    env_color: Option<&'a str>,
+}
+
+impl FormatStyle<'_> {
+    fn paint(&self, text: &str, color: Option<&str>) -> String {
+        if self.color {
+            colorize_environment(text, color)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Another environment whose values are shown next to differing ones (`--values-from`)
+#[derive(Debug, Clone)]
+struct Comparison<'a> {
+    env_name: &'a str,
+    variables: &'a HashMap<String, String>,
+}
+
+impl Comparison<'_> {
+    /// ` (prod: 8080)` when the other environment's value differs, empty otherwise
+    ///
+    /// Encrypted values are never shown; different ciphertexts are reported as
+    /// `[ENCRYPTED]` since they cannot be compared without decrypting.
+    fn annotation(&self, var_name: &str, value: &str, style: &FormatStyle) -> String {
+        let other = match self.variables.get(var_name) {
+            Some(other) if other == value => return String::new(),
+            Some(other) if is_encrypted(other) => "[ENCRYPTED]",
+            Some(other) => other.as_str(),
+            None => "not set",
+        };
+        format!(
+            " {}",
+            style.paint(&format!("({}: {})", self.env_name, other), Some("magenta"))
+        )
+    }
+}
+
 /// Format variables for display
 fn format_variables(
     env_name: &str,
     variables: &HashMap<String, String>,
     sources: &HashMap<String, VarSource>,
-    show_values: bool,
+    display: &ValueDisplay,
+    style: &FormatStyle,
+    comparison: Option<&Comparison>,
 ) -> String {
     let mut output = String::new();
-    output.push_str(&format!("Environment: {}\n", env_name));
+    output.push_str(&format!(
+        "Environment: {}\n",
+        style.paint(env_name, style.env_color)
+    ));
     output.push_str("Variables:\n");
 
     // Sort variables alphabetically
@@ -158,25 +322,35 @@ fn format_variables(
         let source = sources.get(var_name).unwrap_or(&VarSource::Local);
         let encrypted = is_encrypted(value);
 
-        let line = if show_values {
-            if encrypted {
+        let line = match display {
+            ValueDisplay::Plain | ValueDisplay::Masked(_) if encrypted => {
                 format!("  {}=[ENCRYPTED]", var_name)
-            } else {
-                format!("  {}={}", var_name, value)
             }
-        } else if encrypted {
-            format!("  {} [ENCRYPTED]", var_name)
-        } else {
-            format!("  {}", var_name)
+            ValueDisplay::Plain | ValueDisplay::Raw => format!("  {}={}", var_name, value),
+            ValueDisplay::Masked(mask) => {
+                format!("  {}={}", var_name, mask_value_with_options(value, mask))
+            }
+            ValueDisplay::Hidden if encrypted => format!("  {} [ENCRYPTED]", var_name),
+            ValueDisplay::Hidden => format!("  {}", var_name),
         };
 
         let suffix = match source {
             VarSource::Local => "".to_string(),
-            VarSource::Inherited(ancestor) => format!(" (inherited from {})", ancestor),
-            VarSource::Common => " (from common)".to_string(),
+            VarSource::OverridesCommon => {
+                format!(" {}", style.paint("(overrides common)", Some("cyan")))
+            }
+            VarSource::Inherited(ancestor) => format!(
+                " {}",
+                style.paint(&format!("(inherited from {})", ancestor), Some("yellow"))
+            ),
+            VarSource::Common => format!(" {}", style.paint("(from common)", Some("cyan"))),
         };
 
-        output.push_str(&format!("{}{}\n", line, suffix));
+        let difference = comparison
+            .map(|comparison| comparison.annotation(var_name, value, style))
+            .unwrap_or_default();
+
+        output.push_str(&format!("{}{}{}\n", line, difference, suffix));
     }
 
     output
@@ -186,6 +360,7 @@ fn format_variables(
 mod tests {
     use super::*;
     use crate::config::types::{Configuration, Environment, Settings};
+    use serial_test::serial;
     use std::collections::HashMap;
 
     fn create_test_config() -> Configuration {
@@ -211,6 +386,9 @@ mod tests {
                 variables: base_vars,
                 color: None,
                 requires_confirmation: None,
+                isolated: None,
+                default_command: None,
+                hidden: None,
             },
         );
 
@@ -218,10 +396,13 @@ mod tests {
             "dev".to_string(),
             Environment {
                 description: "Development environment".to_string(),
-                extends: Some("base".to_string()),
+                extends: Some("base".into()),
                 variables: dev_vars,
                 color: Some("green".to_string()),
                 requires_confirmation: None,
+                isolated: None,
+                default_command: None,
+                hidden: None,
             },
         );
 
@@ -230,6 +411,8 @@ mod tests {
             environments,
             common: Some(common),
             settings: Settings::default(),
+            encryption: None,
+            include: Vec::new(),
         }
     }
 
@@ -267,6 +450,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_variable_sources_local_overrides_common() {
+        let mut config = create_test_config();
+        config
+            .environments
+            .get_mut("dev")
+            .unwrap()
+            .variables
+            .insert("LOG_FORMAT".to_string(), "text".to_string());
+
+        let sources = detect_variable_sources(&config, "dev").unwrap();
+
+        assert_eq!(sources.get("LOG_FORMAT"), Some(&VarSource::OverridesCommon));
+        assert_eq!(sources.get("DEBUG"), Some(&VarSource::Local));
+        assert_eq!(sources.get("APP_NAME"), Some(&VarSource::Common));
+    }
+
     #[test]
     fn test_format_variables_names_only() {
         let mut variables = HashMap::new();
@@ -277,7 +477,14 @@ mod tests {
         sources.insert("APP_NAME".to_string(), VarSource::Common);
         sources.insert("DEBUG".to_string(), VarSource::Local);
 
-        let output = format_variables("dev", &variables, &sources, false);
+        let output = format_variables(
+            "dev",
+            &variables,
+            &sources,
+            &ValueDisplay::Hidden,
+            &FormatStyle::default(),
+            None,
+        );
 
         assert!(output.contains("Environment: dev"));
         assert!(output.contains("Variables:"));
@@ -299,7 +506,14 @@ mod tests {
         sources.insert("APP_NAME".to_string(), VarSource::Common);
         sources.insert("DEBUG".to_string(), VarSource::Local);
 
-        let output = format_variables("dev", &variables, &sources, true);
+        let output = format_variables(
+            "dev",
+            &variables,
+            &sources,
+            &ValueDisplay::Plain,
+            &FormatStyle::default(),
+            None,
+        );
 
         assert!(output.contains("Environment: dev"));
         assert!(output.contains("APP_NAME=MyApp (from common)"));
@@ -318,15 +532,307 @@ mod tests {
         sources.insert("DEBUG".to_string(), VarSource::Local);
 
         // Test with show_values=true
-        let output = format_variables("dev", &variables, &sources, true);
+        let output = format_variables(
+            "dev",
+            &variables,
+            &sources,
+            &ValueDisplay::Plain,
+            &FormatStyle::default(),
+            None,
+        );
         assert!(output.contains("API_KEY=[ENCRYPTED]"));
         assert!(!output.contains("encrypted:abc123"));
         assert!(output.contains("DEBUG=true"));
 
         // Test with show_values=false
-        let output = format_variables("dev", &variables, &sources, false);
+        let output = format_variables(
+            "dev",
+            &variables,
+            &sources,
+            &ValueDisplay::Hidden,
+            &FormatStyle::default(),
+            None,
+        );
         assert!(output.contains("API_KEY [ENCRYPTED]"));
         assert!(output.contains("DEBUG"));
         assert!(!output.contains("DEBUG [ENCRYPTED]"));
     }
+
+    #[test]
+    #[serial]
+    fn test_format_variables_colors_sources_and_header() {
+        colored::control::set_override(true);
+
+        let mut variables = HashMap::new();
+        variables.insert("APP_NAME".to_string(), "MyApp".to_string());
+        variables.insert("PORT".to_string(), "3000".to_string());
+        let mut sources = HashMap::new();
+        sources.insert("APP_NAME".to_string(), VarSource::Common);
+        sources.insert("PORT".to_string(), VarSource::Inherited("base".to_string()));
+
+        let style = FormatStyle {
+            color: true,
+            env_color: Some("green"),
+        };
+        let output = format_variables(
+            "dev",
+            &variables,
+            &sources,
+            &ValueDisplay::Hidden,
+            &style,
+            None,
+        );
+
+        colored::control::unset_override();
+
+        assert!(output.contains("\x1b["));
+        assert!(output.contains(&colorize_environment("dev", Some("green"))));
+        assert!(output.contains(&colorize_environment("(from common)", Some("cyan"))));
+        assert!(output.contains(&colorize_environment(
+            "(inherited from base)",
+            Some("yellow")
+        )));
+    }
+
+    #[test]
+    #[serial]
+    fn test_show_environment_no_color_env_var_disables_ansi() {
+        colored::control::set_override(true);
+        std::env::set_var("NO_COLOR", "1");
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".stand.toml"),
+            r#"
+version = "2.0"
+
+[common]
+APP_NAME = "MyApp"
+
+[environments.dev]
+description = "Development"
+color = "green"
+DEBUG = "true"
+"#,
+        )
+        .unwrap();
+
+        let output = show_environment(dir.path(), "dev", false);
+
+        std::env::remove_var("NO_COLOR");
+        colored::control::unset_override();
+
+        let output = output.unwrap();
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("Environment: dev"));
+        assert!(output.contains("APP_NAME (from common)"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_show_environment_no_color_option_disables_ansi() {
+        colored::control::set_override(true);
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".stand.toml"),
+            r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+color = "green"
+DEBUG = "true"
+"#,
+        )
+        .unwrap();
+
+        let options = ShowOptions {
+            no_color: true,
+            ..ShowOptions::default()
+        };
+        let output = show_environment_with_options(dir.path(), "dev", &options);
+
+        colored::control::unset_override();
+
+        assert!(!output.unwrap().contains('\x1b'));
+    }
+
+    #[test]
+    fn test_show_environment_reads_config_once() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".stand.toml"),
+            r#"
+version = "2.0"
+
+[common]
+APP_NAME = "MyApp"
+
+[environments.base]
+description = "Base"
+PORT = "3000"
+
+[environments.dev]
+description = "Development"
+extends = "base"
+DEBUG = "true"
+"#,
+        )
+        .unwrap();
+
+        let loads = std::cell::Cell::new(0);
+        let context = ConfigContext::load_with(dir.path(), |path| {
+            loads.set(loads.get() + 1);
+            crate::config::loader::load_config_toml_uninterpolated(path)
+        })
+        .unwrap();
+        let options = ShowOptions {
+            no_color: true,
+            ..ShowOptions::default()
+        };
+        let output = show_environment_with_config(&context, "dev", &options).unwrap();
+
+        assert_eq!(loads.get(), 1);
+        assert!(output.contains("APP_NAME (from common)"));
+        assert!(output.contains("PORT (inherited from base)"));
+        assert!(output.contains("  DEBUG\n"));
+    }
+
+    #[test]
+    fn test_show_environment_no_inherit_shows_only_local_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".stand.toml"),
+            r#"
+version = "2.0"
+
+[common]
+APP_NAME = "MyApp"
+LOG_LEVEL = "info"
+
+[environments.base]
+description = "Base"
+PORT = "3000"
+
+[environments.dev]
+description = "Development"
+extends = "base"
+DEBUG = "true"
+LOG_LEVEL = "debug"
+"#,
+        )
+        .unwrap();
+
+        let options = ShowOptions {
+            show_values: true,
+            no_color: true,
+            no_inherit: true,
+            ..ShowOptions::default()
+        };
+        let output = show_environment_with_options(dir.path(), "dev", &options).unwrap();
+
+        assert!(output.contains("DEBUG=true"));
+        assert!(output.contains("LOG_LEVEL=debug"));
+        assert!(!output.contains("APP_NAME"));
+        assert!(!output.contains("PORT"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_show_environment_resolve_system_keeps_unset_references() {
+        std::env::remove_var("STAND_TEST_UNSET_TOKEN");
+        std::env::set_var("STAND_TEST_SET_HOST", "db.local");
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".stand.toml"),
+            r#"
+version = "2.0"
+
+[common]
+TOKEN = "${STAND_TEST_UNSET_TOKEN}"
+
+[environments.dev]
+description = "Development"
+DATABASE_HOST = "${STAND_TEST_SET_HOST}"
+"#,
+        )
+        .unwrap();
+
+        // Without the flag the unset reference is an error
+        assert!(show_environment(dir.path(), "dev", true).is_err());
+
+        let options = ShowOptions {
+            show_values: true,
+            no_color: true,
+            resolve_system: true,
+            ..ShowOptions::default()
+        };
+        let output = show_environment_with_options(dir.path(), "dev", &options);
+        std::env::remove_var("STAND_TEST_SET_HOST");
+
+        let output = output.unwrap();
+        assert!(output.contains("  TOKEN=${STAND_TEST_UNSET_TOKEN} (from common)\n"));
+        assert!(output.contains("  DATABASE_HOST=db.local\n"));
+        assert!(output.ends_with("\nUnset system variables: STAND_TEST_UNSET_TOKEN\n"));
+    }
+
+    #[test]
+    fn test_show_environment_values_from_annotates_differences() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".stand.toml"),
+            r#"
+version = "2.0"
+
+[common]
+APP_NAME = "MyApp"
+
+[environments.dev]
+description = "Development"
+PORT = "3000"
+DEBUG = "true"
+TOKEN = "encrypted:ZGV2"
+
+[environments.prod]
+description = "Production"
+PORT = "8080"
+TOKEN = "encrypted:cHJvZA=="
+REPLICAS = "3"
+"#,
+        )
+        .unwrap();
+
+        let options = ShowOptions {
+            show_values: true,
+            no_color: true,
+            values_from: Some("prod".to_string()),
+            ..ShowOptions::default()
+        };
+        let output = show_environment_with_options(dir.path(), "dev", &options).unwrap();
+
+        assert!(output.contains("  PORT=3000 (prod: 8080)\n"));
+        assert!(output.contains("  DEBUG=true (prod: not set)\n"));
+        assert!(output.contains("  TOKEN=[ENCRYPTED] (prod: [ENCRYPTED])\n"));
+        // Equal values are not annotated; keys only defined in prod are not listed
+        assert!(output.contains("  APP_NAME=MyApp (from common)\n"));
+        assert!(!output.contains("REPLICAS"));
+
+        // Without --values there is nothing to compare
+        let options = ShowOptions {
+            show_values: false,
+            ..options
+        };
+        let output = show_environment_with_options(dir.path(), "dev", &options).unwrap();
+        assert!(output.contains("  PORT\n"));
+
+        let options = ShowOptions {
+            show_values: true,
+            values_from: Some("missing".to_string()),
+            ..ShowOptions::default()
+        };
+        let err = show_environment_with_options(dir.path(), "dev", &options).unwrap_err();
+        assert!(err.to_string().contains("Environment 'missing' not found"));
+    }
 }
@@ -0,0 +1,149 @@
+// switch.rs command implementation
+//
+// Persist the active environment to the project state file.
+
+use crate::config::loader;
+use crate::state::history::append_history_entry;
+use crate::state::persistence::{load_state_from, save_state_to};
+use crate::utils::confirm::ensure_confirmed;
+use crate::utils::paths::find_project_root_from;
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// Switch the active environment for the project
+///
+/// # Arguments
+/// * `project_path` - Path inside the project directory containing .stand.toml
+/// * `env_name` - Name of the environment to activate
+/// * `skip_confirmation` - If true, skip confirmation for environments with requires_confirmation=true
+///
+/// The state file is only written after the environment exists and any required
+/// confirmation has been given. Each switch is also appended to `.stand/history`.
+pub fn switch_environment(
+    project_path: &Path,
+    env_name: &str,
+    skip_confirmation: bool,
+) -> Result<()> {
+    let project_root = find_project_root_from(project_path)?;
+
+    // Load configuration with inheritance applied
+    let config = loader::load_config_toml_with_inheritance(&project_root)?;
+
+    // Check if environment exists
+    let env = config.environments.get(env_name).ok_or_else(|| {
+        let mut available: Vec<_> = config.environments.keys().cloned().collect();
+        available.sort();
+        anyhow!(
+            "Environment '{}' not found. Available: {}",
+            env_name,
+            available.join(", ")
+        )
+    })?;
+
+    // Check if confirmation is required
+    ensure_confirmed(
+        env_name,
+        env.requires_confirmation.unwrap_or(false),
+        skip_confirmation,
+    )?;
+
+    let mut state = load_state_from(&project_root)?;
+    state.set_current_environment(env_name.to_string());
+    state.set_project_root(project_root.to_string_lossy().to_string());
+    save_state_to(&project_root, &state)?;
+
+    // The switch itself succeeded; a history write failure is only worth a warning
+    if let Err(e) = append_history_entry(&project_root, env_name) {
+        eprintln!("Warning: Failed to record history: {}", e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::env;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+DATABASE_URL = "postgres://localhost:5432/dev"
+
+[environments.prod]
+description = "Production environment"
+requires_confirmation = true
+DATABASE_URL = "postgres://prod:5432/prod"
+"#;
+
+    #[test]
+    fn test_switch_persists_environment() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        switch_environment(dir.path(), "dev", false).unwrap();
+
+        let state = load_state_from(dir.path()).unwrap();
+        assert_eq!(state.get_current_environment(), Some("dev"));
+    }
+
+    #[test]
+    fn test_switch_nonexistent_environment() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let result = switch_environment(dir.path(), "staging", false);
+
+        assert!(result.is_err());
+        let error_msg = format!("{}", result.unwrap_err());
+        assert!(error_msg.contains("not found"));
+        assert!(!dir.path().join(".stand").join("state.json").exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_requires_confirmation_non_tty() {
+        env::set_var("STAND_FORCE_NON_TTY", "1");
+
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        switch_environment(dir.path(), "dev", false).unwrap();
+
+        let result = switch_environment(dir.path(), "prod", false);
+
+        // Clean up
+        env::remove_var("STAND_FORCE_NON_TTY");
+
+        assert!(result.is_err());
+        let error_msg = format!("{}", result.unwrap_err());
+        assert!(error_msg.contains("requires confirmation"));
+        assert!(error_msg.contains("not a terminal"));
+
+        // State must still point at the previous environment
+        let state = load_state_from(dir.path()).unwrap();
+        assert_eq!(state.get_current_environment(), Some("dev"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_switch_skips_confirmation_with_yes_flag() {
+        env::set_var("STAND_FORCE_NON_TTY", "1");
+
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let result = switch_environment(dir.path(), "prod", true);
+
+        // Clean up
+        env::remove_var("STAND_FORCE_NON_TTY");
+
+        assert!(result.is_ok());
+        let state = load_state_from(dir.path()).unwrap();
+        assert_eq!(state.get_current_environment(), Some("prod"));
+    }
+}
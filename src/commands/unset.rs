@@ -0,0 +1,268 @@
+//! Unset command implementation.
+//!
+//! Removes one or all variables from an environment in the configuration file.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::utils::write_atomic;
+use toml_edit::{DocumentMut, Table};
+
+use crate::config::types::ENVIRONMENT_META_KEYS;
+use crate::utils::confirm::{Confirm, StdinConfirm};
+
+/// Remove a single variable from `[environments.<environment>]`.
+///
+/// Environment properties such as `description` cannot be removed this way.
+pub fn unset_variable(
+    project_dir: &Path,
+    environment: &str,
+    key: &str,
+) -> Result<(), UnsetCommandError> {
+    if ENVIRONMENT_META_KEYS.contains(&key) {
+        return Err(UnsetCommandError::NotAVariable(key.to_string()));
+    }
+
+    let config_path = project_dir.join(".stand.toml");
+    let mut doc = read_document(&config_path)?;
+    let env_table = environment_table(&mut doc, environment)?;
+
+    if env_table.remove(key).is_none() {
+        return Err(UnsetCommandError::VariableNotFound(key.to_string()));
+    }
+
+    write_atomic(&config_path, doc.to_string())?;
+    Ok(())
+}
+
+/// Remove every variable from `[environments.<environment>]`.
+///
+/// Keeps the environment properties (`description`, `color`, `extends`, ...).
+/// Asks for confirmation unless `skip_confirmation` is true, and refuses to run
+/// without `-y` when stdin is not a terminal. Returns the removed keys in file order.
+pub fn unset_all_variables(
+    project_dir: &Path,
+    environment: &str,
+    skip_confirmation: bool,
+) -> Result<Vec<String>, UnsetCommandError> {
+    unset_all_variables_with_confirm(
+        project_dir,
+        environment,
+        skip_confirmation,
+        &StdinConfirm::default(),
+    )
+}
+
+/// Like [`unset_all_variables`], asking for confirmation through `confirmer`
+pub fn unset_all_variables_with_confirm(
+    project_dir: &Path,
+    environment: &str,
+    skip_confirmation: bool,
+    confirmer: &dyn Confirm,
+) -> Result<Vec<String>, UnsetCommandError> {
+    let config_path = project_dir.join(".stand.toml");
+    let mut doc = read_document(&config_path)?;
+    let env_table = environment_table(&mut doc, environment)?;
+
+    // Variables are flattened into the environment table, so everything that is
+    // not a known property is a variable.
+    let keys: Vec<String> = env_table
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !ENVIRONMENT_META_KEYS.contains(&key.as_str()))
+        .collect();
+
+    if keys.is_empty() {
+        return Ok(keys);
+    }
+
+    if !skip_confirmation {
+        if !confirmer.is_interactive() {
+            return Err(UnsetCommandError::Cancelled(
+                "stdin is not a terminal. Use -y or --yes to skip confirmation.".to_string(),
+            ));
+        }
+        let question = format!(
+            "Remove all {} variable(s) from [environments.{}]?",
+            keys.len(),
+            environment
+        );
+        if !confirmer.confirm(&question, false)? {
+            return Err(UnsetCommandError::Cancelled(
+                "no variables were removed.".to_string(),
+            ));
+        }
+    }
+
+    for key in &keys {
+        env_table.remove(key);
+    }
+
+    write_atomic(&config_path, doc.to_string())?;
+    Ok(keys)
+}
+
+/// Read and parse the configuration with toml_edit to preserve formatting.
+fn read_document(config_path: &Path) -> Result<DocumentMut, UnsetCommandError> {
+    let content = fs::read_to_string(config_path)?;
+    content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| UnsetCommandError::TomlParse(e.to_string()))
+}
+
+/// Navigate to `[environments.<environment>]`.
+fn environment_table<'a>(
+    doc: &'a mut DocumentMut,
+    environment: &str,
+) -> Result<&'a mut Table, UnsetCommandError> {
+    doc.get_mut("environments")
+        .and_then(|e| e.get_mut(environment))
+        .and_then(|e| e.as_table_mut())
+        .ok_or_else(|| UnsetCommandError::EnvironmentNotFound(environment.to_string()))
+}
+
+/// Error type for unset command.
+#[derive(Debug, thiserror::Error)]
+pub enum UnsetCommandError {
+    #[error("Environment not found: {0}")]
+    EnvironmentNotFound(String),
+
+    #[error("Variable not found: {0}")]
+    VariableNotFound(String),
+
+    #[error("'{0}' is an environment property, not a variable")]
+    NotAVariable(String),
+
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
+    #[error("TOML parsing error: {0}")]
+    TomlParse(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("{0}")]
+    Prompt(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::loader;
+    use crate::utils::confirm::MockConfirm;
+    use serial_test::serial;
+    use std::env;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"version = "2.0"
+
+[environments.base]
+description = "Base"
+
+[environments.dev]
+description = "Development"
+extends = "base"
+color = "green"
+requires_confirmation = false
+API_URL = "http://localhost"
+DEBUG = "true"
+"#;
+
+    #[test]
+    fn test_unset_all_keeps_properties() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let removed = unset_all_variables(dir.path(), "dev", true).unwrap();
+
+        assert_eq!(removed, vec!["API_URL", "DEBUG"]);
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        let dev = &config.environments["dev"];
+        assert!(dev.variables.is_empty());
+        assert_eq!(dev.description, "Development");
+        assert_eq!(dev.color.as_deref(), Some("green"));
+        assert_eq!(dev.parents(), ["base"]);
+        assert_eq!(dev.requires_confirmation, Some(false));
+    }
+
+    #[test]
+    #[serial]
+    fn test_unset_all_requires_yes_without_tty() {
+        env::set_var("STAND_FORCE_NON_TTY", "1");
+
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let result = unset_all_variables(dir.path(), "dev", false);
+
+        env::remove_var("STAND_FORCE_NON_TTY");
+
+        assert!(matches!(result, Err(UnsetCommandError::Cancelled(_))));
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert_eq!(content, CONFIG);
+    }
+
+    #[test]
+    fn test_unset_all_follows_confirmation_answer() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let decline = MockConfirm::answering(false);
+        let result = unset_all_variables_with_confirm(dir.path(), "dev", false, &decline);
+        assert!(matches!(result, Err(UnsetCommandError::Cancelled(_))));
+        assert_eq!(
+            decline.prompts.borrow().as_slice(),
+            ["Remove all 2 variable(s) from [environments.dev]?"]
+        );
+        let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+        assert_eq!(content, CONFIG);
+
+        let accept = MockConfirm::answering(true);
+        let removed = unset_all_variables_with_confirm(dir.path(), "dev", false, &accept).unwrap();
+        assert_eq!(removed, vec!["API_URL", "DEBUG"]);
+    }
+
+    #[test]
+    fn test_unset_all_with_no_variables_is_noop() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let removed = unset_all_variables(dir.path(), "base", false).unwrap();
+
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn test_unset_single_variable() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        unset_variable(dir.path(), "dev", "DEBUG").unwrap();
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        let vars = &config.environments["dev"].variables;
+        assert!(!vars.contains_key("DEBUG"));
+        assert!(vars.contains_key("API_URL"));
+    }
+
+    #[test]
+    fn test_unset_single_variable_errors() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        assert!(matches!(
+            unset_variable(dir.path(), "dev", "MISSING"),
+            Err(UnsetCommandError::VariableNotFound(_))
+        ));
+        assert!(matches!(
+            unset_variable(dir.path(), "dev", "description"),
+            Err(UnsetCommandError::NotAVariable(_))
+        ));
+        assert!(matches!(
+            unset_variable(dir.path(), "prod", "DEBUG"),
+            Err(UnsetCommandError::EnvironmentNotFound(_))
+        ));
+    }
+}
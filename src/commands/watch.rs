@@ -0,0 +1,277 @@
+// watch.rs command implementation
+//
+// Run a command with an environment and restart it whenever the configuration changes.
+
+use crate::commands::exec::{build_executor, ExecOptions};
+use crate::config::loader::{self, dotenv_cascade_files, LOCAL_CONFIG_FILE, YAML_CONFIG_FILE};
+use crate::process::executor::ExecOutcome;
+use anyhow::{anyhow, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Quiet period after a change before the command is restarted, so an editor's
+/// several writes for one save cause a single restart
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// How long a stopped command may take to exit after SIGTERM before it is killed
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Interval between checks of the stop flag and the running command
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Options for the watch command
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Skip confirmation for environments with requires_confirmation=true
+    pub skip_confirmation: bool,
+    /// Start the command with a cleared environment (also enabled by `isolated = true`)
+    pub isolated: bool,
+    /// Run the command joined into one string with `$SHELL -c`
+    pub use_shell: bool,
+    /// Wait this long after a change before restarting
+    pub debounce: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            skip_confirmation: false,
+            isolated: false,
+            use_shell: false,
+            debounce: DEFAULT_DEBOUNCE,
+        }
+    }
+}
+
+/// Files whose changes restart the command
+///
+/// `.stand.toml` (or `.stand.yaml`), `.stand.local.toml`, the files included by
+/// `.stand.toml`, and with `settings.dotenv_cascade` the dotenv files of `env_name`.
+/// Files need not exist; creating one also counts as a change.
+pub fn watched_files(project_path: &Path, env_name: &str) -> Vec<PathBuf> {
+    let mut files = loader::included_files(project_path, &project_path.join(".stand.toml"));
+    files.push(project_path.join(YAML_CONFIG_FILE));
+    files.push(project_path.join(LOCAL_CONFIG_FILE));
+    let cascade = loader::load_config_toml_uninterpolated(project_path)
+        .is_ok_and(|config| config.settings.dotenv_cascade == Some(true));
+    if cascade {
+        files.extend(dotenv_cascade_files(project_path, env_name));
+    }
+    files
+}
+
+/// Set a flag once SIGINT or SIGTERM is received, instead of terminating (Unix only)
+///
+/// Pass the flag to [`watch_with_options`] so the command is stopped before Stand exits.
+pub fn stop_on_signals() -> Result<Arc<AtomicBool>> {
+    let stop = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+        signal_hook::flag::register(signal, Arc::clone(&stop))?;
+    }
+    Ok(stop)
+}
+
+/// Run `command` with the environment and restart it whenever a [`watched_files`] file changes
+///
+/// The running command is stopped (SIGTERM, then SIGKILL after a grace period) and
+/// started again with freshly resolved variables. A command that exits on its own
+/// is started again on the next change. Runs until `stop` is set, then stops the
+/// command and returns 0.
+///
+/// # Errors
+/// Returns an error if the first run cannot be started (e.g. unknown environment or
+/// declined confirmation). Later failures, such as a configuration that is invalid
+/// while being edited, are printed and the next change is awaited.
+pub fn watch_with_options(
+    project_path: &Path,
+    env_name: &str,
+    command: Vec<String>,
+    options: &WatchOptions,
+    stop: &AtomicBool,
+) -> Result<i32> {
+    let mut exec_options = ExecOptions {
+        skip_confirmation: options.skip_confirmation,
+        isolated: options.isolated,
+        use_shell: options.use_shell,
+        ..ExecOptions::default()
+    };
+    let mut first_run = true;
+
+    loop {
+        let files = watched_files(project_path, env_name);
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        let dirs: BTreeSet<_> = files
+            .iter()
+            .filter_map(|file| file.parent())
+            .filter(|dir| dir.is_dir())
+            .collect();
+        for dir in dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        let started = build_executor(project_path, env_name, command.clone(), &exec_options)
+            .and_then(|executor| executor.spawn());
+        let mut child = match started {
+            Ok(child) => Some(child),
+            Err(e) if first_run => return Err(e),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                eprintln!("Waiting for changes...");
+                None
+            }
+        };
+        // Confirmation is asked once, not on every restart
+        first_run = false;
+        exec_options.skip_confirmation = true;
+
+        let changed = wait_for_change(&receiver, &files, &mut child, options.debounce, stop)?;
+        if let Some(child) = child {
+            stop_child(child)?;
+        }
+        if !changed {
+            return Ok(0);
+        }
+        eprintln!("Configuration changed; restarting");
+    }
+}
+
+/// Wait until a watched file changes (true) or `stop` is set (false)
+///
+/// Reports when the command exits on its own and clears `child`.
+fn wait_for_change(
+    receiver: &mpsc::Receiver<notify::Result<Event>>,
+    files: &[PathBuf],
+    child: &mut Option<Child>,
+    debounce: Duration,
+    stop: &AtomicBool,
+) -> Result<bool> {
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        if let Some(status) = child.as_mut().map(Child::try_wait).transpose()?.flatten() {
+            eprintln!(
+                "Command exited with code {}; waiting for changes",
+                ExecOutcome::from_status(status).exit_code()
+            );
+            *child = None;
+        }
+
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(event)) if is_change_to(&event, files) => {
+                thread::sleep(debounce);
+                while receiver.try_recv().is_ok() {}
+                return Ok(true);
+            }
+            Ok(_) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(anyhow!("File watcher stopped unexpectedly"));
+            }
+        }
+    }
+}
+
+/// Returns true if `event` modifies, creates or removes one of `files`
+fn is_change_to(event: &Event, files: &[PathBuf]) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+        && event.paths.iter().any(|path| files.contains(path))
+}
+
+/// Ask `child` to exit (SIGTERM on Unix), killing it after [`STOP_GRACE_PERIOD`]
+fn stop_child(mut child: Child) -> Result<()> {
+    #[cfg(unix)]
+    {
+        // SAFETY: kill(2) has no memory-safety requirements
+        unsafe {
+            libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+        }
+        let deadline = Instant::now() + STOP_GRACE_PERIOD;
+        while Instant::now() < deadline {
+            if child.try_wait()?.is_some() {
+                return Ok(());
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+    // The child may have exited in the meantime
+    let _ = child.kill();
+    child.wait()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, ModifyKind};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_watched_files_follow_configuration() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\ninclude = [\"envs/prod.toml\"]\n\n\
+             [settings]\ndotenv_cascade = true\n\n\
+             [environments.dev]\ndescription = \"Dev\"\n",
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("envs")).unwrap();
+        fs::write(
+            dir.path().join("envs/prod.toml"),
+            "include = [\"envs/shared.toml\"]\n\n[environments.prod]\ndescription = \"Prod\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("envs/shared.toml"),
+            "[common]\nAPP = \"demo\"\n",
+        )
+        .unwrap();
+
+        let files = watched_files(dir.path(), "dev");
+
+        for name in [
+            ".stand.toml",
+            ".stand.local.toml",
+            "envs/prod.toml",
+            "envs/shared.toml",
+            ".env",
+            ".env.dev",
+            ".env.dev.local",
+        ] {
+            assert!(
+                files.contains(&dir.path().join(name)),
+                "{} not watched",
+                name
+            );
+        }
+        assert!(!files.contains(&dir.path().join(".env.prod")));
+    }
+
+    #[test]
+    fn test_is_change_to_ignores_other_files_and_reads() {
+        let files = vec![PathBuf::from("/app/.stand.toml")];
+        let event = |kind, path: &str| Event::new(kind).add_path(PathBuf::from(path));
+
+        assert!(is_change_to(
+            &event(EventKind::Modify(ModifyKind::Any), "/app/.stand.toml"),
+            &files
+        ));
+        assert!(!is_change_to(
+            &event(EventKind::Modify(ModifyKind::Any), "/app/runs.txt"),
+            &files
+        ));
+        assert!(!is_change_to(
+            &event(EventKind::Access(AccessKind::Any), "/app/.stand.toml"),
+            &files
+        ));
+    }
+}
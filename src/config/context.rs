@@ -0,0 +1,133 @@
+use crate::config::loader;
+use crate::config::types::Configuration;
+use crate::config::ConfigError;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// The project configuration, parsed once per command invocation
+///
+/// Holds both the configuration as loaded (overlay merged and interpolated, but
+/// without inheritance) and the same configuration with `[common]` and `extends`
+/// applied, so commands that need both views read `.stand.toml` a single time.
+#[derive(Debug, Clone)]
+pub struct ConfigContext {
+    raw: Configuration,
+    resolved: Configuration,
+    /// Unset process variables left as `${VAR}`, by environment (see [`Self::load_leaving_unset`])
+    unset: HashMap<String, BTreeSet<String>>,
+}
+
+impl ConfigContext {
+    /// Load the configuration of the project at `project_path`
+    ///
+    /// Reads the same files as [`loader::load_config_toml`].
+    pub fn load(project_path: &Path) -> Result<Self, ConfigError> {
+        Self::load_with(project_path, loader::load_config_toml_uninterpolated)
+    }
+
+    /// Build the context from the uninterpolated configuration returned by `load`
+    ///
+    /// `load` is called exactly once; both views are derived from its result.
+    pub fn load_with<F>(project_path: &Path, load: F) -> Result<Self, ConfigError>
+    where
+        F: FnOnce(&Path) -> Result<Configuration, ConfigError>,
+    {
+        let mut raw = load(project_path)?;
+        let mut resolved = raw.clone();
+        loader::apply_variable_inheritance(&mut resolved)?;
+        loader::apply_dotenv_cascade(&mut resolved, project_path)?;
+        loader::interpolate_configuration(&mut raw, project_path)?;
+        loader::interpolate_configuration(&mut resolved, project_path)?;
+        Ok(Self {
+            raw,
+            resolved,
+            unset: HashMap::new(),
+        })
+    }
+
+    /// Load like [`Self::load`], but keep `${VAR}` references to unset process
+    /// variables in the values instead of failing
+    ///
+    /// The skipped variables are available from [`Self::unset_variables`].
+    pub fn load_leaving_unset(project_path: &Path) -> Result<Self, ConfigError> {
+        let mut raw = loader::load_config_toml_uninterpolated(project_path)?;
+        let mut resolved = raw.clone();
+        loader::apply_variable_inheritance(&mut resolved)?;
+        loader::apply_dotenv_cascade(&mut resolved, project_path)?;
+        loader::interpolate_configuration_leaving_unset(&mut raw, project_path)?;
+        let unset = loader::interpolate_configuration_leaving_unset(&mut resolved, project_path)?;
+        Ok(Self {
+            raw,
+            resolved,
+            unset,
+        })
+    }
+
+    /// The configuration without inheritance, as written in the project files
+    pub fn raw(&self) -> &Configuration {
+        &self.raw
+    }
+
+    /// The configuration with `[common]` and `extends` applied
+    pub fn resolved(&self) -> &Configuration {
+        &self.resolved
+    }
+
+    /// Unset process variables referenced by `env_name`, including through `[common]`
+    /// and `extends`, sorted by name
+    ///
+    /// Always empty unless the context was loaded with [`Self::load_leaving_unset`].
+    pub fn unset_variables(&self, env_name: &str) -> Vec<&str> {
+        self.unset
+            .get(env_name)
+            .map(|names| names.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_context_views_share_one_load() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            r#"version = "2.0"
+
+[common]
+APP = "stand"
+
+[environments.base]
+description = "Base"
+PORT = "3000"
+
+[environments.dev]
+description = "Development"
+extends = "base"
+DEBUG = "true"
+"#,
+        )
+        .unwrap();
+
+        let loads = Cell::new(0);
+        let context = ConfigContext::load_with(dir.path(), |path| {
+            loads.set(loads.get() + 1);
+            loader::load_config_toml_uninterpolated(path)
+        })
+        .unwrap();
+
+        assert_eq!(loads.get(), 1);
+        let raw_dev = &context.raw().environments["dev"];
+        assert!(!raw_dev.variables.contains_key("PORT"));
+        assert!(!raw_dev.variables.contains_key("APP"));
+        let dev = &context.resolved().environments["dev"];
+        assert_eq!(dev.variables["PORT"], "3000");
+        assert_eq!(dev.variables["APP"], "stand");
+        assert_eq!(dev.variables["DEBUG"], "true");
+    }
+}
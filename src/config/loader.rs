@@ -1,16 +1,32 @@
 use crate::config::types::Configuration;
 use crate::config::validator::{
     validate_common_config, validate_environment_references, validate_no_circular_references,
-    validate_required_fields,
+    validate_required_fields, ConfigWarning,
 };
 use crate::config::ConfigError;
-use std::collections::HashSet;
+use crate::environment::loader::load_env_files_with_options;
+use crate::environment::parser::ParseOptions;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// Load configuration from TOML file (.stand.toml)
-pub fn load_config_toml(project_path: &Path) -> Result<Configuration, ConfigError> {
+/// Machine-specific overlay merged on top of `.stand.toml` when present
+pub const LOCAL_CONFIG_FILE: &str = ".stand.local.toml";
+
+/// YAML configuration file, used when a project has no `.stand.toml`
+pub const YAML_CONFIG_FILE: &str = ".stand.yaml";
+
+/// Pseudo-variable holding the name of the environment being loaded
+pub const STAND_ENV_VAR: &str = "STAND_ENV";
+
+/// Pseudo-variable holding the project directory (the one containing `.stand.toml`)
+pub const STAND_PROJECT_ROOT_VAR: &str = "STAND_PROJECT_ROOT";
+
+/// Parse the TOML file (.stand.toml) as written, without interpolation or inheritance
+///
+/// Files listed in `include` are merged in (see [`resolve_includes`]).
+pub fn parse_config_toml(project_path: &Path) -> Result<Configuration, ConfigError> {
     let config_path = project_path.join(".stand.toml");
 
     if !config_path.exists() {
@@ -20,38 +36,334 @@ pub fn load_config_toml(project_path: &Path) -> Result<Configuration, ConfigErro
     }
 
     let content = fs::read_to_string(&config_path)?;
-    let mut config: Configuration = toml::from_str(&content)?;
+    let config: Configuration =
+        toml::from_str(&content).map_err(|e| explain_incomplete_config(&content, e))?;
+    crate::log_verbose!("Loaded configuration from {}", config_path.display());
+
+    resolve_includes(project_path, &config_path, config)
+}
+
+/// Replace the serde error for an empty `.stand.toml`, or one without `version` or
+/// `environments`, with a message that says how to fix it
+///
+/// Any other error, including invalid TOML, is returned unchanged.
+fn explain_incomplete_config(content: &str, error: toml::de::Error) -> ConfigError {
+    let Ok(table) = toml::from_str::<toml::Table>(content) else {
+        return error.into();
+    };
+    let problem = if table.is_empty() {
+        ".stand.toml is empty"
+    } else if !table.contains_key("version") {
+        ".stand.toml has no 'version' (expected version = \"2.0\")"
+    } else if !table.contains_key("environments") {
+        ".stand.toml defines no environments (add an [environments.<name>] section)"
+    } else {
+        return error.into();
+    };
+    ConfigError::ValidationError {
+        message: format!(
+            "{}. Run 'stand init --force' to regenerate the template.",
+            problem
+        ),
+    }
+}
+
+/// Parse the YAML file (.stand.yaml) as written, without interpolation or inheritance
+///
+/// The document has the same shape as `.stand.toml`: `version`, `settings`,
+/// `common` and `environments` with variables inline.
+pub fn parse_config_yaml(project_path: &Path) -> Result<Configuration, ConfigError> {
+    let config_path = project_path.join(YAML_CONFIG_FILE);
+
+    if !config_path.exists() {
+        return Err(ConfigError::ValidationError {
+            message: "Stand configuration not found. Run 'stand init' to initialize.".to_string(),
+        });
+    }
+
+    let content = fs::read_to_string(&config_path)?;
+    let config: Configuration = serde_yaml::from_str(&content)?;
+    crate::log_verbose!("Loaded configuration from {}", config_path.display());
+
+    resolve_includes(project_path, &config_path, config)
+}
+
+/// Merge the files listed in `config.include` underneath `config`
+///
+/// Included files are TOML with the same schema as `.stand.toml`; like the local
+/// overlay they may omit `version`, and `description` for environments defined by
+/// the including file. Paths are relative to `project_path`. Included files are
+/// merged in order with [`Configuration::merge`] (later ones win) and the including
+/// file is merged on top, so its own definitions always win. Included files may
+/// include further files.
+///
+/// # Errors
+/// Returns [`ConfigError::FileNotFound`] for a missing file and
+/// [`ConfigError::IncludeCycle`] when a file includes itself, directly or not.
+fn resolve_includes(
+    project_path: &Path,
+    config_path: &Path,
+    config: Configuration,
+) -> Result<Configuration, ConfigError> {
+    let root = config_path.canonicalize()?;
+    let name = config_path
+        .strip_prefix(project_path)
+        .unwrap_or(config_path)
+        .display()
+        .to_string();
+    resolve_includes_from(project_path, config, &mut vec![(root, name)])
+}
+
+/// [`resolve_includes`] for a file whose canonical path and name end `chain`
+fn resolve_includes_from(
+    project_path: &Path,
+    mut config: Configuration,
+    chain: &mut Vec<(PathBuf, String)>,
+) -> Result<Configuration, ConfigError> {
+    let includes = std::mem::take(&mut config.include);
+    let mut merged: Option<Configuration> = None;
+
+    for include in includes {
+        let path = project_path.join(&include);
+        if !path.is_file() {
+            return Err(ConfigError::FileNotFound {
+                configured_path: include,
+                resolved_path: path.display().to_string(),
+            });
+        }
+        let canonical = path.canonicalize()?;
+        if chain.iter().any(|(seen, _)| *seen == canonical) {
+            let mut cycle: Vec<String> = chain.iter().map(|(_, name)| name.clone()).collect();
+            cycle.push(include);
+            return Err(ConfigError::IncludeCycle { cycle });
+        }
+
+        let included = parse_overlay(&path, &config).map_err(|e| ConfigError::ValidationError {
+            message: format!("Failed to load included file '{}': {}", include, e),
+        })?;
+        crate::log_verbose!("Included {}", path.display());
+        chain.push((canonical, include));
+        let included = resolve_includes_from(project_path, included, chain)?;
+        chain.pop();
+
+        match merged.as_mut() {
+            Some(merged) => merged.merge(included),
+            None => merged = Some(included),
+        }
+    }
+
+    Ok(match merged {
+        Some(mut merged) => {
+            merged.version = config.version.clone();
+            merged.merge(config);
+            merged
+        }
+        None => config,
+    })
+}
+
+/// `config_path` and every file it includes, directly or not
+///
+/// Only the `include` lists are read, so this works even while a file is invalid.
+/// Missing or unparsable files are listed but not followed.
+pub fn included_files(project_path: &Path, config_path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![config_path.to_path_buf()];
+    let mut next = 0;
+    while next < files.len() {
+        let includes = fs::read_to_string(&files[next])
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .and_then(|mut table| table.remove("include"))
+            .and_then(|include| include.as_array().cloned())
+            .unwrap_or_default();
+        for include in includes.iter().filter_map(|include| include.as_str()) {
+            let path = project_path.join(include);
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        next += 1;
+    }
+    files
+}
+
+/// The configuration file the loader reads in `project_path`
+///
+/// `.stand.toml`, or `.stand.yaml` when the project has no TOML file. The path is
+/// returned even if neither file exists.
+pub fn config_file(project_path: &Path) -> PathBuf {
+    let toml = project_path.join(".stand.toml");
+    let yaml = project_path.join(YAML_CONFIG_FILE);
+    if !toml.exists() && yaml.exists() {
+        yaml
+    } else {
+        toml
+    }
+}
+
+/// Parse `.stand.toml`, or `.stand.yaml` when the project has no TOML file
+fn parse_project_config(project_path: &Path) -> Result<Configuration, ConfigError> {
+    if config_file(project_path).ends_with(YAML_CONFIG_FILE) {
+        parse_config_yaml(project_path)
+    } else {
+        parse_config_toml(project_path)
+    }
+}
+
+/// Load configuration from TOML file (.stand.toml)
+///
+/// Falls back to `.stand.yaml` when there is no `.stand.toml`. A `.stand.local.toml`
+/// next to it is merged on top before interpolation (see [`load_config_toml_with_overlay`]).
+pub fn load_config_toml(project_path: &Path) -> Result<Configuration, ConfigError> {
+    load_config_toml_with_overlay(project_path, &project_path.join(LOCAL_CONFIG_FILE))
+}
+
+/// Load `.stand.toml` and merge the configuration at `overlay_path` on top of it
+///
+/// The overlay is merged with [`Configuration::merge`] before interpolation, so it
+/// wins over the base for variables, `[common]` values and settings. It may omit
+/// `version`, and environments that already exist in the base may omit
+/// `description`. A missing overlay file is ignored.
+pub fn load_config_toml_with_overlay(
+    project_path: &Path,
+    overlay_path: &Path,
+) -> Result<Configuration, ConfigError> {
+    let mut config = merge_overlay(parse_project_config(project_path)?, overlay_path)?;
+    interpolate_configuration(&mut config, project_path)?;
+    Ok(config)
+}
+
+/// Load `.stand.toml` (or `.stand.yaml`) with the local overlay merged, but not interpolated
+///
+/// `${VAR}` references are still as written. Interpolate the result with
+/// [`interpolate_configuration`], after applying inheritance if needed, so that
+/// `${STAND_ENV}` expands to the environment that ends up using the value.
+pub fn load_config_toml_uninterpolated(project_path: &Path) -> Result<Configuration, ConfigError> {
+    merge_overlay(
+        parse_project_config(project_path)?,
+        &project_path.join(LOCAL_CONFIG_FILE),
+    )
+}
+
+/// Merge the overlay at `overlay_path` (if any) into a parsed configuration
+fn merge_overlay(
+    mut config: Configuration,
+    overlay_path: &Path,
+) -> Result<Configuration, ConfigError> {
+    if overlay_path.exists() {
+        let overlay = parse_overlay(overlay_path, &config)?;
+        config.merge(overlay);
+        crate::log_verbose!("Merged overlay {}", overlay_path.display());
+    }
+
+    Ok(config)
+}
+
+/// Parse an overlay file, filling the fields it may leave out from `base`
+fn parse_overlay(overlay_path: &Path, base: &Configuration) -> Result<Configuration, ConfigError> {
+    let content = fs::read_to_string(overlay_path)?;
+    let mut table: toml::Table = toml::from_str(&content)?;
+
+    table
+        .entry("version")
+        .or_insert_with(|| toml::Value::String(base.version.clone()));
+    let environments = table
+        .entry("environments")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    if let Some(environments) = environments.as_table_mut() {
+        for (name, env) in environments.iter_mut() {
+            if let (Some(env), Some(base_env)) = (env.as_table_mut(), base.environments.get(name)) {
+                env.entry("description")
+                    .or_insert_with(|| toml::Value::String(base_env.description.clone()));
+            }
+        }
+    }
+
+    Ok(toml::Value::Table(table).try_into()?)
+}
+
+/// Load configuration from YAML file (.stand.yaml)
+///
+/// Same as [`load_config_toml`] for a YAML project, including the
+/// `.stand.local.toml` overlay, but never reads `.stand.toml`.
+pub fn load_config_yaml(project_path: &Path) -> Result<Configuration, ConfigError> {
+    let mut config = merge_overlay(
+        parse_config_yaml(project_path)?,
+        &project_path.join(LOCAL_CONFIG_FILE),
+    )?;
+    interpolate_configuration(&mut config, project_path)?;
+    Ok(config)
+}
+
+/// Load configuration from YAML file with variable inheritance
+pub fn load_config_yaml_with_inheritance(
+    project_path: &Path,
+) -> Result<Configuration, ConfigError> {
+    let mut config = merge_overlay(
+        parse_config_yaml(project_path)?,
+        &project_path.join(LOCAL_CONFIG_FILE),
+    )?;
 
-    // Apply environment variable interpolation
-    interpolate_configuration(&mut config)?;
+    apply_variable_inheritance(&mut config)?;
+    apply_dotenv_cascade(&mut config, project_path)?;
+    interpolate_configuration(&mut config, project_path)?;
 
     Ok(config)
 }
 
 /// Load configuration from TOML file with variable inheritance
+///
+/// Inheritance is applied before interpolation, so inherited values that use
+/// `${STAND_ENV}` expand to the inheriting environment's name.
 pub fn load_config_toml_with_inheritance(
     project_path: &Path,
 ) -> Result<Configuration, ConfigError> {
-    let mut config = load_config_toml(project_path)?;
+    let mut config = load_config_toml_uninterpolated(project_path)?;
 
     // Apply variable inheritance
     apply_variable_inheritance(&mut config)?;
+    apply_dotenv_cascade(&mut config, project_path)?;
+    interpolate_configuration(&mut config, project_path)?;
 
     Ok(config)
 }
 
 /// Load configuration from TOML file with variable inheritance and validation
 pub fn load_config_toml_with_validation(project_path: &Path) -> Result<Configuration, ConfigError> {
-    // Load and apply inheritance
-    let config = load_config_toml_with_inheritance(project_path)?;
+    load_config_toml_with_warnings(project_path).map(|(config, _)| config)
+}
+
+/// Load and validate configuration, also returning non-fatal warnings
+///
+/// Errors are the same as [`load_config_toml_with_validation`]; warnings (see
+/// [`collect_warnings`](crate::config::validator::collect_warnings) and
+/// [`check_unused_common_variables`](crate::config::validator::check_unused_common_variables))
+/// never fail the load.
+pub fn load_config_toml_with_warnings(
+    project_path: &Path,
+) -> Result<(Configuration, Vec<ConfigWarning>), ConfigError> {
+    let mut config = load_config_toml_uninterpolated(project_path)?;
+
+    // Check encrypted values against the raw config, before inheritance duplicates them
+    crate::config::validator::validate_encryption_consistency(&config)?;
+    let unused_common = crate::config::validator::check_unused_common_variables(&config);
+
+    apply_variable_inheritance(&mut config)?;
+    apply_dotenv_cascade(&mut config, project_path)?;
+    // References between variables are only visible before interpolation
+    crate::config::validator::validate_no_interpolation_cycles(&config)?;
+    interpolate_configuration(&mut config, project_path)?;
 
     // Apply all validation checks
     crate::config::validator::validate_required_fields(&config)?;
     crate::config::validator::validate_environment_references(&config)?;
     crate::config::validator::validate_no_circular_references(&config)?;
     crate::config::validator::validate_common_config(&config)?;
+    crate::config::validator::validate_aliases(&config)?;
 
-    Ok(config)
+    let mut warnings = crate::config::validator::collect_warnings(&config);
+    warnings.extend(unused_common);
+    Ok((config, warnings))
 }
 
 /// Load configuration from the given directory (legacy YAML format)
@@ -183,16 +495,91 @@ fn apply_default_values(config: &mut Configuration) {
 //     Ok(())
 // }
 
+/// Built-in values for `${STAND_ENV}` and `${STAND_PROJECT_ROOT}`
+///
+/// They take precedence over process variables of the same name. A variable of
+/// the same name defined in the configuration overrides them.
+#[derive(Debug, Clone)]
+pub(crate) struct PseudoVariables {
+    values: Vec<(&'static str, String)>,
+}
+
+impl PseudoVariables {
+    /// Pseudo-variables for `env_name`
+    ///
+    /// Without an environment (values of `[common]` as written), `${STAND_ENV}` is
+    /// kept as is so that each environment expands it once `[common]` is merged in.
+    pub(crate) fn new(project_path: &Path, env_name: Option<&str>) -> Self {
+        let env_value = match env_name {
+            Some(name) => name.to_string(),
+            None => format!("${{{}}}", STAND_ENV_VAR),
+        };
+        Self {
+            values: vec![
+                (STAND_ENV_VAR, env_value),
+                (STAND_PROJECT_ROOT_VAR, project_path.display().to_string()),
+            ],
+        }
+    }
+
+    /// Use the values of pseudo-variables that `lookup` finds defined instead of the built-in ones
+    ///
+    /// The defined values are interpolated against process variables only.
+    pub(crate) fn with_overrides<'a>(
+        mut self,
+        lookup: impl Fn(&str) -> Option<&'a String>,
+    ) -> Result<Self, ConfigError> {
+        for (name, value) in self.values.iter_mut() {
+            if let Some(defined) = lookup(name) {
+                *value = interpolate_string(defined)?;
+            }
+        }
+        Ok(self)
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(pseudo, _)| *pseudo == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
 /// Interpolate environment variables in a single string
 /// Uses single-pass expansion to avoid reprocessing inserted content
 /// Supports ${VAR} format only - nested expansions are not supported
-fn interpolate_string(input: &str) -> Result<String, ConfigError> {
+/// `$$` is an escaped `$`, so `$${VAR}` yields a literal `${VAR}`
+pub(crate) fn interpolate_string(input: &str) -> Result<String, ConfigError> {
+    interpolate_string_with(input, None)
+}
+
+/// Interpolate a string like [`interpolate_string`], resolving `pseudo` variables first
+pub(crate) fn interpolate_string_with(
+    input: &str,
+    pseudo: Option<&PseudoVariables>,
+) -> Result<String, ConfigError> {
+    expand_placeholders(input, pseudo, None)
+}
+
+/// Expand `${VAR}` placeholders in `input`
+///
+/// With `unset`, a placeholder for an unset process variable is kept as written and
+/// its name recorded there instead of failing.
+fn expand_placeholders(
+    input: &str,
+    pseudo: Option<&PseudoVariables>,
+    mut unset: Option<&mut BTreeSet<String>>,
+) -> Result<String, ConfigError> {
     let mut result = String::new();
     let mut chars = input.char_indices();
     let input_bytes = input.as_bytes();
 
     while let Some((i, ch)) = chars.next() {
-        if ch == '$' && i + 1 < input.len() && input_bytes[i + 1] == b'{' {
+        if ch == '$' && i + 1 < input.len() && input_bytes[i + 1] == b'$' {
+            // Skip the second '$'
+            chars.next();
+            result.push('$');
+        } else if ch == '$' && i + 1 < input.len() && input_bytes[i + 1] == b'{' {
             // Skip the '{' character
             chars.next();
 
@@ -226,9 +613,30 @@ fn interpolate_string(input: &str) -> Result<String, ConfigError> {
                 });
             }
 
-            let replacement = env::var(var_name).map_err(|_| ConfigError::InterpolationError {
-                variable: var_name.to_string(),
-            })?;
+            let replacement = match pseudo.and_then(|pseudo| pseudo.get(var_name)) {
+                Some(value) => {
+                    crate::log_debug!("Interpolated ${{{}}} (built-in)", var_name);
+                    value.to_string()
+                }
+                None => match (env::var(var_name), unset.as_deref_mut()) {
+                    (Ok(value), _) => {
+                        crate::log_debug!(
+                            "Interpolated ${{{}}} from the system environment",
+                            var_name
+                        );
+                        value
+                    }
+                    (Err(_), Some(unset)) => {
+                        unset.insert(var_name.to_string());
+                        format!("${{{}}}", var_name)
+                    }
+                    (Err(_), None) => {
+                        return Err(ConfigError::InterpolationError {
+                            variable: var_name.to_string(),
+                        })
+                    }
+                },
+            };
 
             result.push_str(&replacement);
         } else {
@@ -239,23 +647,111 @@ fn interpolate_string(input: &str) -> Result<String, ConfigError> {
     Ok(result)
 }
 
+/// Names of the `${VAR}` placeholders in `input`, in order of appearance
+///
+/// Follows the rules of [`interpolate_string`]: `$$` escapes are skipped, and
+/// unterminated or empty placeholders are errors.
+pub(crate) fn placeholder_names(input: &str) -> Result<Vec<&str>, ConfigError> {
+    let mut names = Vec::new();
+    let mut rest = input;
+    let mut offset = 0;
+
+    while let Some(pos) = rest.find('$') {
+        let after = &rest[pos + 1..];
+        if let Some(escaped) = after.strip_prefix('$') {
+            rest = escaped;
+            offset += pos + 2;
+        } else if let Some(placeholder) = after.strip_prefix('{') {
+            let end = placeholder
+                .find('}')
+                .ok_or_else(|| ConfigError::ValidationError {
+                    message: format!(
+                        "Unterminated variable placeholder starting at position {}: missing closing '}}' for '${{...'",
+                        offset + pos
+                    ),
+                })?;
+            if end == 0 {
+                return Err(ConfigError::ValidationError {
+                    message: format!(
+                        "Empty variable name in placeholder at position {}: '${{}}' is not valid",
+                        offset + pos
+                    ),
+                });
+            }
+            names.push(&placeholder[..end]);
+            rest = &placeholder[end + 1..];
+            offset += pos + end + 3;
+        } else {
+            rest = after;
+            offset += pos + 1;
+        }
+    }
+
+    Ok(names)
+}
+
 /// Apply environment variable interpolation to the entire configuration
-fn interpolate_configuration(config: &mut Configuration) -> Result<(), ConfigError> {
+///
+/// Values of each environment also see the [`PseudoVariables`] of that environment.
+pub(crate) fn interpolate_configuration(
+    config: &mut Configuration,
+    project_path: &Path,
+) -> Result<(), ConfigError> {
+    interpolate_configuration_impl(config, project_path, None)
+}
+
+/// Interpolate like [`interpolate_configuration`], keeping references to unset
+/// process variables as `${VAR}` instead of failing
+///
+/// Returns the unset variables referenced by each environment's description and
+/// variables. References in `[common]` are only counted for the environments it
+/// has been merged into.
+pub(crate) fn interpolate_configuration_leaving_unset(
+    config: &mut Configuration,
+    project_path: &Path,
+) -> Result<HashMap<String, BTreeSet<String>>, ConfigError> {
+    let mut unset = HashMap::new();
+    interpolate_configuration_impl(config, project_path, Some(&mut unset))?;
+    Ok(unset)
+}
+
+fn interpolate_configuration_impl(
+    config: &mut Configuration,
+    project_path: &Path,
+    mut unset: Option<&mut HashMap<String, BTreeSet<String>>>,
+) -> Result<(), ConfigError> {
+    let common = config.common.clone().unwrap_or_default();
+
     // Interpolate common variables
-    if let Some(ref mut common) = config.common {
-        for (_, value) in common.iter_mut() {
-            *value = interpolate_string(value)?;
+    if let Some(ref mut common_vars) = config.common {
+        crate::log_debug!("Interpolating [common]");
+        let pseudo =
+            PseudoVariables::new(project_path, None).with_overrides(|name| common.get(name))?;
+        // Unset references here are reported per environment, not for `[common]` itself
+        let mut common_unset = BTreeSet::new();
+        for (_, value) in common_vars.iter_mut() {
+            let leave_unset = unset.is_some().then_some(&mut common_unset);
+            *value = expand_placeholders(value, Some(&pseudo), leave_unset)?;
         }
     }
 
     // Interpolate environment variables and descriptions
-    for (_, env) in config.environments.iter_mut() {
+    for (name, env) in config.environments.iter_mut() {
+        crate::log_debug!("Interpolating environment '{}'", name);
+        let pseudo = PseudoVariables::new(project_path, Some(name))
+            .with_overrides(|name| common.get(name))?
+            .with_overrides(|name| env.variables.get(name))?;
+        let mut env_unset = unset
+            .as_deref_mut()
+            .map(|unset| unset.entry(name.clone()).or_default());
+
         // Interpolate description
-        env.description = interpolate_string(&env.description)?;
+        env.description =
+            expand_placeholders(&env.description, Some(&pseudo), env_unset.as_deref_mut())?;
 
         // Interpolate all environment variables
         for (_, value) in env.variables.iter_mut() {
-            *value = interpolate_string(value)?;
+            *value = expand_placeholders(value, Some(&pseudo), env_unset.as_deref_mut())?;
         }
     }
 
@@ -263,9 +759,13 @@ fn interpolate_configuration(config: &mut Configuration) -> Result<(), ConfigErr
 }
 
 /// Apply variable inheritance (common variables and extends relationships)
-fn apply_variable_inheritance(config: &mut Configuration) -> Result<(), ConfigError> {
+pub(crate) fn apply_variable_inheritance(config: &mut Configuration) -> Result<(), ConfigError> {
     // First, merge common variables into all environments
     if let Some(common) = &config.common {
+        crate::log_debug!(
+            "Inheritance: merged {} [common] variable(s) into every environment",
+            common.len()
+        );
         let common_vars = common.clone();
         for env in config.environments.values_mut() {
             // Common variables go first (lowest priority)
@@ -289,6 +789,59 @@ fn apply_variable_inheritance(config: &mut Configuration) -> Result<(), ConfigEr
     Ok(())
 }
 
+/// Dotenv files layered beneath `env_name` with `settings.dotenv_cascade`, least
+/// specific first
+pub fn dotenv_cascade_files(project_path: &Path, env_name: &str) -> [PathBuf; 3] {
+    [
+        project_path.join(".env"),
+        project_path.join(format!(".env.{}", env_name)),
+        project_path.join(format!(".env.{}.local", env_name)),
+    ]
+}
+
+/// Layer the [`dotenv_cascade_files`] beneath each environment's variables
+///
+/// Does nothing unless `settings.dotenv_cascade = true`. Missing files are skipped.
+/// More specific files override less specific ones, and the configured variables
+/// (including `[common]` and inherited ones) override them all. Dotenv values are
+/// used as written: `${VAR}` in them is neither expanded nor interpolated.
+pub(crate) fn apply_dotenv_cascade(
+    config: &mut Configuration,
+    project_path: &Path,
+) -> Result<(), ConfigError> {
+    if config.settings.dotenv_cascade != Some(true) {
+        return Ok(());
+    }
+
+    let options = ParseOptions {
+        expand_variables: false,
+        ..ParseOptions::default()
+    };
+    for (env_name, env) in config.environments.iter_mut() {
+        let files: Vec<_> = dotenv_cascade_files(project_path, env_name)
+            .into_iter()
+            .filter(|path| path.is_file())
+            .collect();
+        for path in &files {
+            crate::log_verbose!("Loading dotenv file {}", path.display());
+        }
+        let vars = load_env_files_with_options(&files, &options).map_err(|e| {
+            ConfigError::ValidationError {
+                message: e.to_string(),
+            }
+        })?;
+        // Escape `$` so interpolation keeps the values literal
+        let mut merged_vars: HashMap<_, _> = vars
+            .into_iter()
+            .map(|(key, value)| (key, value.replace('$', "$$")))
+            .collect();
+        merged_vars.extend(std::mem::take(&mut env.variables));
+        env.variables = merged_vars;
+    }
+
+    Ok(())
+}
+
 /// Apply inheritance for a single environment and its hierarchy
 fn apply_environment_inheritance(
     config: &mut Configuration,
@@ -316,35 +869,51 @@ fn apply_environment_inheritance(
         }
     })?;
 
-    if let Some(parent_name) = &env.extends {
-        // Process parent first
-        apply_environment_inheritance(config, parent_name, processed, inheritance_chain)?;
-
-        // Get parent environment data before mutable borrow
-        let parent_data = config
-            .environments
-            .get(parent_name)
-            .map(|p| {
-                (
-                    p.variables.clone(),
-                    p.color.clone(),
-                    p.requires_confirmation,
-                )
-            })
-            .unwrap_or_default();
+    if !env.parents().is_empty() {
+        crate::log_debug!(
+            "Inheritance: '{}' extends {}",
+            env_name,
+            env.parents()
+                .iter()
+                .map(|parent| format!("'{}'", parent))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        // Resolve every parent first, then merge them left to right (later parents override
+        // earlier ones) and the environment's own variables on top
+        let mut merged_vars = HashMap::new();
+        let mut color = None;
+        let mut requires_confirmation = None;
+        let mut isolated = None;
+        let mut default_command = None;
+        for parent_name in env.parents() {
+            apply_environment_inheritance(config, parent_name, processed, inheritance_chain)?;
+
+            if let Some(parent) = config.environments.get(parent_name) {
+                merged_vars.extend(parent.variables.clone());
+                color = parent.color.clone().or(color);
+                requires_confirmation = parent.requires_confirmation.or(requires_confirmation);
+                isolated = parent.isolated.or(isolated);
+                default_command = parent.default_command.clone().or(default_command);
+            }
+        }
 
-        // Merge: parent variables first, then current environment variables (child overrides parent)
         if let Some(current_env) = config.environments.get_mut(env_name) {
-            let mut merged_vars = parent_data.0;
             merged_vars.extend(current_env.variables.clone());
             current_env.variables = merged_vars;
 
             // Also inherit other properties if not set
             if current_env.color.is_none() {
-                current_env.color = parent_data.1;
+                current_env.color = color;
             }
             if current_env.requires_confirmation.is_none() {
-                current_env.requires_confirmation = parent_data.2;
+                current_env.requires_confirmation = requires_confirmation;
+            }
+            if current_env.isolated.is_none() {
+                current_env.isolated = isolated;
+            }
+            if current_env.default_command.is_none() {
+                current_env.default_command = default_command;
             }
         }
     }
@@ -1,4 +1,6 @@
+pub mod context;
 pub mod loader;
+pub mod selection;
 pub mod types;
 pub mod validator;
 
@@ -15,9 +17,12 @@ pub enum ConfigError {
     #[error("Invalid environment reference: {name}")]
     InvalidEnvironment { name: String },
 
-    #[error("Circular reference detected in environment hierarchy: {cycle:?}")]
+    #[error("Circular reference detected: {cycle:?}")]
     CircularReference { cycle: Vec<String> },
 
+    #[error("Include cycle detected: {}", cycle.join(" -> "))]
+    IncludeCycle { cycle: Vec<String> },
+
     #[error("Environment file not found: '{configured_path}' (resolved to '{resolved_path}')")]
     FileNotFound {
         configured_path: String,
@@ -51,3 +56,22 @@ pub enum ConfigError {
         source: toml::de::Error,
     },
 }
+
+impl ConfigError {
+    /// Stable identifier for this kind of error, used in `stand validate --json`
+    pub fn code(&self) -> &'static str {
+        match self {
+            ConfigError::ValidationError { .. } => "validation_error",
+            ConfigError::MissingField { .. } => "missing_field",
+            ConfigError::InvalidEnvironment { .. } => "invalid_environment",
+            ConfigError::CircularReference { .. } => "circular_reference",
+            ConfigError::IncludeCycle { .. } => "include_cycle",
+            ConfigError::FileNotFound { .. } => "file_not_found",
+            ConfigError::NotAFile { .. } => "not_a_file",
+            ConfigError::InterpolationError { .. } => "interpolation_error",
+            ConfigError::IoError { .. } => "io_error",
+            ConfigError::YamlError { .. } => "yaml_error",
+            ConfigError::TomlError { .. } => "toml_error",
+        }
+    }
+}
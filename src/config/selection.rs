@@ -0,0 +1,394 @@
+use crate::config::loader;
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// Select the environment a command should operate on
+///
+/// Resolution order (first match wins):
+/// 1. `explicit` - the environment given as a positional argument
+/// 2. `env_flag` - the global `--env` option
+/// 3. The process variable named by `settings.environment_from` (ignored when unset or empty)
+/// 4. The active environment recorded in the state file (`stand switch`)
+/// 5. `settings.default_environment`
+///
+/// Steps 4 and 5 are [`Configuration::resolve_default_environment`](crate::config::types::Configuration::resolve_default_environment).
+/// Names from steps 1 to 3 go through [`match_environment`], so aliases and fuzzy
+/// matching apply to them.
+pub fn select_environment(
+    project_path: &Path,
+    explicit: Option<&str>,
+    env_flag: Option<&str>,
+) -> Result<String> {
+    if let Some(name) = explicit.or(env_flag) {
+        return match_environment(project_path, name);
+    }
+
+    let config = loader::load_config_toml(project_path)?;
+
+    if let Some(var_name) = &config.settings.environment_from {
+        if let Ok(value) = std::env::var(var_name) {
+            if !value.is_empty() {
+                return config.match_environment_name(&value);
+            }
+        }
+    }
+
+    if let Some(name) = config.resolve_default_environment(project_path)? {
+        return Ok(name);
+    }
+
+    Err(anyhow!(
+        "No environment specified. Pass an environment name, use --env, \
+         or set 'default_environment' in [settings]."
+    ))
+}
+
+/// Resolve an environment name typed by the user against the project configuration
+///
+/// See [`Configuration::match_environment_name`](crate::config::types::Configuration::match_environment_name).
+/// The configuration is not interpolated, so an unset `${VAR}` does not stop an
+/// alias from resolving. If the configuration cannot be loaded, `name` is returned
+/// unchanged and the command reports the problem when it loads the configuration
+/// itself.
+pub fn match_environment(project_path: &Path, name: &str) -> Result<String> {
+    match loader::load_config_toml_uninterpolated(project_path) {
+        Ok(config) => config.match_environment_name(name),
+        Err(_) => Ok(name.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::persistence::save_state_to;
+    use crate::state::types::State;
+    use serial_test::serial;
+    use std::env;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"
+version = "2.0"
+
+[settings]
+environment_from = "STAND_TEST_APP_ENV"
+default_environment = "dev"
+
+[environments.dev]
+description = "Development environment"
+
+[environments.staging]
+description = "Staging environment"
+
+[environments.prod]
+description = "Production environment"
+"#;
+
+    fn switch_state(dir: &Path, env_name: &str) {
+        let mut state = State::new();
+        state.set_current_environment(env_name.to_string());
+        save_state_to(dir, &state).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_select_uses_environment_from_variable() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        env::set_var("STAND_TEST_APP_ENV", "prod");
+
+        let result = select_environment(dir.path(), None, None);
+
+        env::remove_var("STAND_TEST_APP_ENV");
+        assert_eq!(result.unwrap(), "prod");
+    }
+
+    #[test]
+    #[serial]
+    fn test_select_explicit_and_flag_override_environment_from() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        env::set_var("STAND_TEST_APP_ENV", "prod");
+
+        let explicit = select_environment(dir.path(), Some("dev"), Some("staging"));
+        let flag = select_environment(dir.path(), None, Some("staging"));
+
+        env::remove_var("STAND_TEST_APP_ENV");
+        assert_eq!(explicit.unwrap(), "dev");
+        assert_eq!(flag.unwrap(), "staging");
+    }
+
+    #[test]
+    #[serial]
+    fn test_select_environment_from_overrides_state() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        switch_state(dir.path(), "staging");
+        env::set_var("STAND_TEST_APP_ENV", "prod");
+
+        let result = select_environment(dir.path(), None, None);
+
+        env::remove_var("STAND_TEST_APP_ENV");
+        assert_eq!(result.unwrap(), "prod");
+    }
+
+    #[test]
+    #[serial]
+    fn test_select_falls_back_to_state_then_default() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        env::set_var("STAND_TEST_APP_ENV", "");
+
+        let default = select_environment(dir.path(), None, None).unwrap();
+        switch_state(dir.path(), "staging");
+        let from_state = select_environment(dir.path(), None, None).unwrap();
+
+        env::remove_var("STAND_TEST_APP_ENV");
+        assert_eq!(default, "dev");
+        assert_eq!(from_state, "staging");
+    }
+
+    #[test]
+    fn test_select_errors_when_nothing_configured() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\n",
+        )
+        .unwrap();
+
+        let result = select_environment(dir.path(), None, None);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("No environment specified"));
+    }
+
+    #[test]
+    fn test_resolve_default_environment_prefers_state() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        switch_state(dir.path(), "staging");
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        let resolved = config.resolve_default_environment(dir.path()).unwrap();
+
+        assert_eq!(resolved.as_deref(), Some("staging"));
+    }
+
+    #[test]
+    fn test_resolve_default_environment_without_state_uses_setting() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        let resolved = config.resolve_default_environment(dir.path()).unwrap();
+
+        assert_eq!(resolved.as_deref(), Some("dev"));
+    }
+
+    #[test]
+    fn test_resolve_default_environment_rejects_deleted_environment() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+        switch_state(dir.path(), "qa");
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        let result = config.resolve_default_environment(dir.path());
+
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("'qa' no longer exists"));
+        assert!(error_msg.contains("dev, prod, staging"));
+    }
+
+    #[test]
+    fn test_resolve_default_environment_rejects_unknown_default() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".stand.toml"),
+            "version = \"2.0\"\n\n[settings]\ndefault_environment = \"qa\"\n\n[environments.dev]\ndescription = \"Dev\"\n",
+        )
+        .unwrap();
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        let result = config.resolve_default_environment(dir.path());
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("default_environment 'qa' does not exist"));
+    }
+
+    const FUZZY_CONFIG: &str = r#"
+version = "2.0"
+
+[settings]
+fuzzy_env_match = true
+
+[environments.dev]
+description = "Development"
+
+[environments.prod]
+description = "Production"
+
+[environments.preview]
+description = "Preview"
+
+[environments.Staging]
+description = "Staging"
+"#;
+
+    #[test]
+    fn test_match_environment_exact_and_disabled() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), CONFIG).unwrap();
+
+        assert_eq!(match_environment(dir.path(), "prod").unwrap(), "prod");
+        // Without fuzzy_env_match the name is passed through untouched
+        assert_eq!(match_environment(dir.path(), "PROD").unwrap(), "PROD");
+        assert_eq!(match_environment(dir.path(), "pro").unwrap(), "pro");
+    }
+
+    #[test]
+    fn test_match_environment_case_insensitive() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), FUZZY_CONFIG).unwrap();
+
+        assert_eq!(match_environment(dir.path(), "dev").unwrap(), "dev");
+        assert_eq!(match_environment(dir.path(), "PROD").unwrap(), "prod");
+        assert_eq!(match_environment(dir.path(), "staging").unwrap(), "Staging");
+    }
+
+    #[test]
+    fn test_match_environment_unique_prefix() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), FUZZY_CONFIG).unwrap();
+
+        assert_eq!(match_environment(dir.path(), "pro").unwrap(), "prod");
+        assert_eq!(match_environment(dir.path(), "d").unwrap(), "dev");
+        assert_eq!(match_environment(dir.path(), "st").unwrap(), "Staging");
+        // No match is left for the command to report
+        assert_eq!(match_environment(dir.path(), "qa").unwrap(), "qa");
+    }
+
+    #[test]
+    fn test_match_environment_ambiguous_prefix() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), FUZZY_CONFIG).unwrap();
+
+        let error_msg = match_environment(dir.path(), "pr").unwrap_err().to_string();
+
+        assert!(error_msg.contains("'pr' is ambiguous"));
+        assert!(error_msg.contains("preview, prod"));
+    }
+
+    #[test]
+    fn test_select_applies_fuzzy_match_to_explicit_name() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), FUZZY_CONFIG).unwrap();
+
+        assert_eq!(
+            select_environment(dir.path(), Some("DEV"), None).unwrap(),
+            "dev"
+        );
+        assert_eq!(
+            select_environment(dir.path(), None, Some("prev")).unwrap(),
+            "preview"
+        );
+    }
+
+    const ALIAS_CONFIG: &str = r#"
+version = "2.0"
+
+[settings]
+aliases = { production = "prod", live = "production" }
+
+[environments.dev]
+description = "Development"
+
+[environments.prod]
+description = "Production"
+"#;
+
+    #[test]
+    fn test_match_environment_follows_aliases() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".stand.toml"), ALIAS_CONFIG).unwrap();
+
+        assert_eq!(match_environment(dir.path(), "production").unwrap(), "prod");
+        // Aliases may point to other aliases
+        assert_eq!(match_environment(dir.path(), "live").unwrap(), "prod");
+        assert_eq!(match_environment(dir.path(), "dev").unwrap(), "dev");
+        assert_eq!(
+            select_environment(dir.path(), None, Some("production")).unwrap(),
+            "prod"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_environment_from_follows_aliases() {
+        let dir = tempdir().unwrap();
+        let config = ALIAS_CONFIG.replace(
+            "[settings]\n",
+            "[settings]\nenvironment_from = \"STAND_TEST_ALIAS_APP_ENV\"\n",
+        );
+        fs::write(dir.path().join(".stand.toml"), config).unwrap();
+        env::set_var("STAND_TEST_ALIAS_APP_ENV", "live");
+
+        let result = select_environment(dir.path(), None, None);
+
+        env::remove_var("STAND_TEST_ALIAS_APP_ENV");
+        assert_eq!(result.unwrap(), "prod");
+    }
+
+    #[test]
+    fn test_alias_colliding_with_environment_is_an_error() {
+        let dir = tempdir().unwrap();
+        let config = ALIAS_CONFIG.replace("live = \"production\"", "dev = \"prod\"");
+        fs::write(dir.path().join(".stand.toml"), config).unwrap();
+
+        let error = loader::load_config_toml_with_validation(dir.path()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Configuration validation failed: Alias 'dev' collides with the environment of the same name"
+        );
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        assert!(config.match_environment_name("dev").is_err());
+    }
+
+    #[test]
+    fn test_alias_to_missing_environment_is_an_error() {
+        let dir = tempdir().unwrap();
+        let config = ALIAS_CONFIG.replace("\"prod\", live", "\"qa\", live");
+        fs::write(dir.path().join(".stand.toml"), config).unwrap();
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        assert_eq!(
+            config
+                .match_environment_name("live")
+                .unwrap_err()
+                .to_string(),
+            "Alias 'live' points to environment 'qa', which does not exist"
+        );
+        assert!(loader::load_config_toml_with_validation(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_alias_cycle_is_an_error() {
+        let dir = tempdir().unwrap();
+        let config = ALIAS_CONFIG.replace("\"prod\", live", "\"live\", live");
+        fs::write(dir.path().join(".stand.toml"), config).unwrap();
+
+        let config = loader::load_config_toml(dir.path()).unwrap();
+        assert_eq!(
+            config
+                .match_environment_name("live")
+                .unwrap_err()
+                .to_string(),
+            "Alias cycle detected: live -> production -> live"
+        );
+    }
+}
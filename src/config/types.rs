@@ -1,5 +1,20 @@
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+/// Variables referenced with `${VAR}`, as returned by [`Configuration::list_dependencies`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dependencies {
+    /// Resolved by Stand itself: `${STAND_ENV}` and `${STAND_PROJECT_ROOT}` (or a
+    /// configuration variable of the same name, which overrides them)
+    pub internal: BTreeSet<String>,
+    /// Read from the system environment; loading fails while one of them is unset
+    ///
+    /// This includes names that are also configuration variables, since
+    /// interpolation never reads other configuration values.
+    pub system: BTreeSet<String>,
+}
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Configuration {
@@ -8,16 +23,333 @@ pub struct Configuration {
     pub common: Option<HashMap<String, String>>,
     #[serde(default)]
     pub settings: Settings,
+    /// Encryption settings (`[encryption]` section), present when encryption is enabled
+    pub encryption: Option<EncryptionSettings>,
+    /// Files merged underneath this one, relative to the project root
+    ///
+    /// Resolved (and emptied) by the loader, see [`crate::config::loader::parse_config_toml`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+}
+
+impl Configuration {
+    /// Environment to use when a command is given none explicitly
+    ///
+    /// Returns the active environment recorded by `stand switch` if there is one,
+    /// otherwise `settings.default_environment`, or `None` when neither is set.
+    ///
+    /// # Errors
+    /// Returns an error if the chosen environment is not defined in this configuration
+    /// (e.g. it was removed after `stand switch`) or the state file cannot be read.
+    pub fn resolve_default_environment(&self, project_path: &Path) -> Result<Option<String>> {
+        if let Ok(project_root) = crate::utils::paths::find_project_root_from(project_path) {
+            let state = crate::state::persistence::load_state_from(&project_root)?;
+            if let Some(name) = state.get_current_environment() {
+                if !self.environments.contains_key(name) {
+                    return Err(anyhow!(
+                        "Active environment '{}' no longer exists. Available: {}. \
+                         Run 'stand switch <environment>' to pick another.",
+                        name,
+                        self.sorted_environment_names().join(", ")
+                    ));
+                }
+                return Ok(Some(name.to_string()));
+            }
+        }
+
+        match &self.settings.default_environment {
+            Some(name) if !self.environments.contains_key(name) => Err(anyhow!(
+                "settings.default_environment '{}' does not exist. Available: {}",
+                name,
+                self.sorted_environment_names().join(", ")
+            )),
+            Some(name) => Ok(Some(name.clone())),
+            None => Ok(None),
+        }
+    }
+
+    /// `${VAR}` references in the configuration, split by where their value comes from
+    ///
+    /// Call this on a configuration that has not been interpolated yet (see
+    /// [`crate::config::loader::load_config_toml_uninterpolated`]). With `env_name`,
+    /// only the description and variables of that environment count, including those
+    /// it gets from `[common]` and `extends`; otherwise every environment and
+    /// `[common]` is scanned.
+    ///
+    /// # Errors
+    /// Returns an error if `env_name` is not defined, the inheritance is invalid, or a
+    /// value contains a malformed placeholder.
+    pub fn list_dependencies(
+        &self,
+        env_name: Option<&str>,
+    ) -> Result<Dependencies, crate::config::ConfigError> {
+        use crate::config::loader;
+
+        let mut values: Vec<String> = Vec::new();
+        match env_name {
+            Some(name) => {
+                if !self.environments.contains_key(name) {
+                    return Err(crate::config::ConfigError::InvalidEnvironment {
+                        name: name.to_string(),
+                    });
+                }
+                let mut resolved = self.clone();
+                loader::apply_variable_inheritance(&mut resolved)?;
+                let env = &resolved.environments[name];
+                values.push(env.description.clone());
+                values.extend(env.variables.values().cloned());
+            }
+            None => {
+                for env in self.environments.values() {
+                    values.push(env.description.clone());
+                    values.extend(env.variables.values().cloned());
+                }
+                values.extend(
+                    self.common
+                        .iter()
+                        .flat_map(|common| common.values().cloned()),
+                );
+            }
+        }
+
+        let mut dependencies = Dependencies::default();
+        for value in &values {
+            for name in loader::placeholder_names(value)? {
+                if name == loader::STAND_ENV_VAR || name == loader::STAND_PROJECT_ROOT_VAR {
+                    dependencies.internal.insert(name.to_string());
+                } else {
+                    dependencies.system.insert(name.to_string());
+                }
+            }
+        }
+        Ok(dependencies)
+    }
+
+    /// Merge `overlay` into this configuration
+    ///
+    /// Precedence, for each part of the configuration:
+    /// - Environments with the same name are merged with [`Environment::merge`];
+    ///   other overlay environments are added
+    /// - `[common]` maps are merged and overlay values win
+    /// - Each `[settings]` option and `[encryption]` are taken from the overlay when set there
+    /// - `version` is kept from this configuration
+    pub fn merge(&mut self, overlay: Configuration) {
+        for (name, env) in overlay.environments {
+            match self.environments.get_mut(&name) {
+                Some(existing) => existing.merge(env),
+                None => {
+                    self.environments.insert(name, env);
+                }
+            }
+        }
+
+        if let Some(overlay_common) = overlay.common {
+            self.common
+                .get_or_insert_with(HashMap::new)
+                .extend(overlay_common);
+        }
+
+        self.settings.merge(overlay.settings);
+
+        if overlay.encryption.is_some() {
+            self.encryption = overlay.encryption;
+        }
+    }
+
+    /// Map an environment name given by the user to a defined environment
+    ///
+    /// An exact match always wins, followed by `settings.aliases`. With
+    /// `settings.fuzzy_env_match` enabled, a case-insensitive match is tried next,
+    /// then a unique case-insensitive prefix (`pr` -> `prod`). Otherwise, or when
+    /// nothing matches, `name` is returned unchanged so the caller reports the
+    /// missing environment as usual.
+    ///
+    /// # Errors
+    /// Returns an error listing the candidates when a fuzzy match is ambiguous, or
+    /// when `name` is an alias that [`Configuration::resolve_alias`] rejects.
+    pub fn match_environment_name(&self, name: &str) -> Result<String> {
+        if let Some(target) = self.resolve_alias(name)? {
+            return Ok(target);
+        }
+        if self.environments.contains_key(name) || self.settings.fuzzy_env_match != Some(true) {
+            return Ok(name.to_string());
+        }
+
+        let names = self.sorted_environment_names();
+        let lower = name.to_lowercase();
+        let case_insensitive: Vec<&str> = names
+            .iter()
+            .copied()
+            .filter(|candidate| candidate.to_lowercase() == lower)
+            .collect();
+        let candidates = if case_insensitive.is_empty() {
+            names
+                .iter()
+                .copied()
+                .filter(|candidate| candidate.to_lowercase().starts_with(&lower))
+                .collect()
+        } else {
+            case_insensitive
+        };
+
+        match candidates.as_slice() {
+            [] => Ok(name.to_string()),
+            [single] => Ok(single.to_string()),
+            _ => Err(anyhow!(
+                "Environment '{}' is ambiguous. Matches: {}",
+                name,
+                candidates.join(", ")
+            )),
+        }
+    }
+
+    /// Follow `settings.aliases` from `name` to a defined environment
+    ///
+    /// Aliases may point to other aliases. Returns `None` if `name` is not an alias.
+    ///
+    /// # Errors
+    /// Returns an error if the alias has the same name as an environment, if the
+    /// chain ends at an environment that does not exist, or if it forms a cycle.
+    pub fn resolve_alias(&self, name: &str) -> Result<Option<String>> {
+        let Some(aliases) = &self.settings.aliases else {
+            return Ok(None);
+        };
+        if !aliases.contains_key(name) {
+            return Ok(None);
+        }
+        if self.environments.contains_key(name) {
+            return Err(anyhow!(
+                "Alias '{}' collides with the environment of the same name",
+                name
+            ));
+        }
+
+        let mut chain = vec![name];
+        let mut current = name;
+        while let Some(target) = aliases.get(current) {
+            if chain.contains(&target.as_str()) {
+                chain.push(target);
+                return Err(anyhow!("Alias cycle detected: {}", chain.join(" -> ")));
+            }
+            if self.environments.contains_key(target) {
+                return Ok(Some(target.clone()));
+            }
+            chain.push(target);
+            current = target;
+        }
+        Err(anyhow!(
+            "Alias '{}' points to environment '{}', which does not exist",
+            name,
+            current
+        ))
+    }
+
+    pub(crate) fn sorted_environment_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.environments.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+}
+
+/// Keys of an `[environments.<name>]` table that are environment properties rather
+/// than variables. Keep in sync with the named fields of [`Environment`].
+pub const ENVIRONMENT_META_KEYS: &[&str] = &[
+    "description",
+    "extends",
+    "color",
+    "requires_confirmation",
+    "isolated",
+    "default_command",
+    "hidden",
+];
+
+/// Parent environment(s) named by `extends`
+///
+/// Either a single name (`extends = "base"`) or a list (`extends = ["logging", "db"]`).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Extends {
+    /// Parent names in the order they are merged (later parents override earlier ones)
+    pub fn parents(&self) -> &[String] {
+        match self {
+            Extends::One(parent) => std::slice::from_ref(parent),
+            Extends::Many(parents) => parents,
+        }
+    }
+}
+
+impl From<&str> for Extends {
+    fn from(parent: &str) -> Self {
+        Extends::One(parent.to_string())
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Environment {
     pub description: String,
-    pub extends: Option<String>,
+    pub extends: Option<Extends>,
     #[serde(flatten)]
     pub variables: HashMap<String, String>,
     pub color: Option<String>,
     pub requires_confirmation: Option<bool>,
+    /// If true, `exec` starts commands with a cleared environment (see `--isolated`)
+    pub isolated: Option<bool>,
+    /// Command run by `stand exec <env>` when no command is given (split like a shell would)
+    pub default_command: Option<String>,
+    /// If true, `stand list` leaves the environment out unless `--all` is given (not inherited)
+    pub hidden: Option<bool>,
+}
+
+impl Environment {
+    /// Names of the environments this one extends, empty when it has no parent
+    pub fn parents(&self) -> &[String] {
+        self.extends
+            .as_ref()
+            .map(Extends::parents)
+            .unwrap_or_default()
+    }
+
+    /// Number of variables in this environment
+    ///
+    /// Counts `variables` as loaded: on a configuration with inheritance applied
+    /// (see `loader::load_config_toml_with_inheritance`) this includes variables from
+    /// `[common]` and `extends` parents, otherwise only the environment's own section.
+    pub fn variable_count(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Merge `overlay` into this environment
+    ///
+    /// The overlay's description replaces this one, its variables are added or
+    /// replace existing ones, and each optional property is taken from the overlay
+    /// when set there.
+    pub fn merge(&mut self, overlay: Environment) {
+        self.description = overlay.description;
+        self.variables.extend(overlay.variables);
+        if overlay.extends.is_some() {
+            self.extends = overlay.extends;
+        }
+        if overlay.color.is_some() {
+            self.color = overlay.color;
+        }
+        if overlay.requires_confirmation.is_some() {
+            self.requires_confirmation = overlay.requires_confirmation;
+        }
+        if overlay.isolated.is_some() {
+            self.isolated = overlay.isolated;
+        }
+        if overlay.default_command.is_some() {
+            self.default_command = overlay.default_command;
+        }
+        if overlay.hidden.is_some() {
+            self.hidden = overlay.hidden;
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -26,6 +358,89 @@ pub struct Settings {
     pub show_env_in_prompt: Option<bool>,
     /// If true, automatically exit the Stand subshell when navigating outside the project directory
     pub auto_exit_on_dir_change: Option<bool>,
+    /// If true, suppress informational banners (same as passing `--quiet`)
+    pub quiet: Option<bool>,
+    /// Environment used when none is given on the command line or in the state file
+    pub default_environment: Option<String>,
+    /// Name of a process variable (e.g. `APP_ENV`) whose value selects the environment
+    pub environment_from: Option<String>,
+    /// Number of leading characters left visible when values are masked (`inspect --mask`)
+    pub mask_visible_chars: Option<usize>,
+    /// If true, environment names on the command line also match case-insensitively
+    /// and by unique prefix (see [`Configuration::match_environment_name`])
+    pub fuzzy_env_match: Option<bool>,
+    /// Shell started by `stand shell` instead of `$SHELL` (`--shell` still wins)
+    pub shell: Option<String>,
+    /// If false, `stand env` and `stand inspect --values` print `encrypted:` values
+    /// as stored instead of decrypting them (`exec` and `shell` still decrypt)
+    pub auto_decrypt: Option<bool>,
+    /// If true, `.env`, `.env.<environment>` and `.env.<environment>.local` in the
+    /// project directory are layered beneath each environment's variables
+    pub dotenv_cascade: Option<bool>,
+    /// Alternative names for environments, alias -> environment
+    /// (see [`Configuration::resolve_alias`])
+    pub aliases: Option<BTreeMap<String, String>>,
+    /// Directory `stand shell` starts in (default: [`ShellCwd::Current`])
+    pub shell_cwd: Option<ShellCwd>,
+}
+
+impl Settings {
+    /// Take every option that is set in `overlay`, keeping the others
+    pub fn merge(&mut self, overlay: Settings) {
+        let Settings {
+            nested_shell_behavior,
+            show_env_in_prompt,
+            auto_exit_on_dir_change,
+            quiet,
+            default_environment,
+            environment_from,
+            mask_visible_chars,
+            fuzzy_env_match,
+            shell,
+            auto_decrypt,
+            dotenv_cascade,
+            aliases,
+            shell_cwd,
+        } = overlay;
+
+        self.nested_shell_behavior = nested_shell_behavior.or(self.nested_shell_behavior.take());
+        self.show_env_in_prompt = show_env_in_prompt.or(self.show_env_in_prompt);
+        self.auto_exit_on_dir_change = auto_exit_on_dir_change.or(self.auto_exit_on_dir_change);
+        self.quiet = quiet.or(self.quiet);
+        self.default_environment = default_environment.or(self.default_environment.take());
+        self.environment_from = environment_from.or(self.environment_from.take());
+        self.mask_visible_chars = mask_visible_chars.or(self.mask_visible_chars);
+        self.fuzzy_env_match = fuzzy_env_match.or(self.fuzzy_env_match);
+        self.shell = shell.or(self.shell.take());
+        self.auto_decrypt = auto_decrypt.or(self.auto_decrypt);
+        self.dotenv_cascade = dotenv_cascade.or(self.dotenv_cascade);
+        self.aliases = aliases.or(self.aliases.take());
+        self.shell_cwd = shell_cwd.or(self.shell_cwd);
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EncryptionSettings {
+    /// age public key used to encrypt new values
+    pub public_key: String,
+    /// Public keys of teammates; new values are also encrypted to each of them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<String>,
+}
+
+impl EncryptionSettings {
+    /// `public_key` followed by every key in `recipients`, parsed
+    ///
+    /// # Errors
+    /// Returns an error if any of the keys is not a valid age public key.
+    pub fn all_recipients(
+        &self,
+    ) -> Result<Vec<age::x25519::Recipient>, crate::crypto::CryptoError> {
+        std::iter::once(&self.public_key)
+            .chain(&self.recipients)
+            .map(|key| crate::crypto::keys::parse_public_key(key))
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -35,3 +450,13 @@ pub enum NestedBehavior {
     Allow,
     Warn,
 }
+
+/// Working directory of the shell started by `stand shell`
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellCwd {
+    /// The directory containing `.stand.toml`
+    ProjectRoot,
+    /// The directory `stand shell` was run from
+    Current,
+}
@@ -1,67 +1,164 @@
 use crate::config::types::Configuration;
 use crate::config::ConfigError;
+use crate::crypto::is_encrypted;
+use crate::environment::resolver::EnvironmentResolver;
+use indexmap::IndexMap;
+use serde::Serialize;
 use std::collections::HashSet;
 
+/// A [`ConfigError`] together with the environment and field it was found in
+type LocatedError = (ConfigError, Option<String>, Option<String>);
+
+/// Environment names in sorted order, so errors are reported deterministically
+fn sorted_environment_names(config: &Configuration) -> Vec<&String> {
+    let mut names: Vec<_> = config.environments.keys().collect();
+    names.sort();
+    names
+}
+
+fn first_error(errors: Vec<LocatedError>) -> Result<(), ConfigError> {
+    match errors.into_iter().next() {
+        Some((error, _, _)) => Err(error),
+        None => Ok(()),
+    }
+}
+
 /// Validate that all required fields are present
 pub fn validate_required_fields(config: &Configuration) -> Result<(), ConfigError> {
+    first_error(required_field_errors(config))
+}
+
+fn required_field_errors(config: &Configuration) -> Vec<LocatedError> {
+    let mut errors = Vec::new();
+
     if config.version.is_empty() {
-        return Err(ConfigError::MissingField {
-            field: "version".to_string(),
-        });
+        errors.push((
+            ConfigError::MissingField {
+                field: "version".to_string(),
+            },
+            None,
+            Some("version".to_string()),
+        ));
     }
 
     // Check that environments map is not empty
     if config.environments.is_empty() {
-        return Err(ConfigError::MissingField {
-            field: "environments".to_string(),
-        });
+        errors.push((
+            ConfigError::MissingField {
+                field: "environments".to_string(),
+            },
+            None,
+            Some("environments".to_string()),
+        ));
     }
 
     // Check that each environment has a non-empty description
-    for (env_name, env) in &config.environments {
-        if env.description.is_empty() {
-            return Err(ConfigError::ValidationError {
-                message: format!(
-                    "Environment '{}' must have a non-empty description",
-                    env_name
-                ),
-            });
+    for env_name in sorted_environment_names(config) {
+        if config.environments[env_name].description.is_empty() {
+            errors.push((
+                ConfigError::ValidationError {
+                    message: format!(
+                        "Environment '{}' must have a non-empty description",
+                        env_name
+                    ),
+                },
+                Some(env_name.clone()),
+                Some("description".to_string()),
+            ));
         }
     }
 
-    Ok(())
+    errors
 }
 
 /// Validate that all environment references are valid
 pub fn validate_environment_references(config: &Configuration) -> Result<(), ConfigError> {
-    let env_names: HashSet<&String> = config.environments.keys().collect();
+    first_error(environment_reference_errors(config))
+}
 
+fn environment_reference_errors(config: &Configuration) -> Vec<LocatedError> {
     // Check that all 'extends' references are valid
-    for env in config.environments.values() {
-        if let Some(extends) = &env.extends {
-            if !env_names.contains(&extends) {
-                return Err(ConfigError::InvalidEnvironment {
-                    name: extends.clone(),
-                });
-            }
-        }
-    }
-
-    Ok(())
+    sorted_environment_names(config)
+        .into_iter()
+        .flat_map(|env_name| {
+            config.environments[env_name]
+                .parents()
+                .iter()
+                .filter(|parent| !config.environments.contains_key(*parent))
+                .map(move |parent| {
+                    (
+                        ConfigError::InvalidEnvironment {
+                            name: parent.clone(),
+                        },
+                        Some(env_name.clone()),
+                        Some("extends".to_string()),
+                    )
+                })
+        })
+        .collect()
 }
 
 /// Validate that there are no circular references in environment hierarchy
 pub fn validate_no_circular_references(config: &Configuration) -> Result<(), ConfigError> {
-    for env_name in config.environments.keys() {
+    first_error(circular_reference_errors(config))
+}
+
+/// One error per distinct cycle, reported at the first environment (by name) that reaches it
+fn circular_reference_errors(config: &Configuration) -> Vec<LocatedError> {
+    let mut errors = Vec::new();
+    let mut seen_cycles: Vec<Vec<String>> = Vec::new();
+
+    for env_name in sorted_environment_names(config) {
         let mut visited = HashSet::new();
         let mut path = Vec::new();
 
-        if detect_circular_reference(config, env_name, &mut visited, &mut path)? {
-            return Err(ConfigError::CircularReference { cycle: path });
+        if detect_circular_reference(config, env_name, &mut visited, &mut path) {
+            // The cycle itself starts where the repeated environment first appears
+            let repeated = path.last().cloned().unwrap_or_default();
+            let start = path.iter().position(|name| *name == repeated).unwrap_or(0);
+            let mut members = path[start..path.len() - 1].to_vec();
+            members.sort();
+            if seen_cycles.contains(&members) {
+                continue;
+            }
+            seen_cycles.push(members);
+            errors.push((
+                ConfigError::CircularReference { cycle: path },
+                Some(env_name.clone()),
+                Some("extends".to_string()),
+            ));
         }
     }
 
-    Ok(())
+    errors
+}
+
+/// Validate that no `${VAR}` references between variables form a cycle
+///
+/// Must run after inheritance and before interpolation, so each environment's
+/// variables include `[common]` and its parents and still contain the references.
+pub fn validate_no_interpolation_cycles(config: &Configuration) -> Result<(), ConfigError> {
+    first_error(interpolation_cycle_errors(config))
+}
+
+fn interpolation_cycle_errors(config: &Configuration) -> Vec<LocatedError> {
+    let mut errors = Vec::new();
+    for env_name in sorted_environment_names(config) {
+        let mut merged = config.common.clone().unwrap_or_default();
+        merged.extend(config.environments[env_name].variables.clone());
+        // Sorted, so the same cycle is reported on every run
+        let mut variables: IndexMap<String, String> = merged.into_iter().collect();
+        variables.sort_keys();
+        if let Some(cycle) = EnvironmentResolver::find_reference_cycle(&variables) {
+            let field = cycle.first().cloned();
+            errors.push((
+                ConfigError::CircularReference { cycle },
+                Some(env_name.clone()),
+                field,
+            ));
+        }
+    }
+    errors
 }
 
 /// Detect circular references using DFS
@@ -70,47 +167,278 @@ fn detect_circular_reference(
     current: &str,
     visited: &mut HashSet<String>,
     path: &mut Vec<String>,
-) -> Result<bool, ConfigError> {
+) -> bool {
     if path.contains(&current.to_string()) {
         path.push(current.to_string());
-        return Ok(true);
+        return true;
     }
 
     if visited.contains(current) {
-        return Ok(false);
+        return false;
     }
 
     visited.insert(current.to_string());
     path.push(current.to_string());
 
     if let Some(env) = config.environments.get(current) {
-        if let Some(extends) = &env.extends {
-            if detect_circular_reference(config, extends, visited, path)? {
-                return Ok(true);
+        for parent in env.parents() {
+            if detect_circular_reference(config, parent, visited, path) {
+                return true;
             }
         }
     }
 
     path.pop();
-    Ok(false)
+    false
 }
 
 /// Validate common configuration if present
 pub fn validate_common_config(config: &Configuration) -> Result<(), ConfigError> {
+    first_error(common_config_errors(config))
+}
+
+fn common_config_errors(config: &Configuration) -> Vec<LocatedError> {
+    let Some(common) = &config.common else {
+        return Vec::new();
+    };
+    let mut keys: Vec<_> = common.keys().collect();
+    keys.sort();
+
+    let mut errors = Vec::new();
+    for key in keys {
+        let field = Some(format!("common.{}", key));
+        if key.is_empty() {
+            errors.push((
+                ConfigError::ValidationError {
+                    message: "Common variable keys cannot be empty".to_string(),
+                },
+                None,
+                field,
+            ));
+        } else if common[key].is_empty() {
+            errors.push((
+                ConfigError::ValidationError {
+                    message: format!("Common variable '{}' cannot have empty value", key),
+                },
+                None,
+                field,
+            ));
+        }
+    }
+    errors
+}
+
+/// Validate `settings.aliases`: no alias shadows an environment, and every alias
+/// leads to an existing environment without a cycle
+pub fn validate_aliases(config: &Configuration) -> Result<(), ConfigError> {
+    first_error(alias_errors(config))
+}
+
+fn alias_errors(config: &Configuration) -> Vec<LocatedError> {
+    let Some(aliases) = &config.settings.aliases else {
+        return Vec::new();
+    };
+    aliases
+        .keys()
+        .filter_map(|alias| {
+            let error = config.resolve_alias(alias).err()?;
+            Some((
+                ConfigError::ValidationError {
+                    message: error.to_string(),
+                },
+                None,
+                Some(format!("settings.aliases.{}", alias)),
+            ))
+        })
+        .collect()
+}
+
+/// Validate that encrypted values only appear when an `[encryption]` section exists
+///
+/// Without the section, `encrypted:` values can never be decrypted, which usually
+/// means the section was lost (e.g. in a bad merge). Must run on the raw
+/// configuration, before inheritance copies values between environments.
+pub fn validate_encryption_consistency(config: &Configuration) -> Result<(), ConfigError> {
+    if config.encryption.is_some() {
+        return Ok(());
+    }
+
+    let mut encrypted_vars: Vec<String> = Vec::new();
+
     if let Some(common) = &config.common {
         for (key, value) in common {
-            if key.is_empty() {
-                return Err(ConfigError::ValidationError {
-                    message: "Common variable keys cannot be empty".to_string(),
-                });
+            if is_encrypted(value) {
+                encrypted_vars.push(format!("common.{}", key));
             }
-            if value.is_empty() {
-                return Err(ConfigError::ValidationError {
-                    message: format!("Common variable '{}' cannot have empty value", key),
-                });
+        }
+    }
+
+    for (env_name, env) in &config.environments {
+        for (key, value) in &env.variables {
+            if is_encrypted(value) {
+                encrypted_vars.push(format!("{}.{}", env_name, key));
             }
         }
     }
 
-    Ok(())
+    if encrypted_vars.is_empty() {
+        return Ok(());
+    }
+
+    encrypted_vars.sort();
+    Err(ConfigError::ValidationError {
+        message: format!(
+            "Encrypted values found but no [encryption] section is configured: {}",
+            encrypted_vars.join(", ")
+        ),
+    })
+}
+
+/// How serious a [`ValidationIssue`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A validation problem and where it was found, for machine-readable output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// [`ConfigError::code`] for errors, [`ConfigWarning::code`] for warnings
+    pub code: &'static str,
+    pub message: String,
+    /// Environment the problem is in, if it is specific to one
+    pub environment: Option<String>,
+    /// Offending field, e.g. `extends` or `common.API_KEY`
+    pub field: Option<String>,
+}
+
+impl ValidationIssue {
+    /// An error-severity issue for `error`
+    pub fn from_error(
+        error: &ConfigError,
+        environment: Option<String>,
+        field: Option<String>,
+    ) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: error.code(),
+            message: error.to_string(),
+            environment,
+            field,
+        }
+    }
+
+    /// A warning-severity issue for `warning`
+    pub fn from_warning(warning: ConfigWarning) -> Self {
+        Self {
+            severity: Severity::Warning,
+            code: warning.code,
+            message: warning.message,
+            environment: warning.environment,
+            field: None,
+        }
+    }
+}
+
+/// Run every structural check on a raw (uninherited) configuration and collect all errors
+///
+/// Unlike the `validate_*` functions, this does not stop at the first error.
+pub fn collect_errors(config: &Configuration) -> Vec<ValidationIssue> {
+    let mut errors = Vec::new();
+    errors.extend(required_field_errors(config));
+    errors.extend(environment_reference_errors(config));
+    errors.extend(circular_reference_errors(config));
+    errors.extend(common_config_errors(config));
+    errors.extend(alias_errors(config));
+    if let Err(error) = validate_encryption_consistency(config) {
+        errors.push((error, None, None));
+    }
+
+    errors
+        .into_iter()
+        .map(|(error, environment, field)| ValidationIssue::from_error(&error, environment, field))
+        .collect()
+}
+
+/// A non-fatal configuration problem, reported alongside a valid configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    /// Stable identifier for this kind of warning, used in `stand validate --json`
+    pub code: &'static str,
+    pub message: String,
+    /// Environment the warning is about, if any
+    pub environment: Option<String>,
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Collect every non-fatal warning for a resolved configuration
+///
+/// [`check_unused_common_variables`] needs the configuration before inheritance and
+/// is run separately.
+pub fn collect_warnings(config: &Configuration) -> Vec<ConfigWarning> {
+    let mut warnings = Vec::new();
+    warnings.extend(check_default_environment_confirmation(config));
+    warnings
+}
+
+/// Warn about `[common]` variables that every environment overrides
+///
+/// Expects the configuration before inheritance: a common variable is unused when
+/// every environment sets the same key in its own section, whatever the values
+/// interpolate to. Informational only.
+pub fn check_unused_common_variables(config: &Configuration) -> Vec<ConfigWarning> {
+    let Some(common) = &config.common else {
+        return Vec::new();
+    };
+    if config.environments.is_empty() {
+        return Vec::new();
+    }
+
+    let mut keys: Vec<_> = common.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .filter(|key| {
+            config
+                .environments
+                .values()
+                .all(|env| env.variables.contains_key(*key))
+        })
+        .map(|key| ConfigWarning {
+            code: "unused_common_variable",
+            message: format!(
+                "common variable '{}' is overridden in every environment; consider removing it from [common]",
+                key
+            ),
+            environment: None,
+        })
+        .collect()
+}
+
+/// Warn when `settings.default_environment` requires confirmation
+///
+/// Every command that falls back to the default environment would prompt, which is
+/// rarely intended.
+pub fn check_default_environment_confirmation(config: &Configuration) -> Option<ConfigWarning> {
+    let name = config.settings.default_environment.as_deref()?;
+    let env = config.environments.get(name)?;
+    if !env.requires_confirmation.unwrap_or(false) {
+        return None;
+    }
+
+    Some(ConfigWarning {
+        code: "default_environment_requires_confirmation",
+        message: format!(
+            "default_environment '{}' requires confirmation; every command that falls back to it will prompt",
+            name
+        ),
+        environment: Some(name.to_string()),
+    })
 }
@@ -21,7 +21,24 @@ pub fn is_encrypted(value: &str) -> bool {
 /// # Errors
 /// Returns `CryptoError::EncryptionFailed` if encryption fails.
 pub fn encrypt_value(plaintext: &str, recipient: &Recipient) -> Result<String, CryptoError> {
-    let encryptor = age::Encryptor::with_recipients(vec![Box::new(recipient.clone())])
+    encrypt_value_for_recipients(plaintext, std::slice::from_ref(recipient))
+}
+
+/// Encrypts a plaintext value so that the private key of any of `recipients` decrypts it.
+///
+/// Returns the encrypted value with the "encrypted:" prefix.
+///
+/// # Errors
+/// Returns `CryptoError::EncryptionFailed` if `recipients` is empty or encryption fails.
+pub fn encrypt_value_for_recipients(
+    plaintext: &str,
+    recipients: &[Recipient],
+) -> Result<String, CryptoError> {
+    let recipients = recipients
+        .iter()
+        .map(|recipient| Box::new(recipient.clone()) as Box<dyn age::Recipient + Send>)
+        .collect();
+    let encryptor = age::Encryptor::with_recipients(recipients)
         .ok_or_else(|| CryptoError::EncryptionFailed("Failed to create encryptor".to_string()))?;
 
     let mut encrypted = vec![];
@@ -56,8 +73,20 @@ pub fn decrypt_value(encrypted_value: &str, identity: &Identity) -> Result<Strin
         ));
     }
 
+    // Only report where decoding failed: the offending symbol may belong to a
+    // plaintext value that was mistakenly given the prefix
     let encrypted = BASE64.decode(encoded).map_err(|e| {
-        CryptoError::DecryptionFailed(format!("Invalid base64 encoding in encrypted value: {}", e))
+        let detail = match e {
+            base64::DecodeError::InvalidByte(offset, _)
+            | base64::DecodeError::InvalidLastSymbol(offset, _) => {
+                format!("invalid symbol at offset {}", offset)
+            }
+            other => other.to_string(),
+        };
+        CryptoError::DecryptionFailed(format!(
+            "Invalid base64 encoding in encrypted value: {}",
+            detail
+        ))
     })?;
 
     let decryptor = match age::Decryptor::new(&encrypted[..])
@@ -87,8 +116,27 @@ pub fn decrypt_value(encrypted_value: &str, identity: &Identity) -> Result<Strin
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::assert_error_hides_secret;
     use crate::crypto::keys::generate_key_pair;
 
+    #[test]
+    fn test_decrypt_errors_do_not_leak_plaintext() {
+        let secret = "s3cr3t!plaintext";
+        let key_pair = generate_key_pair();
+        let encrypted = encrypt_value(secret, &key_pair.to_recipient().unwrap()).unwrap();
+
+        // Wrong key
+        let other = generate_key_pair().to_identity().unwrap();
+        let err = decrypt_value(&encrypted, &other).unwrap_err();
+        assert_error_hides_secret(&err, secret);
+
+        // Plaintext that was given the prefix by mistake
+        let identity = key_pair.to_identity().unwrap();
+        let err = decrypt_value(&format!("{}{}", ENCRYPTED_PREFIX, secret), &identity).unwrap_err();
+        assert_error_hides_secret(&err, secret);
+        assert!(err.to_string().contains("invalid symbol at offset 6"));
+    }
+
     #[test]
     fn test_encrypt_and_decrypt_roundtrip() {
         let key_pair = generate_key_pair();
@@ -4,7 +4,8 @@
 
 use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 use age::secrecy::ExposeSecret;
 use age::x25519::{Identity, Recipient};
@@ -64,6 +65,25 @@ pub fn generate_key_pair() -> KeyPair {
     }
 }
 
+/// Derives a key pair from a fixed 32-byte seed, for reproducible tests.
+///
+/// The seed is used directly as the X25519 secret key. Only compiled for tests;
+/// real keys always come from [`generate_key_pair`].
+#[cfg(test)]
+pub(crate) fn generate_key_pair_from_seed(seed: [u8; 32]) -> KeyPair {
+    use bech32::ToBase32;
+
+    let encoded = bech32::encode("age-secret-key-", seed.to_base32(), bech32::Variant::Bech32)
+        .expect("HRP is valid")
+        .to_uppercase();
+    let identity: Identity = encoded.parse().expect("any 32 bytes are an X25519 secret");
+
+    KeyPair {
+        public_key: identity.to_public().to_string(),
+        private_key: identity.to_string().expose_secret().to_string(),
+    }
+}
+
 /// Saves the private key to a file.
 ///
 /// The file is created with restricted permissions (0600 on Unix).
@@ -124,22 +144,75 @@ pub fn load_private_key(path: &Path) -> Result<String, CryptoError> {
     Err(CryptoError::NoPrivateKey)
 }
 
-/// Loads the private key from an environment variable.
+/// Finds the private key in the content of a key file.
+///
+/// The content is either a bare `AGE-SECRET-KEY-...` line or a `.stand.keys` file;
+/// blank lines and `#` comments are skipped.
+pub fn extract_private_key(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.strip_prefix("STAND_PRIVATE_KEY=")
+                .unwrap_or(line)
+                .trim()
+        })
+}
+
+/// Loads the private key from environment variables.
+///
+/// `STAND_PRIVATE_KEY` holds the key itself and takes precedence over
+/// `STAND_PRIVATE_KEY_FILE`, which names a file containing the key (see
+/// [`extract_private_key`]), e.g. a secret mounted by CI.
 ///
 /// # Returns
-/// - `Ok(Some(key))` if the environment variable is set and valid UTF-8
-/// - `Ok(None)` if the environment variable is not set
-/// - `Err(CryptoError::InvalidPrivateKey)` if the environment variable contains invalid UTF-8
+/// - `Ok(Some(key))` if either environment variable is set
+/// - `Ok(None)` if neither is set
+/// - `Err(CryptoError::InvalidPrivateKey)` if a variable is empty or contains invalid UTF-8,
+///   or the file contains no key
+/// - `Err(CryptoError::IoError)` if the file cannot be read
 pub fn load_private_key_from_env() -> Result<Option<String>, CryptoError> {
-    match std::env::var("STAND_PRIVATE_KEY") {
-        Ok(key) if key.trim().is_empty() => Err(CryptoError::InvalidPrivateKey(
-            "STAND_PRIVATE_KEY environment variable is empty".to_string(),
-        )),
-        Ok(key) => Ok(Some(key)),
+    if let Some(key) = read_key_variable("STAND_PRIVATE_KEY")? {
+        crate::log_verbose!("Using the private key from STAND_PRIVATE_KEY");
+        return Ok(Some(key));
+    }
+
+    let Some(path) = read_key_variable("STAND_PRIVATE_KEY_FILE")? else {
+        return Ok(None);
+    };
+    crate::log_verbose!(
+        "Using the private key from {} (STAND_PRIVATE_KEY_FILE)",
+        path
+    );
+    let content = fs::read_to_string(&path).map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!("Failed to read STAND_PRIVATE_KEY_FILE '{}': {}", path, e),
+        )
+    })?;
+    match extract_private_key(&content) {
+        Some(key) => Ok(Some(key.to_string())),
+        None => Err(CryptoError::InvalidPrivateKey(format!(
+            "no private key found in STAND_PRIVATE_KEY_FILE '{}'",
+            path
+        ))),
+    }
+}
+
+/// Reads a key environment variable, rejecting empty and non-UTF-8 values.
+fn read_key_variable(name: &str) -> Result<Option<String>, CryptoError> {
+    match std::env::var(name) {
+        Ok(value) if value.trim().is_empty() => Err(CryptoError::InvalidPrivateKey(format!(
+            "{} environment variable is empty",
+            name
+        ))),
+        Ok(value) => Ok(Some(value)),
         Err(std::env::VarError::NotPresent) => Ok(None),
-        Err(std::env::VarError::NotUnicode(_)) => Err(CryptoError::InvalidPrivateKey(
-            "STAND_PRIVATE_KEY environment variable contains invalid UTF-8".to_string(),
-        )),
+        Err(std::env::VarError::NotUnicode(_)) => Err(CryptoError::InvalidPrivateKey(format!(
+            "{} environment variable contains invalid UTF-8",
+            name
+        ))),
     }
 }
 
@@ -150,6 +223,21 @@ pub fn parse_public_key(public_key: &str) -> Result<Recipient, CryptoError> {
         .map_err(|e| CryptoError::InvalidPublicKey(e.to_string()))
 }
 
+/// Short fingerprint of a public key for comparing it out-of-band.
+///
+/// The first 8 hex digits of the SHA-256 of the key string, e.g. `SHA256:1f2e3d4c`.
+pub fn public_key_fingerprint(public_key: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(public_key.trim().as_bytes());
+    let hex: String = digest
+        .iter()
+        .take(4)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("SHA256:{}", hex)
+}
+
 /// Parses a private key string into an age Identity.
 pub fn parse_private_key(private_key: &str) -> Result<Identity, CryptoError> {
     private_key
@@ -157,12 +245,104 @@ pub fn parse_private_key(private_key: &str) -> Result<Identity, CryptoError> {
         .map_err(|e| CryptoError::InvalidPrivateKey(e.to_string()))
 }
 
+/// A problem with how the private key file is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityWarning {
+    /// The keys file is accessible by users other than the owner (Unix only).
+    InsecurePermissions { path: PathBuf, mode: u32 },
+    /// The keys file is tracked by git and may be pushed to a remote.
+    TrackedByGit { path: PathBuf },
+}
+
+impl std::fmt::Display for SecurityWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecurityWarning::InsecurePermissions { path, mode } => write!(
+                f,
+                "{} has permissions {:04o}; run 'chmod 600 {}' to restrict it to the owner",
+                path.display(),
+                mode,
+                path.display()
+            ),
+            SecurityWarning::TrackedByGit { path } => write!(
+                f,
+                "{} is tracked by git; run 'git rm --cached {}' and rotate the key",
+                path.display(),
+                path.display()
+            ),
+        }
+    }
+}
+
+/// Checks the `.stand.keys` file in `project_dir` for common leak risks.
+///
+/// Reports permissions broader than 0600 (Unix only) and whether the file is
+/// tracked by git. Returns no warnings if the file does not exist; the git check
+/// is skipped when git is unavailable or the directory is not a repository.
+pub fn check_keys_file_security(project_dir: &Path) -> Vec<SecurityWarning> {
+    let path = project_dir.join(".stand.keys");
+    let mut warnings = Vec::new();
+
+    let Ok(metadata) = fs::metadata(&path) else {
+        return warnings;
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = metadata.permissions().mode() & 0o777;
+        if mode & 0o077 != 0 {
+            warnings.push(SecurityWarning::InsecurePermissions {
+                path: path.clone(),
+                mode,
+            });
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = metadata;
+
+    if is_tracked_by_git(project_dir, &path) {
+        warnings.push(SecurityWarning::TrackedByGit { path });
+    }
+
+    warnings
+}
+
+/// Returns true if `git ls-files` reports `path` as tracked.
+fn is_tracked_by_git(project_dir: &Path, path: &Path) -> bool {
+    Command::new("git")
+        .arg("ls-files")
+        .arg("--error-unmatch")
+        .arg(path)
+        .current_dir(project_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serial_test::serial;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_public_key_fingerprint() {
+        let key_pair = generate_key_pair();
+        let fingerprint = public_key_fingerprint(&key_pair.public_key);
+
+        assert!(fingerprint.starts_with("SHA256:"));
+        assert_eq!(fingerprint.len(), "SHA256:".len() + 8);
+        assert_eq!(fingerprint, public_key_fingerprint(&key_pair.public_key));
+        assert_ne!(
+            fingerprint,
+            public_key_fingerprint(&generate_key_pair().public_key)
+        );
+    }
+
     #[test]
     fn test_generate_key_pair() {
         let key_pair = generate_key_pair();
@@ -182,6 +362,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_key_pair_from_seed_is_deterministic() {
+        let first = generate_key_pair_from_seed([7; 32]);
+        let second = generate_key_pair_from_seed([7; 32]);
+        let other = generate_key_pair_from_seed([8; 32]);
+
+        assert_eq!(first.public_key, second.public_key);
+        assert_eq!(first.private_key, second.private_key);
+        assert_ne!(first.public_key, other.public_key);
+        assert!(first.public_key.starts_with("age1"));
+        assert!(first.private_key.starts_with("AGE-SECRET-KEY-1"));
+
+        // The key pair is a working age key pair
+        let encrypted =
+            crate::crypto::encrypt_value("secret", &first.to_recipient().unwrap()).unwrap();
+        let decrypted =
+            crate::crypto::decrypt_value(&encrypted, &second.to_identity().unwrap()).unwrap();
+        assert_eq!(decrypted, "secret");
+    }
+
     #[test]
     fn test_key_pair_to_recipient_and_identity() {
         let key_pair = generate_key_pair();
@@ -246,6 +446,74 @@ mod tests {
         assert!(result.unwrap().is_none());
     }
 
+    #[test]
+    #[serial]
+    fn test_load_private_key_from_env_file() {
+        std::env::remove_var("STAND_PRIVATE_KEY");
+        let dir = tempdir().unwrap();
+        let key_pair = generate_key_pair();
+        let stand_keys = dir.path().join("stand.keys");
+        save_private_key(&stand_keys, &key_pair.private_key).unwrap();
+        let bare = dir.path().join("age.key");
+        std::fs::write(
+            &bare,
+            format!("# created by age-keygen\n{}\n", key_pair.private_key),
+        )
+        .unwrap();
+
+        // Both a .stand.keys file and a bare key (as written by age-keygen) work
+        std::env::set_var("STAND_PRIVATE_KEY_FILE", &stand_keys);
+        let from_stand_keys = load_private_key_from_env();
+        std::env::set_var("STAND_PRIVATE_KEY_FILE", &bare);
+        let from_bare = load_private_key_from_env();
+        std::env::remove_var("STAND_PRIVATE_KEY_FILE");
+
+        assert_eq!(from_stand_keys.unwrap(), Some(key_pair.private_key.clone()));
+        assert_eq!(from_bare.unwrap(), Some(key_pair.private_key));
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_private_key_from_env_prefers_inline_key() {
+        let dir = tempdir().unwrap();
+        let file_key = generate_key_pair();
+        let inline_key = generate_key_pair();
+        let key_file = dir.path().join("stand.keys");
+        save_private_key(&key_file, &file_key.private_key).unwrap();
+
+        std::env::set_var("STAND_PRIVATE_KEY", &inline_key.private_key);
+        std::env::set_var("STAND_PRIVATE_KEY_FILE", &key_file);
+        let result = load_private_key_from_env();
+        std::env::remove_var("STAND_PRIVATE_KEY");
+        std::env::remove_var("STAND_PRIVATE_KEY_FILE");
+
+        assert_eq!(result.unwrap(), Some(inline_key.private_key));
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_private_key_from_env_file_errors() {
+        std::env::remove_var("STAND_PRIVATE_KEY");
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing.key");
+        let empty = dir.path().join("empty.key");
+        std::fs::write(&empty, "# no key here\n").unwrap();
+
+        std::env::set_var("STAND_PRIVATE_KEY_FILE", &missing);
+        let missing_result = load_private_key_from_env();
+        std::env::set_var("STAND_PRIVATE_KEY_FILE", &empty);
+        let empty_result = load_private_key_from_env();
+        std::env::remove_var("STAND_PRIVATE_KEY_FILE");
+
+        let err = missing_result.unwrap_err();
+        assert!(matches!(err, CryptoError::IoError(_)));
+        assert!(err.to_string().contains("missing.key"), "{}", err);
+        assert!(matches!(
+            empty_result,
+            Err(CryptoError::InvalidPrivateKey(_))
+        ));
+    }
+
     #[test]
     fn test_load_private_key_empty_value() {
         let dir = tempdir().unwrap();
@@ -318,4 +586,67 @@ mod tests {
         let mode = metadata.permissions().mode() & 0o777;
         assert_eq!(mode, 0o600, "File should have 0600 permissions");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_keys_file_security_world_readable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let key_file = dir.path().join(".stand.keys");
+        std::fs::write(&key_file, "STAND_PRIVATE_KEY=AGE-SECRET-KEY-1\n").unwrap();
+        std::fs::set_permissions(&key_file, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let warnings = check_keys_file_security(dir.path());
+
+        assert!(warnings.contains(&SecurityWarning::InsecurePermissions {
+            path: key_file.clone(),
+            mode: 0o644,
+        }));
+        assert!(warnings[0].to_string().contains("chmod 600"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_keys_file_security_owner_only() {
+        let dir = tempdir().unwrap();
+        let key_pair = generate_key_pair();
+        save_private_key(&dir.path().join(".stand.keys"), &key_pair.private_key).unwrap();
+
+        assert!(check_keys_file_security(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_check_keys_file_security_missing_file() {
+        let dir = tempdir().unwrap();
+        assert!(check_keys_file_security(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_check_keys_file_security_tracked_by_git() {
+        let dir = tempdir().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|s| s.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init", "-q"]) {
+            eprintln!("skipping: git not available");
+            return;
+        }
+        let key_pair = generate_key_pair();
+        save_private_key(&dir.path().join(".stand.keys"), &key_pair.private_key).unwrap();
+        assert!(git(&["add", "-f", ".stand.keys"]));
+
+        let warnings = check_keys_file_security(dir.path());
+
+        assert!(warnings
+            .iter()
+            .any(|w| matches!(w, SecurityWarning::TrackedByGit { .. })));
+    }
 }
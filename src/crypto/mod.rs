@@ -6,7 +6,7 @@
 mod age_crypto;
 pub mod keys;
 
-pub use age_crypto::{decrypt_value, encrypt_value, is_encrypted};
+pub use age_crypto::{decrypt_value, encrypt_value, encrypt_value_for_recipients, is_encrypted};
 pub use keys::{generate_key_pair, KeyPair};
 
 use std::collections::HashMap;
@@ -61,7 +61,9 @@ pub enum CryptoError {
 ///
 /// # Returns
 /// A new HashMap with all encrypted values decrypted.
-/// If no encrypted values are found, returns the original HashMap unchanged.
+/// If no encrypted values are found, returns the original HashMap unchanged
+/// without loading the private key, so projects that enable encryption before
+/// encrypting anything need no `.stand.keys`.
 /// If encrypted values are found but no private key is available, returns an error.
 pub fn decrypt_variables(
     variables: HashMap<String, String>,
@@ -81,6 +83,7 @@ pub fn decrypt_variables(
     let mut result = HashMap::new();
     for (key, value) in variables {
         if is_encrypted(&value) {
+            crate::log_verbose!("Decrypting {}", key);
             let decrypted = decrypt_value(&value, &identity).map_err(|e| {
                 CryptoError::DecryptionFailedForVariable {
                     variable: key.clone(),
@@ -96,29 +99,76 @@ pub fn decrypt_variables(
     Ok(result)
 }
 
-/// Load private key from environment variable or .stand.keys file.
+/// Load private key from environment variables or .stand.keys file.
 ///
-/// Tries `STAND_PRIVATE_KEY` environment variable first, then falls back
-/// to reading from the `.stand.keys` file in the project directory.
+/// Precedence: the `STAND_PRIVATE_KEY` environment variable, then the file named
+/// by `STAND_PRIVATE_KEY_FILE`, then the `.stand.keys` file in the project directory.
 pub fn load_private_key_for_decryption(project_dir: &Path) -> Result<String, CryptoError> {
-    // First try environment variable (may error on invalid UTF-8)
-    match keys::load_private_key_from_env() {
-        Ok(Some(key)) => return Ok(key),
-        Ok(None) => {} // Not set, try file
-        Err(e) => return Err(e),
+    // First try environment variables (may error on invalid UTF-8 or an unreadable file)
+    if let Some(key) = keys::load_private_key_from_env()? {
+        return Ok(key);
     }
 
     // Then try .stand.keys file
     let keys_path = project_dir.join(".stand.keys");
+    crate::log_verbose!("Using the private key from {}", keys_path.display());
     keys::load_private_key(&keys_path)
 }
 
+/// Assert that `secret` appears nowhere in `error` or its chain of sources
+///
+/// Used by regression tests that feed a known secret through failing encryption,
+/// decryption and command paths.
+#[cfg(test)]
+pub(crate) fn assert_error_hides_secret(error: &(dyn std::error::Error + 'static), secret: &str) {
+    let mut current = Some(error);
+    while let Some(err) = current {
+        let message = err.to_string();
+        assert!(
+            !message.contains(secret),
+            "error message leaks a secret: {}",
+            message
+        );
+        current = err.source();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
+    use std::env;
     use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    #[serial]
+    fn test_load_private_key_for_decryption_precedence() {
+        let project = tempdir().unwrap();
+        let [inline_key, file_key, project_key] = [(); 3].map(|_| keys::generate_key_pair());
+        keys::save_private_key(
+            &project.path().join(".stand.keys"),
+            &project_key.private_key,
+        )
+        .unwrap();
+        let mounted = tempdir().unwrap();
+        let key_file = mounted.path().join("stand.key");
+        fs::write(&key_file, &file_key.private_key).unwrap();
+
+        // STAND_PRIVATE_KEY > STAND_PRIVATE_KEY_FILE > .stand.keys
+        env::set_var("STAND_PRIVATE_KEY", &inline_key.private_key);
+        env::set_var("STAND_PRIVATE_KEY_FILE", &key_file);
+        let with_both = load_private_key_for_decryption(project.path());
+        env::remove_var("STAND_PRIVATE_KEY");
+        let with_file = load_private_key_for_decryption(project.path());
+        env::remove_var("STAND_PRIVATE_KEY_FILE");
+        let with_neither = load_private_key_for_decryption(project.path());
+
+        assert_eq!(with_both.unwrap(), inline_key.private_key);
+        assert_eq!(with_file.unwrap(), file_key.private_key);
+        assert_eq!(with_neither.unwrap(), project_key.private_key);
+    }
+
     #[test]
     fn test_encrypted_prefix_constant() {
         assert_eq!(ENCRYPTED_PREFIX, "encrypted:");
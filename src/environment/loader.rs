@@ -2,7 +2,7 @@ use crate::environment::parser::{parse_env_content_with_options, ParseError, Par
 use anyhow::Result;
 use indexmap::IndexMap;
 use std::fs;
-use std::io;
+use std::io::{self, Read};
 use std::path::Path;
 
 #[derive(Debug, thiserror::Error)]
@@ -16,9 +16,11 @@ pub enum LoadError {
     #[error("Path is not a file: {path:?}")]
     NotAFile { path: std::path::PathBuf },
 
-    #[error("Parse error in file {path:?}: {source}")]
+    #[error("Failed to parse '{}' at line {line}: {}", .path.display(), .source.detail())]
     ParseError {
         path: std::path::PathBuf,
+        /// 1-based line number, same as `source.line()`
+        line: usize,
         source: ParseError,
     },
 
@@ -29,10 +31,41 @@ pub enum LoadError {
     },
 }
 
+impl LoadError {
+    /// Attach the file path to a parser error
+    fn parse(path: &Path, source: ParseError) -> Self {
+        LoadError::ParseError {
+            path: path.to_path_buf(),
+            line: source.line(),
+            source,
+        }
+    }
+}
+
 pub fn load_env_file<P: AsRef<Path>>(path: P) -> Result<IndexMap<String, String>, LoadError> {
     load_env_file_with_options(path, &ParseOptions::default())
 }
 
+/// Load variables from dotenv content provided by a reader (e.g. stdin)
+///
+/// `origin` is only used to label errors.
+pub fn load_env_reader_with_options<R: Read>(
+    mut reader: R,
+    origin: &Path,
+    options: &ParseOptions,
+) -> Result<IndexMap<String, String>, LoadError> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|err| LoadError::IoError {
+            path: origin.to_path_buf(),
+            source: err,
+        })?;
+
+    parse_env_content_with_options(&content, options)
+        .map_err(|parse_error| LoadError::parse(origin, parse_error))
+}
+
 pub fn load_env_file_with_options<P: AsRef<Path>>(
     path: P,
     options: &ParseOptions,
@@ -72,10 +105,28 @@ pub fn load_env_file_with_options<P: AsRef<Path>>(
     })?;
 
     // Parse the content using our parser
-    parse_env_content_with_options(&content, options).map_err(|parse_error| LoadError::ParseError {
-        path: path_buf,
-        source: parse_error,
-    })
+    parse_env_content_with_options(&content, options)
+        .map_err(|parse_error| LoadError::parse(path, parse_error))
+}
+
+/// Load several env files and merge them in order (later files override earlier ones)
+pub fn load_env_files<P: AsRef<Path>>(paths: &[P]) -> Result<IndexMap<String, String>, LoadError> {
+    load_env_files_with_options(paths, &ParseOptions::default())
+}
+
+/// Load and merge several env files like [`load_env_files`], parsing each with `options`
+///
+/// Keys keep the position of their first definition. Loading stops at the first
+/// file that fails; the error names that file.
+pub fn load_env_files_with_options<P: AsRef<Path>>(
+    paths: &[P],
+    options: &ParseOptions,
+) -> Result<IndexMap<String, String>, LoadError> {
+    let mut variables = IndexMap::new();
+    for path in paths {
+        variables.extend(load_env_file_with_options(path, options)?);
+    }
+    Ok(variables)
 }
 
 #[cfg(test)]
@@ -118,4 +169,59 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), LoadError::NotAFile { .. }));
     }
+
+    #[test]
+    fn test_load_env_file_parse_error_reports_path_and_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let env_file = temp_dir.path().join(".env");
+
+        fs::write(&env_file, "KEY=value\n# comment\nINVALID LINE\n").unwrap();
+
+        let err = load_env_file(&env_file).unwrap_err();
+        assert!(matches!(err, LoadError::ParseError { line: 3, .. }));
+        // The line may be a pasted secret, so it is masked
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Failed to parse '{}' at line 3: '********'",
+                env_file.display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_load_env_files_merges_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths: Vec<_> = [".env", ".env.dev", ".env.dev.local"]
+            .iter()
+            .map(|name| temp_dir.path().join(name))
+            .collect();
+        fs::write(&paths[0], "A=base\nB=base\nC=base\n").unwrap();
+        fs::write(&paths[1], "B=dev\nC=dev\nD=dev\n").unwrap();
+        fs::write(&paths[2], "C=local\n").unwrap();
+
+        let result = load_env_files(&paths).unwrap();
+
+        let entries: Vec<_> = result
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            [("A", "base"), ("B", "dev"), ("C", "local"), ("D", "dev")]
+        );
+    }
+
+    #[test]
+    fn test_load_env_files_names_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let present = temp_dir.path().join(".env");
+        let missing = temp_dir.path().join(".env.missing");
+        fs::write(&present, "A=1\n").unwrap();
+
+        let err = load_env_files(&[&present, &missing]).unwrap_err();
+
+        assert!(matches!(&err, LoadError::FileNotFound { path } if *path == missing));
+        assert!(err.to_string().contains(".env.missing"));
+    }
 }
@@ -1,3 +1,4 @@
+use crate::utils::colors::{format_secret_summary, mask_value};
 use anyhow::Result;
 use indexmap::IndexMap;
 use std::fmt;
@@ -13,7 +14,12 @@ impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ParseError::InvalidFormat { line, content } => {
-                write!(f, "Invalid format at line {}: '{}'", line, content)
+                write!(
+                    f,
+                    "Invalid format at line {}: '{}'",
+                    line,
+                    masked_line(content)
+                )
             }
             ParseError::UnterminatedQuote { line } => {
                 write!(f, "Unterminated quote at line {}", line)
@@ -27,6 +33,40 @@ impl fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+impl ParseError {
+    /// 1-based line number where the error occurred
+    pub fn line(&self) -> usize {
+        match self {
+            ParseError::InvalidFormat { line, .. }
+            | ParseError::UnterminatedQuote { line }
+            | ParseError::InvalidEscape { line, .. } => *line,
+        }
+    }
+
+    /// What is wrong with the line, without the line number
+    ///
+    /// For `InvalidFormat` this is the offending line itself, quoted, with the value masked.
+    pub fn detail(&self) -> String {
+        match self {
+            ParseError::InvalidFormat { content, .. } => format!("'{}'", masked_line(content)),
+            ParseError::UnterminatedQuote { .. } => "unterminated quote".to_string(),
+            ParseError::InvalidEscape { sequence, .. } => {
+                format!("invalid escape sequence '{}'", sequence)
+            }
+        }
+    }
+}
+
+/// Mask the value part of a line so error messages never echo secrets
+///
+/// Keeps everything before the first `=` (the key); a line without `=` is masked entirely.
+fn masked_line(line: &str) -> String {
+    match line.split_once('=') {
+        Some((key, value)) => format_secret_summary(key, value.trim()),
+        None => mask_value(line.trim(), false),
+    }
+}
+
 pub fn parse_env_content(content: &str) -> Result<IndexMap<String, String>, ParseError> {
     parse_env_content_with_options(content, &ParseOptions::default())
 }
@@ -34,22 +74,63 @@ pub fn parse_env_content(content: &str) -> Result<IndexMap<String, String>, Pars
 #[derive(Debug, Clone)]
 pub struct ParseOptions {
     pub expand_variables: bool,
+    /// Also expand bare `$VAR` references, not only `${VAR}` (requires `expand_variables`)
+    pub expand_bare_vars: bool,
+    /// Record a [`ParseWarning`] for unquoted values ending in whitespace
+    ///
+    /// The value is still kept as written; see [`parse_env_content_with_warnings`].
+    pub warn_trailing_whitespace: bool,
 }
 
 impl Default for ParseOptions {
     fn default() -> Self {
         Self {
             expand_variables: true,
+            expand_bare_vars: true,
+            warn_trailing_whitespace: false,
         }
     }
 }
 
+/// A suspicious but valid line, reported when enabled in [`ParseOptions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// 1-based line number
+    pub line: usize,
+    pub key: String,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Line {}: unquoted value of {} ends with whitespace; quote the value if the whitespace is intended",
+            self.line, self.key
+        )
+    }
+}
+
 pub fn parse_env_content_with_options(
     content: &str,
     options: &ParseOptions,
 ) -> Result<IndexMap<String, String>, ParseError> {
+    parse_env_content_with_warnings(content, options).map(|(variables, _)| variables)
+}
+
+/// Like [`parse_env_content_with_options`], also returning the warnings enabled in `options`
+pub fn parse_env_content_with_warnings(
+    content: &str,
+    options: &ParseOptions,
+) -> Result<(IndexMap<String, String>, Vec<ParseWarning>), ParseError> {
     let mut variables = IndexMap::new();
-    let lines: Vec<&str> = content.lines().collect();
+    let mut warnings = Vec::new();
+    // Files saved by Windows editors may start with a BOM and end lines with CRLF.
+    // `lines()` keeps the `\r` of a final line without `\n`, so strip it here too
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let lines: Vec<&str> = content
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
     let mut line_idx = 0;
 
     while line_idx < lines.len() {
@@ -81,20 +162,35 @@ pub fn parse_env_content_with_options(
         }
 
         // Parse and process the value (may consume multiple lines)
-        let (parsed_value, lines_consumed) =
+        let (parsed, lines_consumed) =
             parse_value_multiline(value_part, &lines[line_idx..], line_num)?;
 
+        // Quoted values keep their whitespace on purpose; an inline comment trims it
+        let quoted = value_part.trim_start().starts_with(['"', '\'']);
+        if options.warn_trailing_whitespace && !quoted && parsed.text.ends_with(char::is_whitespace)
+        {
+            warnings.push(ParseWarning {
+                line: line_num,
+                key: key.to_string(),
+            });
+        }
+
         let final_value = if options.expand_variables {
-            expand_variables(&parsed_value, &variables)
+            expand_variables(
+                &parsed.text,
+                &parsed.literal_dollars,
+                &variables,
+                options.expand_bare_vars,
+            )
         } else {
-            parsed_value
+            parsed.text
         };
 
         variables.insert(key.to_string(), final_value);
         line_idx += lines_consumed;
     }
 
-    Ok(variables)
+    Ok((variables, warnings))
 }
 
 fn find_equals_position(line: &str) -> Option<usize> {
@@ -124,11 +220,31 @@ fn is_valid_key(key: &str) -> bool {
     !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_')
 }
 
+/// A value with quotes and escapes removed
+#[derive(Debug, PartialEq)]
+struct ParsedValue {
+    text: String,
+    /// Byte offsets in `text` of `$` signs that never start a reference: those
+    /// escaped as `\$`, and every `$` of a single-quoted value
+    literal_dollars: Vec<usize>,
+}
+
+impl ParsedValue {
+    /// A single-quoted value, in which nothing is expanded
+    fn literal(text: String) -> Self {
+        let literal_dollars = text.match_indices('$').map(|(i, _)| i).collect();
+        Self {
+            text,
+            literal_dollars,
+        }
+    }
+}
+
 fn parse_value_multiline(
     value_part: &str,
     remaining_lines: &[&str],
     line_num: usize,
-) -> Result<(String, usize), ParseError> {
+) -> Result<(ParsedValue, usize), ParseError> {
     // Check if this is a quoted value by looking at the trimmed start
     let trimmed_start = value_part.trim_start();
 
@@ -140,7 +256,7 @@ fn parse_value_multiline(
     }
 
     // Handle unquoted values
-    let value = if let Some(comment_pos) = value_part.find('#') {
+    let value = if let Some(comment_pos) = find_inline_comment(value_part) {
         // Remove inline comment and trim trailing whitespace
         value_part[..comment_pos].trim_end()
     } else {
@@ -148,13 +264,46 @@ fn parse_value_multiline(
         value_part
     };
 
-    Ok((value.to_string(), 1))
+    Ok((unescape_dollars(value), 1))
+}
+
+/// Turn `\$` into a literal `$` in an unquoted value; other backslashes are kept
+fn unescape_dollars(value: &str) -> ParsedValue {
+    let mut text = String::with_capacity(value.len());
+    let mut literal_dollars = Vec::new();
+    let mut rest = value;
+    while let Some(pos) = rest.find("\\$") {
+        text.push_str(&rest[..pos]);
+        literal_dollars.push(text.len());
+        text.push('$');
+        rest = &rest[pos + 2..];
+    }
+    text.push_str(rest);
+    ParsedValue {
+        text,
+        literal_dollars,
+    }
+}
+
+/// Position of an inline comment in an unquoted value
+///
+/// `#` only starts a comment at the start of the value or after whitespace, so
+/// `URL=http://x/#frag` keeps its fragment.
+fn find_inline_comment(value: &str) -> Option<usize> {
+    let mut previous: Option<char> = None;
+    for (i, ch) in value.char_indices() {
+        if ch == '#' && previous.is_none_or(char::is_whitespace) {
+            return Some(i);
+        }
+        previous = Some(ch);
+    }
+    None
 }
 
 fn parse_multiline_double_quoted(
     lines: &[&str],
     start_line: usize,
-) -> Result<(String, usize), ParseError> {
+) -> Result<(ParsedValue, usize), ParseError> {
     let first_line = lines[0];
     let value_part = &first_line[first_line.find('=').unwrap() + 1..];
 
@@ -209,7 +358,7 @@ fn parse_multiline_double_quoted(
 fn parse_multiline_single_quoted(
     lines: &[&str],
     start_line: usize,
-) -> Result<(String, usize), ParseError> {
+) -> Result<(ParsedValue, usize), ParseError> {
     let first_line = lines[0];
     let value_part = &first_line[first_line.find('=').unwrap() + 1..];
 
@@ -258,8 +407,8 @@ fn parse_multiline_single_quoted(
         return Err(ParseError::UnterminatedQuote { line: start_line });
     }
 
-    // Single quotes preserve literal values (no escape processing)
-    Ok((content, lines_consumed))
+    // Single quotes preserve literal values (no escape processing or expansion)
+    Ok((ParsedValue::literal(content), lines_consumed))
 }
 
 fn find_closing_quote(content: &str, quote_char: char) -> Option<usize> {
@@ -281,13 +430,18 @@ fn find_closing_quote(content: &str, quote_char: char) -> Option<usize> {
     None
 }
 
-fn process_escape_sequences(value: &str) -> Result<String, ParseError> {
+fn process_escape_sequences(value: &str) -> Result<ParsedValue, ParseError> {
     let mut result = String::new();
+    let mut literal_dollars = Vec::new();
     let mut chars = value.chars().peekable();
 
     while let Some(ch) = chars.next() {
         if ch == '\\' {
             match chars.next() {
+                Some('$') => {
+                    literal_dollars.push(result.len());
+                    result.push('$');
+                }
                 Some('n') => result.push('\n'),
                 Some('t') => result.push('\t'),
                 Some('r') => result.push('\r'),
@@ -306,30 +460,81 @@ fn process_escape_sequences(value: &str) -> Result<String, ParseError> {
         }
     }
 
-    Ok(result)
+    Ok(ParsedValue {
+        text: result,
+        literal_dollars,
+    })
 }
 
-fn expand_variables(value: &str, variables: &IndexMap<String, String>) -> String {
-    let mut result = value.to_string();
-
-    // Simple variable expansion for ${VAR} pattern
-    while let Some(start) = result.find("${") {
-        if let Some(end) = result[start..].find('}') {
-            let var_name = &result[start + 2..start + end];
-            let replacement = variables.get(var_name).map(|v| v.as_str()).unwrap_or("");
-
-            result.replace_range(start..start + end + 1, replacement);
+/// Expand `${VAR}` (and bare `$VAR` when `bare` is set) from earlier-parsed variables
+///
+/// Undefined variables expand to an empty string. `$$`, a `$` not followed by an
+/// identifier (e.g. `$5`) and the `$` signs at the byte offsets in `literal` are
+/// kept as they are.
+fn expand_variables(
+    value: &str,
+    literal: &[usize],
+    variables: &IndexMap<String, String>,
+    bare: bool,
+) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(dollar) = rest.find('$') {
+        result.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let offset = value.len() - rest.len() + dollar;
+
+        if literal.contains(&offset) {
+            result.push('$');
+            rest = after;
+        } else if let Some(braced) = after.strip_prefix('{') {
+            let Some(end) = braced.find('}') else {
+                // No closing brace found
+                result.push_str(&rest[dollar..]);
+                return result;
+            };
+            result.push_str(lookup(variables, &braced[..end]));
+            rest = &braced[end + 1..];
+        } else if let Some(escaped) = after.strip_prefix('$') {
+            result.push_str("$$");
+            rest = escaped;
+        } else if bare && after.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            result.push_str(lookup(variables, &after[..end]));
+            rest = &after[end..];
         } else {
-            break; // No closing brace found
+            result.push('$');
+            rest = after;
         }
     }
 
+    result.push_str(rest);
     result
 }
 
+fn lookup<'a>(variables: &'a IndexMap<String, String>, name: &str) -> &'a str {
+    variables.get(name).map(|v| v.as_str()).unwrap_or("")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::assert_error_hides_secret;
+
+    #[test]
+    fn test_invalid_format_error_masks_value() {
+        for content in ["API_KEY s3cr3t-value", "bad key=s3cr3t-value"] {
+            let err = parse_env_content(content).unwrap_err();
+            assert_error_hides_secret(&err, "s3cr3t-value");
+            assert!(err.detail().contains("****"));
+        }
+
+        let err = parse_env_content("bad key=s3cr3t-value").unwrap_err();
+        assert!(err.to_string().contains("'bad key=*"));
+    }
 
     #[test]
     fn test_find_equals_position() {
@@ -351,18 +556,17 @@ mod tests {
 
     #[test]
     fn test_process_escape_sequences() {
+        let unescape = |value| process_escape_sequences(value).unwrap().text;
+        assert_eq!(unescape("line1\\nline2"), "line1\nline2");
+        assert_eq!(unescape("tab\\there"), "tab\there");
+        assert_eq!(unescape("quote\\\"here"), "quote\"here");
+        assert_eq!(unescape("backslash\\\\here"), "backslash\\here");
         assert_eq!(
-            process_escape_sequences("line1\\nline2").unwrap(),
-            "line1\nline2"
-        );
-        assert_eq!(process_escape_sequences("tab\\there").unwrap(), "tab\there");
-        assert_eq!(
-            process_escape_sequences("quote\\\"here").unwrap(),
-            "quote\"here"
-        );
-        assert_eq!(
-            process_escape_sequences("backslash\\\\here").unwrap(),
-            "backslash\\here"
+            process_escape_sequences("cost \\$5 \\$HOME").unwrap(),
+            ParsedValue {
+                text: "cost $5 $HOME".to_string(),
+                literal_dollars: vec![5, 8],
+            }
         );
     }
 
@@ -372,9 +576,53 @@ mod tests {
         vars.insert("BASE".to_string(), "https://api.example.com".to_string());
 
         assert_eq!(
-            expand_variables("${BASE}/v1", &vars),
+            expand_variables("${BASE}/v1", &[], &vars, true),
             "https://api.example.com/v1"
         );
-        assert_eq!(expand_variables("${UNDEFINED}/v1", &vars), "/v1");
+        assert_eq!(expand_variables("${UNDEFINED}/v1", &[], &vars, true), "/v1");
+    }
+
+    #[test]
+    fn test_dollar_signs_in_values() {
+        let content = r#"USER=admin
+HASH='$2b$10$abcdef'
+PASSWORD='p$word ${USER}'
+ESCAPED=p\$word
+ESCAPED_BRACES="\${USER} is $USER"
+BACKSLASH=C:\dir\$USER
+"#;
+
+        let vars = parse_env_content(content).unwrap();
+
+        assert_eq!(vars["HASH"], "$2b$10$abcdef");
+        assert_eq!(vars["PASSWORD"], "p$word ${USER}");
+        assert_eq!(vars["ESCAPED"], "p$word");
+        assert_eq!(vars["ESCAPED_BRACES"], "${USER} is admin");
+        // Only `\$` is an escape in unquoted values
+        assert_eq!(vars["BACKSLASH"], "C:\\dir$USER");
+    }
+
+    #[test]
+    fn test_expand_variables_skips_literal_dollars() {
+        let mut vars = IndexMap::new();
+        vars.insert("FOO".to_string(), "foo".to_string());
+
+        assert_eq!(expand_variables("$FOO $FOO", &[0], &vars, true), "$FOO foo");
+        assert_eq!(expand_variables("${FOO}", &[0], &vars, true), "${FOO}");
+    }
+
+    #[test]
+    fn test_expand_bare_variables() {
+        let mut vars = IndexMap::new();
+        vars.insert("FOO".to_string(), "foo".to_string());
+
+        assert_eq!(expand_variables("$FOO/bar", &[], &vars, true), "foo/bar");
+        assert_eq!(expand_variables("${FOO}bar", &[], &vars, true), "foobar");
+        assert_eq!(expand_variables("$FOObar", &[], &vars, true), "");
+        assert_eq!(expand_variables("price=$5", &[], &vars, true), "price=$5");
+        assert_eq!(expand_variables("$$FOO", &[], &vars, true), "$$FOO");
+        assert_eq!(expand_variables("cost: $", &[], &vars, true), "cost: $");
+        assert_eq!(expand_variables("${FOO", &[], &vars, true), "${FOO");
+        assert_eq!(expand_variables("$FOO/bar", &[], &vars, false), "$FOO/bar");
     }
 }
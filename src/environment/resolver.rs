@@ -1,9 +1,15 @@
 use anyhow::Result;
 use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
+use std::fmt;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
 
-use crate::environment::loader::{load_env_file_with_options, LoadError};
+use crate::environment::loader::{
+    load_env_file_with_options, load_env_reader_with_options, LoadError,
+};
 use crate::environment::parser::ParseOptions;
 
 #[derive(Debug, thiserror::Error)]
@@ -14,8 +20,20 @@ pub enum ResolveError {
     #[error("Undefined variable referenced: {variable}")]
     UndefinedVariable { variable: String },
 
-    #[error("Error loading from source: {source}")]
-    SourceError { source: LoadError },
+    #[error("Unexpanded '${{' left in variables: {}", variables.join(", "))]
+    UnexpandedPlaceholder {
+        /// Names of the offending variables, sorted
+        variables: Vec<String>,
+    },
+
+    #[error("Error loading from source #{index} ({description}): {source}")]
+    SourceError {
+        /// 1-based position of the source in the order it was added
+        index: usize,
+        /// The failing source, e.g. `EnvFile(/path/.env.local)`
+        description: String,
+        source: LoadError,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +42,41 @@ pub enum VariableSource {
     EnvFile(PathBuf),
     SystemEnv,
     CliArgs(IndexMap<String, String>),
+    /// Dotenv content piped to stdin (or the reader set with `with_stdin_reader`)
+    Stdin,
+}
+
+impl VariableSource {
+    /// The kind of this source, used by [`ResolutionOptions::priority`]
+    pub fn kind(&self) -> SourceKind {
+        match self {
+            VariableSource::Default(_) => SourceKind::Default,
+            VariableSource::EnvFile(_) => SourceKind::EnvFile,
+            VariableSource::SystemEnv => SourceKind::SystemEnv,
+            VariableSource::CliArgs(_) => SourceKind::CliArgs,
+            VariableSource::Stdin => SourceKind::Stdin,
+        }
+    }
+}
+
+impl fmt::Display for VariableSource {
+    /// Names the source without its variables, which may hold secrets
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableSource::EnvFile(path) => write!(f, "EnvFile({})", path.display()),
+            other => write!(f, "{:?}", other.kind()),
+        }
+    }
+}
+
+/// Kind of a [`VariableSource`], without its data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    Default,
+    EnvFile,
+    SystemEnv,
+    CliArgs,
+    Stdin,
 }
 
 #[derive(Debug, Clone)]
@@ -36,28 +89,62 @@ pub enum UndefinedVariableBehavior {
 #[derive(Debug, Clone)]
 pub struct ResolutionOptions {
     pub undefined_variable_behavior: UndefinedVariableBehavior,
+    /// Merge order of source kinds, lowest priority first (later kinds win)
+    ///
+    /// Sources of the same kind keep their insertion order. Sources whose kind is not
+    /// listed are merged before all listed kinds. The default (empty) merges every
+    /// source in insertion order, so the last source added wins.
+    pub priority: Vec<SourceKind>,
+    /// Fail with [`ResolveError::UnexpandedPlaceholder`] when a resolved value still
+    /// contains `${`, e.g. from an unterminated `${VAR` or an escaped `$${VAR}`
+    pub strict: bool,
 }
 
 impl Default for ResolutionOptions {
     fn default() -> Self {
         Self {
             undefined_variable_behavior: UndefinedVariableBehavior::EmptyString,
+            priority: Vec::new(),
+            strict: false,
         }
     }
 }
 
+/// Reader backing `VariableSource::Stdin`; read at most once and cached
+#[derive(Default)]
+struct StdinSource {
+    reader: RefCell<Option<Box<dyn Read>>>,
+    cache: RefCell<Option<IndexMap<String, String>>>,
+}
+
+impl fmt::Debug for StdinSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StdinSource")
+            .field("consumed", &self.cache.borrow().is_some())
+            .finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct EnvironmentResolver {
     sources: Vec<VariableSource>,
+    stdin: StdinSource,
 }
 
 impl EnvironmentResolver {
     pub fn new() -> Self {
         Self {
             sources: Vec::new(),
+            stdin: StdinSource::default(),
         }
     }
 
+    /// Read `VariableSource::Stdin` content from `reader` instead of the process stdin
+    pub fn with_stdin_reader<R: Read + 'static>(self, reader: R) -> Self {
+        *self.stdin.reader.borrow_mut() = Some(Box::new(reader));
+        self
+    }
+
     pub fn add_source(&mut self, source: VariableSource) {
         self.sources.push(source);
     }
@@ -70,33 +157,83 @@ impl EnvironmentResolver {
         &self,
         options: &ResolutionOptions,
     ) -> Result<IndexMap<String, String>, ResolveError> {
-        // Step 1: Collect variables from all sources in order (later sources override earlier ones)
+        // Step 1: Collect variables from all sources in priority order (later sources
+        // override earlier ones)
         let mut variables = IndexMap::new();
 
-        for source in &self.sources {
-            let source_vars = self.load_source_variables(source)?;
+        for (position, source) in self.sources_by_priority(&options.priority) {
+            let source_vars =
+                self.load_source_variables(source)
+                    .map_err(|e| ResolveError::SourceError {
+                        index: position + 1,
+                        description: source.to_string(),
+                        source: e,
+                    })?;
             for (key, value) in source_vars {
                 variables.insert(key, value);
             }
         }
 
         // Step 2: Expand variables with circular reference detection
-        self.expand_variables(variables, options)
+        let resolved = self.expand_variables(variables, options)?;
+
+        if options.strict {
+            Self::ensure_fully_expanded(&resolved)?;
+        }
+        Ok(resolved)
+    }
+
+    /// Fail if any value still contains `${` after expansion
+    ///
+    /// The error lists every offending variable, sorted by name; values are not
+    /// included since they may be secrets.
+    pub fn ensure_fully_expanded<'a>(
+        variables: impl IntoIterator<Item = (&'a String, &'a String)>,
+    ) -> Result<(), ResolveError> {
+        let mut unexpanded: Vec<String> = variables
+            .into_iter()
+            .filter(|(_, value)| value.contains("${"))
+            .map(|(key, _)| key.clone())
+            .collect();
+        if unexpanded.is_empty() {
+            return Ok(());
+        }
+        unexpanded.sort();
+        Err(ResolveError::UnexpandedPlaceholder {
+            variables: unexpanded,
+        })
+    }
+
+    /// Order sources for merging: unlisted kinds first, then by position in `priority`
+    ///
+    /// Each source is paired with its insertion position, used in error messages.
+    fn sources_by_priority(&self, priority: &[SourceKind]) -> Vec<(usize, &VariableSource)> {
+        let mut sources: Vec<_> = self.sources.iter().enumerate().collect();
+        if !priority.is_empty() {
+            // Stable sort keeps insertion order within a kind; `None` sorts first
+            sources
+                .sort_by_key(|(_, source)| priority.iter().position(|kind| *kind == source.kind()));
+        }
+        sources
     }
 
-    fn load_source_variables(
+    /// Variables of a single source as written, without expanding references
+    ///
+    /// `VariableSource::Stdin` returns the same variables `resolve` used, since stdin
+    /// is read only once.
+    pub fn load_source_variables(
         &self,
         source: &VariableSource,
-    ) -> Result<IndexMap<String, String>, ResolveError> {
+    ) -> Result<IndexMap<String, String>, LoadError> {
         match source {
             VariableSource::Default(vars) => Ok(vars.clone()),
 
             VariableSource::EnvFile(path) => {
                 let parse_options = ParseOptions {
                     expand_variables: false,
+                    ..ParseOptions::default()
                 };
                 load_env_file_with_options(path, &parse_options)
-                    .map_err(|e| ResolveError::SourceError { source: e })
             }
 
             VariableSource::SystemEnv => {
@@ -108,7 +245,38 @@ impl EnvironmentResolver {
             }
 
             VariableSource::CliArgs(vars) => Ok(vars.clone()),
+
+            VariableSource::Stdin => self.load_stdin_variables(),
+        }
+    }
+
+    /// Parse dotenv content from stdin, reading it only on first use
+    ///
+    /// An interactive (non-piped) stdin yields no variables instead of blocking.
+    fn load_stdin_variables(&self) -> Result<IndexMap<String, String>, LoadError> {
+        if let Some(cached) = self.stdin.cache.borrow().as_ref() {
+            return Ok(cached.clone());
         }
+
+        let parse_options = ParseOptions {
+            expand_variables: false,
+            ..ParseOptions::default()
+        };
+        let origin = Path::new("<stdin>");
+        let vars = match self.stdin.reader.borrow_mut().take() {
+            Some(reader) => load_env_reader_with_options(reader, origin, &parse_options),
+            None => {
+                let stdin = io::stdin();
+                if stdin.is_terminal() {
+                    Ok(IndexMap::new())
+                } else {
+                    load_env_reader_with_options(stdin.lock(), origin, &parse_options)
+                }
+            }
+        }?;
+
+        *self.stdin.cache.borrow_mut() = Some(vars.clone());
+        Ok(vars)
     }
 
     fn expand_variables(
@@ -116,70 +284,153 @@ impl EnvironmentResolver {
         variables: IndexMap<String, String>,
         options: &ResolutionOptions,
     ) -> Result<IndexMap<String, String>, ResolveError> {
-        let mut resolved = IndexMap::new();
+        let mut resolved = IndexMap::with_capacity(variables.len());
+        // Fully expanded values, shared across keys so each variable is
+        // expanded at most once however often it is referenced
+        let mut expanded = HashMap::new();
 
         for (key, value) in &variables {
-            let mut expansion_stack = Vec::new(); // Fresh stack for each variable
-            let expanded_value =
-                Self::expand_value(value, &variables, options, &mut expansion_stack)?;
+            let expanded_value = match expanded.get(key.as_str()) {
+                Some(cached) => String::clone(cached),
+                None => {
+                    let mut expansion_stack = Vec::new(); // Fresh stack for each variable
+                    let value = Self::expand_value(
+                        value,
+                        &variables,
+                        options,
+                        &mut expansion_stack,
+                        &mut expanded,
+                    )?;
+                    expanded.insert(key.as_str(), value.clone());
+                    value
+                }
+            };
             resolved.insert(key.clone(), expanded_value);
         }
 
         Ok(resolved)
     }
 
-    fn expand_value(
+    /// Find a `${VAR}` reference cycle among `variables`, e.g. `["A", "B", "A"]`
+    ///
+    /// Only references between entries of `variables` are followed; references to
+    /// other names are ignored. A variable referring to itself, as in
+    /// `PATH = "/opt/bin:${PATH}"`, extends the process variable of the same name
+    /// and is not a cycle. Returns the first cycle found, in map order.
+    pub fn find_reference_cycle(variables: &IndexMap<String, String>) -> Option<Vec<String>> {
+        let options = ResolutionOptions {
+            undefined_variable_behavior: UndefinedVariableBehavior::LeaveUnexpanded,
+            ..ResolutionOptions::default()
+        };
+        let mut expanded = HashMap::new();
+
+        for (key, value) in variables {
+            if expanded.contains_key(key.as_str()) {
+                continue;
+            }
+            let mut expansion_stack = vec![key.as_str()];
+            match Self::expand_value(
+                value,
+                variables,
+                &options,
+                &mut expansion_stack,
+                &mut expanded,
+            ) {
+                Ok(value) => {
+                    expanded.insert(key.as_str(), value);
+                }
+                Err(ResolveError::CircularReference { cycle }) if cycle.len() > 2 => {
+                    return Some(cycle)
+                }
+                Err(_) => {}
+            }
+        }
+        None
+    }
+
+    /// Expand `${VAR}` references and `$$` escapes in one left-to-right pass
+    ///
+    /// Replacements are already fully expanded and are never rescanned, which
+    /// also keeps references left in place by LeaveUnexpanded from being
+    /// revisited. An unterminated `${` stops expansion and keeps the rest as is.
+    fn expand_value<'a>(
         value: &str,
-        all_variables: &IndexMap<String, String>,
+        all_variables: &'a IndexMap<String, String>,
         options: &ResolutionOptions,
-        expansion_stack: &mut Vec<String>,
+        expansion_stack: &mut Vec<&'a str>,
+        expanded: &mut HashMap<&'a str, String>,
     ) -> Result<String, ResolveError> {
-        let mut result = value.to_string();
-
-        // Find and expand all ${VAR} patterns
-        while let Some(start) = result.find("${") {
-            if let Some(end) = result[start..].find('}') {
-                let var_name = &result[start + 2..start + end];
-
-                // Check for circular reference
-                if expansion_stack.contains(&var_name.to_string()) {
-                    // Find the cycle starting from where the variable was first encountered
-                    let start_pos = expansion_stack.iter().position(|v| v == var_name).unwrap();
-                    let mut cycle: Vec<String> = expansion_stack[start_pos..].to_vec();
-                    cycle.push(var_name.to_string());
-                    return Err(ResolveError::CircularReference { cycle });
-                }
-
-                // Get the variable value
-                let replacement = if let Some(var_value) = all_variables.get(var_name) {
-                    // Recursively expand the variable value
-                    expansion_stack.push(var_name.to_string());
-                    let expanded =
-                        Self::expand_value(var_value, all_variables, options, expansion_stack)?;
-                    expansion_stack.pop();
-                    expanded
-                } else {
-                    // Handle undefined variable based on options
-                    match options.undefined_variable_behavior {
-                        UndefinedVariableBehavior::Error => {
-                            return Err(ResolveError::UndefinedVariable {
-                                variable: var_name.to_string(),
-                            });
-                        }
-                        UndefinedVariableBehavior::EmptyString => String::new(),
-                        UndefinedVariableBehavior::LeaveUnexpanded => {
-                            format!("${{{}}}", var_name)
-                        }
-                    }
-                };
-
-                // Replace the variable reference
-                result.replace_range(start..start + end + 1, &replacement);
-            } else {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(offset) = rest.find('$') {
+            result.push_str(&rest[..offset]);
+            let after = &rest[offset + 1..];
+
+            // `$$` is an escaped `$`
+            if let Some(stripped) = after.strip_prefix('$') {
+                result.push('$');
+                rest = stripped;
+                continue;
+            }
+            let Some(body) = after.strip_prefix('{') else {
+                result.push('$');
+                rest = after;
+                continue;
+            };
+            let Some(end) = body.find('}') else {
                 // No closing brace found, stop expansion
+                result.push('$');
+                rest = after;
                 break;
+            };
+            let var_name = &body[..end];
+            rest = &body[end + 1..];
+
+            // Check for circular reference
+            if let Some(start_pos) = expansion_stack.iter().position(|v| *v == var_name) {
+                // Report the cycle starting from where the variable was first encountered
+                let mut cycle: Vec<String> = expansion_stack[start_pos..]
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect();
+                cycle.push(var_name.to_string());
+                return Err(ResolveError::CircularReference { cycle });
+            }
+
+            if let Some(cached) = expanded.get(var_name) {
+                result.push_str(cached);
+            } else if let Some((name, var_value)) = all_variables.get_key_value(var_name) {
+                // Recursively expand the variable value
+                expansion_stack.push(name);
+                let value = Self::expand_value(
+                    var_value,
+                    all_variables,
+                    options,
+                    expansion_stack,
+                    expanded,
+                )?;
+                expansion_stack.pop();
+                result.push_str(&value);
+                expanded.insert(name, value);
+            } else {
+                // Handle undefined variable based on options
+                match options.undefined_variable_behavior {
+                    UndefinedVariableBehavior::Error => {
+                        return Err(ResolveError::UndefinedVariable {
+                            variable: var_name.to_string(),
+                        });
+                    }
+                    UndefinedVariableBehavior::EmptyString => {}
+                    UndefinedVariableBehavior::LeaveUnexpanded => {
+                        result.push_str("${");
+                        result.push_str(var_name);
+                        result.push('}');
+                    }
+                }
             }
         }
+        result.push_str(rest);
 
         Ok(result)
     }
@@ -257,4 +508,181 @@ mod tests {
             ResolveError::CircularReference { .. }
         ));
     }
+
+    #[test]
+    fn test_stdin_source_priority() {
+        let mut resolver =
+            EnvironmentResolver::new().with_stdin_reader(&b"KEY=stdin\nSTDIN_ONLY=yes\n"[..]);
+
+        let mut defaults = IndexMap::new();
+        defaults.insert("KEY".to_string(), "default".to_string());
+        defaults.insert("OTHER".to_string(), "default".to_string());
+        resolver.add_source(VariableSource::Default(defaults));
+
+        resolver.add_source(VariableSource::Stdin);
+
+        let mut cli_args = IndexMap::new();
+        cli_args.insert("OTHER".to_string(), "cli".to_string());
+        resolver.add_source(VariableSource::CliArgs(cli_args));
+
+        let resolved = resolver.resolve().unwrap();
+        assert_eq!(resolved.get("KEY"), Some(&"stdin".to_string()));
+        assert_eq!(resolved.get("STDIN_ONLY"), Some(&"yes".to_string()));
+        assert_eq!(resolved.get("OTHER"), Some(&"cli".to_string()));
+    }
+
+    #[test]
+    fn test_stdin_source_is_read_once() {
+        let mut resolver = EnvironmentResolver::new().with_stdin_reader(&b"KEY=stdin\n"[..]);
+        resolver.add_source(VariableSource::Stdin);
+        resolver.add_source(VariableSource::Stdin);
+
+        let first = resolver.resolve().unwrap();
+        let second = resolver.resolve().unwrap();
+        assert_eq!(first.get("KEY"), Some(&"stdin".to_string()));
+        assert_eq!(second.get("KEY"), Some(&"stdin".to_string()));
+    }
+
+    #[test]
+    fn test_stdin_source_empty_input() {
+        let mut resolver = EnvironmentResolver::new().with_stdin_reader(&b""[..]);
+        resolver.add_source(VariableSource::Stdin);
+
+        let resolved = resolver.resolve().unwrap();
+        assert!(resolved.is_empty());
+    }
+
+    #[test]
+    fn test_leave_unexpanded_keeps_undefined_references() {
+        let mut resolver = EnvironmentResolver::new();
+
+        let mut variables = IndexMap::new();
+        variables.insert("BASE".to_string(), "base".to_string());
+        variables.insert("URL".to_string(), "${BASE}/${MISSING}/${BASE}".to_string());
+        resolver.add_source(VariableSource::Default(variables));
+
+        let resolved = resolver
+            .resolve_with_options(&ResolutionOptions {
+                undefined_variable_behavior: UndefinedVariableBehavior::LeaveUnexpanded,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            resolved.get("URL"),
+            Some(&"base/${MISSING}/base".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dollar_escapes() {
+        let mut resolver = EnvironmentResolver::new();
+
+        let mut variables = IndexMap::new();
+        variables.insert("BASE".to_string(), "base".to_string());
+        variables.insert("TEMPLATE".to_string(), "$${BASE}".to_string());
+        variables.insert("PRICE".to_string(), "$$5".to_string());
+        variables.insert(
+            "MIXED".to_string(),
+            "${BASE}/$${BASE}/$$/${TEMPLATE}".to_string(),
+        );
+        resolver.add_source(VariableSource::Default(variables));
+
+        let resolved = resolver.resolve().unwrap();
+        assert_eq!(resolved.get("TEMPLATE"), Some(&"${BASE}".to_string()));
+        assert_eq!(resolved.get("PRICE"), Some(&"$5".to_string()));
+        assert_eq!(
+            resolved.get("MIXED"),
+            Some(&"base/${BASE}/$/${BASE}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_expansion_of_many_interdependent_variables() {
+        let mut resolver = EnvironmentResolver::new();
+
+        // Every level references the previous one twice, so expanding each
+        // reference afresh would take 2^300 steps
+        let mut variables = IndexMap::new();
+        variables.insert("LEVEL_0".to_string(), String::new());
+        for i in 1..=300 {
+            variables.insert(
+                format!("LEVEL_{}", i),
+                format!("${{LEVEL_{}}}${{LEVEL_{}}}", i - 1, i - 1),
+            );
+        }
+        // A long chain whose values grow with every link
+        variables.insert("CHAIN_0".to_string(), "x".to_string());
+        for i in 1..500 {
+            variables.insert(format!("CHAIN_{}", i), format!("${{CHAIN_{}}}x", i - 1));
+        }
+        resolver.add_source(VariableSource::Default(variables));
+
+        let resolved = resolver.resolve().unwrap();
+        assert_eq!(resolved.get("LEVEL_300"), Some(&String::new()));
+        assert_eq!(resolved.get("CHAIN_499").map(String::len), Some(500));
+    }
+
+    #[test]
+    fn test_circular_reference_reports_cycle() {
+        let mut resolver = EnvironmentResolver::new();
+
+        let mut variables = IndexMap::new();
+        variables.insert("OK".to_string(), "fine".to_string());
+        variables.insert("A".to_string(), "${OK}${B}".to_string());
+        variables.insert("B".to_string(), "${C}".to_string());
+        variables.insert("C".to_string(), "${A}".to_string());
+        resolver.add_source(VariableSource::Default(variables));
+
+        match resolver.resolve().unwrap_err() {
+            ResolveError::CircularReference { cycle } => {
+                assert_eq!(cycle, vec!["B", "C", "A", "B"]);
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_find_reference_cycle() {
+        let variables: IndexMap<String, String> = [
+            ("URL", "http://${HOST}"),
+            ("HOST", "${HOME}/${ALIAS}"),
+            ("ALIAS", "${HOST}"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        assert_eq!(
+            EnvironmentResolver::find_reference_cycle(&variables),
+            Some(vec![
+                "HOST".to_string(),
+                "ALIAS".to_string(),
+                "HOST".to_string()
+            ])
+        );
+
+        // References to undefined names (e.g. process variables) are not cycles
+        let acyclic: IndexMap<String, String> = [
+            ("A", "${B}"),
+            ("B", "${HOME}$${A}"),
+            ("PATH", "/opt/bin:${PATH}"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(EnvironmentResolver::find_reference_cycle(&acyclic), None);
+    }
+
+    #[test]
+    fn test_unterminated_reference_is_kept() {
+        let mut resolver = EnvironmentResolver::new();
+
+        let mut variables = IndexMap::new();
+        variables.insert("BASE".to_string(), "base".to_string());
+        variables.insert("URL".to_string(), "${BASE}/${OPEN/$$".to_string());
+        resolver.add_source(VariableSource::Default(variables));
+
+        let resolved = resolver.resolve().unwrap();
+        assert_eq!(resolved.get("URL"), Some(&"base/${OPEN/$$".to_string()));
+    }
 }
@@ -1,9 +1,28 @@
+use anyhow::Context;
 use clap::Parser;
-use stand::cli::commands::{Cli, Commands, EncryptCommands};
-use stand::commands::{current, encrypt, env, exec, get, init, list, set, shell, show, validate};
+use stand::cli::commands::{Cli, Commands, ConfigCommands, EncryptCommands, KeysCommands};
+use stand::commands::{
+    check, complete, config, copy, current, deps, doctor, encrypt, env, exec, export, get, history,
+    import, init, keys, list, projects, rename, set, shell, show, switch, unset, validate, watch,
+    CommandContext,
+};
+use stand::config::selection;
+use stand::utils::colors::{success_mark, warning_mark};
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    let ctx = CommandContext::new(cli.quiet);
+    stand::utils::log::set_verbosity(cli.verbose);
+    // `init --env` is repeatable; every other command selects a single environment
+    if cli.env.len() > 1 && !matches!(cli.command, Commands::Init { .. }) {
+        eprintln!("Error: --env can only be given once");
+        std::process::exit(1);
+    }
+    let env_flag = cli.env.last().cloned();
+    let no_color = cli.no_color;
+    if no_color {
+        stand::utils::colors::set_no_color(true);
+    }
 
     match cli.command {
         Commands::Init {
@@ -11,11 +30,11 @@ fn main() -> anyhow::Result<()> {
             encrypt: enable_encrypt,
         } => {
             let current_dir = std::env::current_dir()?;
-            init::handle_init(&current_dir, force)?;
+            init::handle_init_with_environments(&current_dir, force, &cli.env, &ctx)?;
 
             // If --encrypt flag is set, also enable encryption
             if enable_encrypt {
-                if let Err(e) = encrypt::enable_encryption(&current_dir) {
+                if let Err(e) = encrypt::enable_encryption_with_context(&current_dir, &ctx) {
                     eprintln!("Error: Failed to enable encryption: {}", e);
                     std::process::exit(1);
                 }
@@ -25,13 +44,29 @@ fn main() -> anyhow::Result<()> {
             environment,
             yes,
             shell: shell_override,
+            command,
+            env_files,
+            no_decrypt,
         } => {
             let current_dir = std::env::current_dir()?;
+            let environment = resolve_environment(
+                &current_dir,
+                environment.as_deref(),
+                env_flag.as_deref(),
+                &ctx,
+            );
+            let options = shell::ShellOptions {
+                skip_confirmation: yes,
+                shell_override,
+                env_files,
+                no_decrypt,
+            };
             match shell::start_shell_with_environment(
                 &current_dir,
                 &environment,
-                yes,
-                shell_override,
+                &options,
+                &ctx,
+                command.as_deref(),
             ) {
                 Ok(exit_code) => {
                     std::process::exit(exit_code);
@@ -45,10 +80,93 @@ fn main() -> anyhow::Result<()> {
         Commands::Exec {
             environment,
             yes,
+            isolated,
+            stdin_env,
+            cwd,
+            chdir_to_root,
+            timeout,
+            capture,
+            print_env_to,
+            no_decrypt,
+            shell: use_shell,
+            retry,
+            retry_delay,
+            no_inherit,
+            strict,
+            command,
+        } => {
+            let current_dir = std::env::current_dir()?;
+            let current_dir = if chdir_to_root {
+                match stand::utils::find_project_root_from(&current_dir) {
+                    Ok(root) => root,
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                current_dir
+            };
+            let environment = match_environment(&current_dir, &environment, &ctx);
+            let options = exec::ExecOptions {
+                skip_confirmation: yes,
+                isolated,
+                stdin_env,
+                cwd,
+                timeout: timeout.map(std::time::Duration::from_secs),
+                print_env_to,
+                no_decrypt,
+                use_shell,
+                retry,
+                retry_delay: std::time::Duration::from_secs(retry_delay),
+                no_inherit,
+                chdir_to_root,
+                strict,
+            };
+            if capture {
+                match exec::execute_captured_with_options(
+                    &current_dir,
+                    &environment,
+                    command,
+                    &options,
+                ) {
+                    Ok(output) => {
+                        println!("{}", serde_json::to_string(&output)?);
+                        std::process::exit(output.exit_code);
+                    }
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            match exec::execute_with_options(&current_dir, &environment, command, &options) {
+                Ok(exit_code) => {
+                    std::process::exit(exit_code);
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Watch {
+            environment,
+            yes,
+            isolated,
+            shell: use_shell,
             command,
         } => {
             let current_dir = std::env::current_dir()?;
-            match exec::execute_with_environment(&current_dir, &environment, command, yes) {
+            let environment = match_environment(&current_dir, &environment, &ctx);
+            let options = watch::WatchOptions {
+                skip_confirmation: yes,
+                isolated,
+                use_shell,
+                ..watch::WatchOptions::default()
+            };
+            let stop = watch::stop_on_signals()?;
+            match watch::watch_with_options(&current_dir, &environment, command, &options, &stop) {
                 Ok(exit_code) => {
                     std::process::exit(exit_code);
                 }
@@ -58,9 +176,42 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
-        Commands::List => {
+        Commands::Switch { environment, yes } => {
+            let current_dir = std::env::current_dir()?;
+            let environment = match_environment(&current_dir, &environment, &ctx);
+            match switch::switch_environment(&current_dir, &environment, yes) {
+                Ok(()) => {
+                    if !ctx.is_quiet() {
+                        println!("Switched to environment '{}'", environment);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::History { limit } => {
+            let current_dir = std::env::current_dir()?;
+            match history::show_history(&current_dir, limit) {
+                Ok(output) => {
+                    println!("{}", output);
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::List { tree, filter, all } => {
             let current_dir = std::env::current_dir()?;
-            match list::list_environments(&current_dir) {
+            let options = list::ListOptions { filter, all };
+            let result = if tree {
+                list::list_environment_tree_with_options(&current_dir, &options)
+            } else {
+                list::list_environments_with_options(&current_dir, &options)
+            };
+            match result {
                 Ok(output) => {
                     println!("{}", output);
                 }
@@ -70,12 +221,55 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
+        Commands::Projects { max_depth } => {
+            let current_dir = std::env::current_dir()?;
+            match projects::list_projects(&current_dir, max_depth) {
+                Ok(output) => {
+                    print!("{}", output);
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Deps { environment } => {
+            let current_dir = std::env::current_dir()?;
+            let environment = environment.map(|name| match_environment(&current_dir, &name, &ctx));
+            match deps::list_dependencies(&current_dir, environment.as_deref()) {
+                Ok(output) => {
+                    print!("{}", output);
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
         Commands::Inspect {
             environment,
             values,
+            mask,
+            resolve_system,
+            values_from,
+            no_inherit,
         } => {
             let current_dir = std::env::current_dir()?;
-            match show::show_environment(&current_dir, &environment, values) {
+            let environment = resolve_environment(
+                &current_dir,
+                environment.as_deref(),
+                env_flag.as_deref(),
+                &ctx,
+            );
+            let options = show::ShowOptions {
+                show_values: values,
+                mask,
+                no_color,
+                resolve_system,
+                values_from,
+                no_inherit,
+            };
+            match show::show_environment_with_options(&current_dir, &environment, &options) {
                 Ok(output) => {
                     println!("{}", output);
                 }
@@ -89,22 +283,217 @@ fn main() -> anyhow::Result<()> {
             environment,
             key,
             value,
+            common,
+            encrypt: should_encrypt,
+            from_stdin,
+            append,
+            prepend,
+            separator,
+        } => {
+            let current_dir = std::env::current_dir()?;
+            if separator.is_some() && !append && !prepend {
+                eprintln!("Error: --separator requires --append or --prepend");
+                std::process::exit(1);
+            }
+            let separator = separator.unwrap_or_else(|| set::DEFAULT_SEPARATOR.to_string());
+            let options = set::SetOptions {
+                encrypt: should_encrypt,
+                mode: if append {
+                    set::SetMode::Append(separator)
+                } else if prepend {
+                    set::SetMode::Prepend(separator)
+                } else {
+                    set::SetMode::Replace
+                },
+            };
+            let result = if common {
+                // With --common the positionals are <KEY> [VALUE]
+                let (key, value) = (environment, key);
+                if from_stdin && value.is_some() {
+                    eprintln!("Error: a value cannot be combined with --from-stdin");
+                    std::process::exit(1);
+                }
+                let value = if from_stdin {
+                    Some(std::io::read_to_string(std::io::stdin())?)
+                } else {
+                    value
+                };
+                set::set_common_variable_with_options(&current_dir, &key, value, &options)
+            } else {
+                let key = key.expect("required without --common");
+                let environment = match_environment(&current_dir, &environment, &ctx);
+                let value = if from_stdin {
+                    Some(std::io::read_to_string(std::io::stdin())?)
+                } else {
+                    value
+                };
+                set::set_variable_with_options(&current_dir, &environment, &key, value, &options)
+            };
+            match result {
+                Ok(message) => {
+                    if !ctx.is_quiet() {
+                        println!("{} {}", success_mark(), message);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Unset {
+            environment,
+            key,
+            all: _,
+            yes,
+        } => {
+            let current_dir = std::env::current_dir()?;
+            let environment = match_environment(&current_dir, &environment, &ctx);
+            let result = match key {
+                Some(key) => unset::unset_variable(&current_dir, &environment, &key)
+                    .map(|()| format!("Removed {} from [environments.{}]", key, environment)),
+                None => unset::unset_all_variables(&current_dir, &environment, yes).map(|keys| {
+                    format!(
+                        "Removed {} variable(s) from [environments.{}]",
+                        keys.len(),
+                        environment
+                    )
+                }),
+            };
+            match result {
+                Ok(message) => {
+                    if !ctx.is_quiet() {
+                        println!("{} {}", success_mark(), message);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Rename { old, new } => {
+            let current_dir = std::env::current_dir()?;
+            let old = match_environment(&current_dir, &old, &ctx);
+            match rename::rename_environment(&current_dir, &old, &new) {
+                Ok(summary) => {
+                    if !ctx.is_quiet() {
+                        println!(
+                            "{} Renamed environment '{}' to '{}'",
+                            success_mark(),
+                            old,
+                            new
+                        );
+                        if !summary.updated_extends.is_empty() {
+                            println!(
+                                "{} Updated extends in: {}",
+                                success_mark(),
+                                summary.updated_extends.join(", ")
+                            );
+                        }
+                        if summary.default_environment_updated {
+                            println!("{} Updated settings.default_environment", success_mark());
+                        }
+                        if summary.active_environment_updated {
+                            println!("{} Updated the active environment", success_mark());
+                        }
+                        if !summary.updated_aliases.is_empty() {
+                            println!(
+                                "{} Updated aliases: {}",
+                                success_mark(),
+                                summary.updated_aliases.join(", ")
+                            );
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Copy { src, dst, extend } => {
+            let current_dir = std::env::current_dir()?;
+            let src = match_environment(&current_dir, &src, &ctx);
+            match copy::copy_environment(&current_dir, &src, &dst, extend) {
+                Ok(()) => {
+                    if !ctx.is_quiet() {
+                        println!(
+                            "{} Created environment '{}' from '{}'",
+                            success_mark(),
+                            dst,
+                            src
+                        );
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Import {
+            environment,
+            file,
             encrypt: should_encrypt,
+            overwrite,
         } => {
             let current_dir = std::env::current_dir()?;
-            match set::set_variable(&current_dir, &environment, &key, value, should_encrypt) {
-                Ok(()) => {}
+            let environment = match_environment(&current_dir, &environment, &ctx);
+            match import::import_env_file(
+                &current_dir,
+                &environment,
+                &file,
+                should_encrypt,
+                overwrite,
+            ) {
+                Ok(summary) => {
+                    for key in &summary.skipped {
+                        eprintln!(
+                            "{} Skipped {}: already set in [environments.{}] (use --overwrite to replace)",
+                            warning_mark(),
+                            key,
+                            environment
+                        );
+                    }
+                    if !ctx.is_quiet() {
+                        println!(
+                            "{} Imported {} variable(s) into [environments.{}]{}",
+                            success_mark(),
+                            summary.imported.len() + summary.overwritten.len(),
+                            environment,
+                            if summary.overwritten.is_empty() {
+                                String::new()
+                            } else {
+                                format!(" ({} overwritten)", summary.overwritten.len())
+                            }
+                        );
+                    }
+                }
                 Err(e) => {
                     eprintln!("Error: {}", e);
                     std::process::exit(1);
                 }
             }
         }
-        Commands::Get { environment, key } => {
+        Commands::Export {
+            environment,
+            file,
+            force,
+        } => {
             let current_dir = std::env::current_dir()?;
-            match get::get_variable(&current_dir, &environment, &key) {
-                Ok(value) => {
-                    println!("{}", value);
+            let environment = match_environment(&current_dir, &environment, &ctx);
+            match export::export_to_file(&current_dir, &environment, &file, force) {
+                Ok(count) => {
+                    if !ctx.is_quiet() {
+                        println!(
+                            "{} Exported {} variable(s) from '{}' to {}",
+                            success_mark(),
+                            count,
+                            environment,
+                            file.display()
+                        );
+                    }
                 }
                 Err(e) => {
                     eprintln!("Error: {}", e);
@@ -112,44 +501,250 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
+        Commands::Get {
+            environment,
+            key,
+            raw,
+            all,
+            mask,
+        } => {
+            let current_dir = std::env::current_dir()?;
+            let options = get::GetOptions { raw, mask };
+            if all {
+                let environment = resolve_environment(
+                    &current_dir,
+                    environment.as_deref(),
+                    env_flag.as_deref(),
+                    &ctx,
+                );
+                match get::get_all_variables(&current_dir, &environment, &options) {
+                    Ok(output) => print!("{}", output),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                // Without --all the first argument is required by clap
+                let environment = environment.unwrap_or_default();
+                // With a single argument it is the key and the environment is resolved
+                let (environment, key) = match key {
+                    Some(key) => (match_environment(&current_dir, &environment, &ctx), key),
+                    None => (
+                        resolve_environment(&current_dir, None, env_flag.as_deref(), &ctx),
+                        environment,
+                    ),
+                };
+                match get::get_variable_with_options(&current_dir, &environment, &key, &options) {
+                    Ok(value) => {
+                        println!("{}", value);
+                    }
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
         Commands::Encrypt(subcmd) => {
             let current_dir = std::env::current_dir()?;
             match subcmd {
-                EncryptCommands::Enable => {
-                    if let Err(e) = encrypt::enable_encryption(&current_dir) {
+                EncryptCommands::Enable { recipients_file } => {
+                    if let Err(e) = encrypt::enable_encryption_with_recipients(
+                        &current_dir,
+                        &ctx,
+                        recipients_file.as_deref(),
+                    ) {
                         eprintln!("Error: {}", e);
                         std::process::exit(1);
                     }
                 }
                 EncryptCommands::Disable => {
-                    if let Err(e) = encrypt::disable_encryption(&current_dir) {
+                    if let Err(e) = encrypt::disable_encryption_with_context(&current_dir, &ctx) {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+        Commands::Keys(subcmd) => {
+            let current_dir = std::env::current_dir()?;
+            match subcmd {
+                KeysCommands::Show => match keys::show_public_key(&current_dir) {
+                    Ok(info) => print!("{}", info.format()),
+                    Err(e) => {
                         eprintln!("Error: {}", e);
                         std::process::exit(1);
                     }
+                },
+                KeysCommands::Export { yes } => match keys::export_private_key(&current_dir, yes) {
+                    Ok(private_key) => println!("{}", private_key),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                },
+                KeysCommands::Import { file, force } => {
+                    let content = match file {
+                        Some(path) if path.as_os_str() != "-" => std::fs::read_to_string(&path)
+                            .with_context(|| format!("Failed to read {}", path.display()))?,
+                        _ => std::io::read_to_string(std::io::stdin())?,
+                    };
+                    match keys::import_private_key(&current_dir, &content, force) {
+                        Ok(()) => {
+                            if !ctx.is_quiet() {
+                                println!("{} Wrote .stand.keys", success_mark());
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+        }
+        Commands::Config(subcmd) => {
+            let current_dir = std::env::current_dir()?;
+            let result = match subcmd {
+                ConfigCommands::Path => {
+                    config::config_path(&current_dir).map(|path| format!("{}\n", path.display()))
+                }
+                ConfigCommands::Show => config::show_config(&current_dir),
+            };
+            match result {
+                Ok(output) => print!("{}", output),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Validate { all, json } => {
+            validate::handle_validate(validate::ValidateOptions { all, json })?;
+        }
+        Commands::Check { environment, all } => {
+            let current_dir = std::env::current_dir()?;
+            let result = if all {
+                check::check_all_environments(&current_dir)
+            } else {
+                let environment =
+                    match_environment(&current_dir, &environment.unwrap_or_default(), &ctx);
+                check::check_environment(&current_dir, &environment).map(|report| vec![report])
+            };
+            match result {
+                Ok(reports) => {
+                    if all {
+                        print!("{}", check::format_summary(&reports));
+                    }
+                    for report in reports.iter().filter(|r| !all || !r.is_ok()) {
+                        print!("{}", report.format());
+                    }
+                    if reports.iter().any(|r| !r.is_ok()) {
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
                 }
             }
         }
-        Commands::Validate => {
-            validate::handle_validate()?;
+        Commands::Doctor => {
+            let current_dir = std::env::current_dir()?;
+            let report = doctor::run_doctor(&current_dir);
+            print!("{}", report.format());
+            if report.has_failures() {
+                std::process::exit(1);
+            }
         }
-        Commands::Current => {
-            current::handle_current()?;
+        Commands::Current { print_path } => {
+            if print_path {
+                let current_dir = std::env::current_dir()?;
+                match current::format_project_path(&current_dir) {
+                    Ok(output) => print!("{}", output),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                current::handle_current()?;
+            }
         }
         Commands::Env {
+            format,
             json,
+            export,
             stand_only,
             user_only,
+            diff_system,
         } => {
             let current_dir = std::env::current_dir()?;
+            let format = match (format, json, export) {
+                (Some(format), _, _) => format,
+                (None, true, _) => env::EnvFormat::Json,
+                (None, _, true) => env::EnvFormat::Export,
+                (None, false, false) => env::EnvFormat::Plain,
+            };
             let options = env::EnvOptions {
-                json,
+                format,
                 stand_only,
                 user_only,
+                diff_system,
             };
             let output = env::show_env(&current_dir, options)?;
             print!("{}", output);
         }
+        Commands::Complete { current } => {
+            let current_dir = std::env::current_dir()?;
+            for name in complete::complete_environments(&current_dir, &current) {
+                println!("{}", name);
+            }
+        }
     }
 
     Ok(())
 }
+
+/// Resolve the target environment, exiting with an error if none can be determined
+fn resolve_environment(
+    project_path: &std::path::Path,
+    explicit: Option<&str>,
+    env_flag: Option<&str>,
+    ctx: &CommandContext,
+) -> String {
+    match selection::select_environment(project_path, explicit, env_flag) {
+        Ok(name) => {
+            if let Some(requested) = explicit.or(env_flag) {
+                report_matched_environment(requested, &name, ctx);
+            }
+            name
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Resolve a user-supplied environment name (`settings.fuzzy_env_match`), exiting on ambiguity
+fn match_environment(project_path: &std::path::Path, name: &str, ctx: &CommandContext) -> String {
+    match selection::match_environment(project_path, name) {
+        Ok(resolved) => {
+            report_matched_environment(name, &resolved, ctx);
+            resolved
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Tell the user which environment a fuzzy name resolved to
+fn report_matched_environment(requested: &str, resolved: &str, ctx: &CommandContext) {
+    if requested != resolved && !ctx.is_quiet() {
+        eprintln!("Using environment '{}' (matched '{}')", resolved, requested);
+    }
+}
@@ -1,17 +1,104 @@
 // executor.rs module
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::process::Command;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(unix)]
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[cfg(unix)]
 use std::os::unix::process::ExitStatusExt;
 
+/// Exit code reported when a command is killed for exceeding its timeout (same as `timeout(1)`)
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Interval between checks of a running child when a timeout is set
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Signals relayed to the child when signal forwarding is enabled
+#[cfg(unix)]
+pub const FORWARDED_SIGNALS: &[i32] = &[signal_hook::consts::SIGTERM, signal_hook::consts::SIGHUP];
+
+/// Parent variables still passed to the child when the environment is cleared
+pub const ISOLATED_ENV_ALLOWLIST: &[&str] = &["PATH", "HOME", "USER", "LANG", "TERM", "TMPDIR"];
+
+/// How an executed command finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecOutcome {
+    /// The process exited normally with the given exit code
+    Exited(i32),
+    /// The process was terminated by the given signal (Unix only)
+    Signaled(i32),
+    /// The process was killed because it exceeded the configured timeout
+    Timeout,
+}
+
+impl ExecOutcome {
+    /// Shell-style exit code for this outcome
+    ///
+    /// - `Exited(code)` returns `code`
+    /// - `Signaled(signal)` returns 128 + signal number (POSIX convention)
+    /// - `Timeout` returns [`TIMEOUT_EXIT_CODE`]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ExecOutcome::Exited(code) => *code,
+            ExecOutcome::Signaled(signal) => 128 + signal,
+            ExecOutcome::Timeout => TIMEOUT_EXIT_CODE,
+        }
+    }
+
+    /// Returns true if the process exited normally with code 0
+    pub fn success(&self) -> bool {
+        matches!(self, ExecOutcome::Exited(0))
+    }
+
+    /// Classify the exit status of a process that was not killed for a timeout
+    pub fn from_status(status: ExitStatus) -> Self {
+        if let Some(code) = status.code() {
+            return ExecOutcome::Exited(code);
+        }
+
+        // Process was terminated by a signal (Unix only)
+        #[cfg(unix)]
+        {
+            if let Some(signal) = status.signal() {
+                return ExecOutcome::Signaled(signal);
+            }
+        }
+
+        // Fallback for non-Unix or unknown termination
+        ExecOutcome::Exited(1)
+    }
+}
+
+/// Output of a command run with [`CommandExecutor::execute_captured`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CapturedOutput {
+    /// Exit code as returned by [`ExecOutcome::exit_code`]
+    pub exit_code: i32,
+    /// Everything written to stdout (invalid UTF-8 is replaced)
+    pub stdout: String,
+    /// Everything written to stderr (invalid UTF-8 is replaced)
+    pub stderr: String,
+}
+
 /// Executes commands with environment variables
+#[derive(Clone)]
 pub struct CommandExecutor {
     command: String,
     args: Vec<String>,
     env_vars: HashMap<String, String>,
+    timeout: Option<Duration>,
+    clear_env: bool,
+    current_dir: Option<PathBuf>,
+    forward_signals: bool,
 }
 
 impl CommandExecutor {
@@ -21,6 +108,10 @@ impl CommandExecutor {
             command,
             args,
             env_vars: HashMap::new(),
+            timeout: None,
+            clear_env: false,
+            current_dir: None,
+            forward_signals: false,
         }
     }
 
@@ -30,50 +121,298 @@ impl CommandExecutor {
         self
     }
 
+    /// Start the command with a cleared environment
+    ///
+    /// Only the variables in [`ISOLATED_ENV_ALLOWLIST`] are copied from the parent
+    /// process; everything else comes from [`CommandExecutor::with_env`].
+    pub fn with_clear_env(mut self, clear_env: bool) -> Self {
+        self.clear_env = clear_env;
+        self
+    }
+
+    /// Run the command in `dir` instead of the current directory
+    ///
+    /// The directory is checked when the command is executed.
+    pub fn with_current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// Relay SIGTERM and SIGHUP received by Stand to the child (Unix only)
+    ///
+    /// While the child runs, these signals no longer terminate Stand itself; Stand waits
+    /// for the child and reports its exit status instead. Signals are sent to the child
+    /// process only, because moving it to its own process group would take it out of the
+    /// terminal's foreground group. Ignored on other platforms.
+    pub fn with_signal_forwarding(mut self, forward_signals: bool) -> Self {
+        self.forward_signals = forward_signals;
+        self
+    }
+
+    /// Kill the command if it is still running after `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Execute the command and return the exit code
     ///
     /// # Returns
     /// - `Ok(i32)` - The exit code of the executed command
     ///   - If the process terminates normally, returns its exit code
     ///   - If the process is terminated by a signal (Unix only), returns 128 + signal number
+    ///   - If the process is killed after the timeout, returns 124
     ///
     /// # Errors
     /// Returns an error if:
     /// - The command cannot be found or executed
+    /// - The working directory set with [`CommandExecutor::with_current_dir`] does not exist
+    ///   or is not a directory
     /// - I/O errors occur during execution
     pub fn execute(self) -> Result<i32> {
+        Ok(self.execute_detailed()?.exit_code())
+    }
+
+    /// Execute the command and report how it finished
+    ///
+    /// Use this instead of [`CommandExecutor::execute`] when the caller needs to tell a
+    /// normal non-zero exit apart from signal termination or a timeout.
+    ///
+    /// # Errors
+    /// Same as [`CommandExecutor::execute`].
+    pub fn execute_detailed(self) -> Result<ExecOutcome> {
+        let mut cmd = self.build_command()?;
+
+        if self.timeout.is_none() && !self.forward_signals {
+            return Ok(ExecOutcome::from_status(cmd.status()?));
+        }
+
+        self.spawn_and_wait(&mut cmd, |cmd| Ok((cmd.spawn()?, ())))
+            .map(|(outcome, ())| outcome)
+    }
+
+    /// Execute the command and return its exit code, stdout and stderr
+    ///
+    /// Both streams are read to the end while the command runs, so large outputs do
+    /// not block the child. Stdin is not inherited. Timeouts and signal forwarding
+    /// behave as in [`CommandExecutor::execute_detailed`].
+    ///
+    /// # Errors
+    /// Same as [`CommandExecutor::execute`].
+    pub fn execute_captured(self) -> Result<CapturedOutput> {
+        let mut cmd = self.build_command()?;
+
+        if self.timeout.is_none() && !self.forward_signals {
+            let output = cmd.output()?;
+            return Ok(CapturedOutput {
+                exit_code: ExecOutcome::from_status(output.status).exit_code(),
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let (outcome, (stdout, stderr)) = self.spawn_and_wait(&mut cmd, |cmd| {
+            let mut child = cmd.spawn()?;
+            let stdout = child.stdout.take().map(read_to_end_in_background);
+            let stderr = child.stderr.take().map(read_to_end_in_background);
+            Ok((child, (stdout, stderr)))
+        })?;
+
+        let collect = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+            reader
+                .and_then(|handle| handle.join().ok())
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default()
+        };
+        Ok(CapturedOutput {
+            exit_code: outcome.exit_code(),
+            stdout: collect(stdout),
+            stderr: collect(stderr),
+        })
+    }
+
+    /// Start the command without waiting for it
+    ///
+    /// For callers that manage the child themselves, such as `stand watch`, which
+    /// kills and restarts it. The timeout and signal forwarding do not apply.
+    ///
+    /// # Errors
+    /// Same as [`CommandExecutor::execute`].
+    pub fn spawn(&self) -> Result<Child> {
+        Ok(self.build_command()?.spawn()?)
+    }
+
+    /// Build the `Command` with arguments, working directory and environment applied
+    fn build_command(&self) -> Result<Command> {
         let mut cmd = Command::new(&self.command);
         cmd.args(&self.args);
 
+        if let Some(dir) = &self.current_dir {
+            if !dir.exists() {
+                return Err(anyhow!(
+                    "Working directory '{}' does not exist",
+                    dir.display()
+                ));
+            }
+            if !dir.is_dir() {
+                return Err(anyhow!(
+                    "Working directory '{}' is not a directory",
+                    dir.display()
+                ));
+            }
+            cmd.current_dir(dir);
+        }
+
+        if self.clear_env {
+            cmd.env_clear();
+            for key in ISOLATED_ENV_ALLOWLIST {
+                if let Some(value) = std::env::var_os(key) {
+                    cmd.env(key, value);
+                }
+            }
+        }
+
         // Add environment variables
         for (key, value) in &self.env_vars {
             cmd.env(key, value);
         }
 
-        let status = cmd.status()?;
-
-        // Return exit code, handling signal termination on Unix
-        match status.code() {
-            Some(code) => Ok(code),
-            None => {
-                // Process was terminated by a signal (Unix only)
-                #[cfg(unix)]
-                {
-                    if let Some(signal) = status.signal() {
-                        // POSIX convention: 128 + signal number
-                        return Ok(128 + signal);
-                    }
+        Ok(cmd)
+    }
+
+    /// Spawn the child with `spawn`, forward signals and wait for it, honoring the timeout
+    ///
+    /// `spawn` may return extra state (e.g. output readers) that is handed back with the outcome.
+    fn spawn_and_wait<T>(
+        &self,
+        cmd: &mut Command,
+        spawn: impl FnOnce(&mut Command) -> Result<(Child, T)>,
+    ) -> Result<(ExecOutcome, T)> {
+        // Register handlers before spawning so no signal slips through in between
+        #[cfg(unix)]
+        let signals = if self.forward_signals {
+            let default_action = default_action_flag()?;
+            default_action.store(false, Ordering::SeqCst);
+            let signals = signal_hook::iterator::Signals::new(FORWARDED_SIGNALS)?;
+            Some((signals, default_action))
+        } else {
+            None
+        };
+
+        let (mut child, extra) = spawn(cmd)?;
+
+        #[cfg(unix)]
+        let _forwarder = signals.map(|(signals, default_action)| {
+            SignalForwarder::start(signals, default_action, child.id())
+        });
+
+        let Some(timeout) = self.timeout else {
+            return Ok((ExecOutcome::from_status(child.wait()?), extra));
+        };
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok((ExecOutcome::from_status(status), extra));
+            }
+            if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                return Ok((ExecOutcome::Timeout, extra));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Read a child's output stream on its own thread so the child never blocks on a full pipe
+fn read_to_end_in_background(
+    mut stream: impl Read + Send + 'static,
+) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = stream.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+/// Flag that gives [`FORWARDED_SIGNALS`] their default action (terminate) while set
+///
+/// signal-hook keeps its handlers installed after a forwarder is closed, which would
+/// otherwise leave stand ignoring SIGTERM and SIGHUP until it exits. The flag is
+/// registered once and cleared while a forwarder is running.
+#[cfg(unix)]
+fn default_action_flag() -> Result<Arc<AtomicBool>> {
+    static FLAG: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+    let mut registered = FLAG.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(flag) = registered.as_ref() {
+        return Ok(Arc::clone(flag));
+    }
+
+    let flag = Arc::new(AtomicBool::new(true));
+    for &signal in FORWARDED_SIGNALS {
+        signal_hook::flag::register_conditional_default(signal, Arc::clone(&flag))?;
+    }
+    *registered = Some(Arc::clone(&flag));
+    Ok(flag)
+}
+
+/// Relays [`FORWARDED_SIGNALS`] to a child process until dropped
+///
+/// Dropping it restores the default action of the forwarded signals.
+#[cfg(unix)]
+struct SignalForwarder {
+    handle: signal_hook::iterator::Handle,
+    default_action: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+#[cfg(unix)]
+impl SignalForwarder {
+    fn start(
+        mut signals: signal_hook::iterator::Signals,
+        default_action: Arc<AtomicBool>,
+        child_pid: u32,
+    ) -> Self {
+        let handle = signals.handle();
+        let pid = child_pid as libc::pid_t;
+
+        let thread = thread::spawn(move || {
+            for signal in signals.forever() {
+                // SAFETY: kill(2) has no memory-safety requirements
+                unsafe {
+                    libc::kill(pid, signal);
                 }
-                // Fallback for non-Unix or unknown termination
-                Ok(1)
             }
+        });
+
+        Self {
+            handle,
+            default_action,
+            thread: Some(thread),
         }
     }
 }
 
+#[cfg(unix)]
+impl Drop for SignalForwarder {
+    fn drop(&mut self) {
+        self.handle.close();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        self.default_action.store(true, Ordering::SeqCst);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_execute_simple_command() {
@@ -129,6 +468,64 @@ mod tests {
         assert_eq!(exit_code, 0);
     }
 
+    #[test]
+    fn test_execute_captured_both_streams() {
+        let executor = CommandExecutor::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                "echo out; echo err >&2; exit 3".to_string(),
+            ],
+        );
+
+        let output = executor.execute_captured().unwrap();
+
+        assert_eq!(
+            output,
+            CapturedOutput {
+                exit_code: 3,
+                stdout: "out\n".to_string(),
+                stderr: "err\n".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_execute_captured_large_output_with_timeout() {
+        // Output larger than a pipe buffer on both streams must not block the child
+        let executor = CommandExecutor::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                "i=0; while [ $i -lt 20000 ]; do echo 0123456789; echo abcdefghij >&2; i=$((i+1)); done"
+                    .to_string(),
+            ],
+        )
+        .with_timeout(Duration::from_secs(30))
+        .with_signal_forwarding(true);
+
+        let output = executor.execute_captured().unwrap();
+
+        assert_eq!(output.exit_code, 0);
+        assert_eq!(output.stdout.len(), 20000 * 11);
+        assert_eq!(output.stderr.len(), 20000 * 11);
+    }
+
+    #[test]
+    fn test_execute_captured_timeout() {
+        let executor = CommandExecutor::new(
+            "sh".to_string(),
+            vec!["-c".to_string(), "echo started; exec sleep 5".to_string()],
+        )
+        .with_timeout(Duration::from_millis(200));
+
+        let output = executor.execute_captured().unwrap();
+
+        assert_eq!(output.exit_code, TIMEOUT_EXIT_CODE);
+        assert_eq!(output.stdout, "started\n");
+    }
+
     #[test]
     fn test_execute_with_multiple_arguments() {
         // Test command with multiple arguments
@@ -176,4 +573,147 @@ mod tests {
 
         assert_eq!(exit_code, 143); // 128 + SIGTERM(15)
     }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn test_signal_forwarding_restores_default_action_afterwards() {
+        let executor =
+            CommandExecutor::new("true".to_string(), vec![]).with_signal_forwarding(true);
+        executor.execute().unwrap();
+
+        // SIGTERM and SIGHUP terminate stand again once the child is gone
+        assert!(default_action_flag().unwrap().load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_execute_detailed_normal_exit() {
+        let executor = CommandExecutor::new(
+            "sh".to_string(),
+            vec!["-c".to_string(), "exit 3".to_string()],
+        );
+        let outcome = executor.execute_detailed().unwrap();
+
+        assert_eq!(outcome, ExecOutcome::Exited(3));
+        assert_eq!(outcome.exit_code(), 3);
+        assert!(!outcome.success());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_detailed_signal_termination() {
+        let executor = CommandExecutor::new(
+            "sh".to_string(),
+            vec!["-c".to_string(), "kill -15 $$".to_string()],
+        );
+        let outcome = executor.execute_detailed().unwrap();
+
+        assert_eq!(outcome, ExecOutcome::Signaled(15));
+        assert_eq!(outcome.exit_code(), 143);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_detailed_timeout() {
+        let executor = CommandExecutor::new("sleep".to_string(), vec!["5".to_string()])
+            .with_timeout(Duration::from_millis(100));
+
+        let started = Instant::now();
+        let outcome = executor.execute_detailed().unwrap();
+
+        assert_eq!(outcome, ExecOutcome::Timeout);
+        assert_eq!(outcome.exit_code(), TIMEOUT_EXIT_CODE);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_execute_detailed_finishes_before_timeout() {
+        let executor =
+            CommandExecutor::new("true".to_string(), vec![]).with_timeout(Duration::from_secs(5));
+
+        let outcome = executor.execute_detailed().unwrap();
+        assert!(outcome.success());
+    }
+
+    #[test]
+    #[serial]
+    fn test_execute_with_clear_env_hides_parent_variables() {
+        std::env::set_var("STAND_TEST_PARENT_ONLY", "leaked");
+
+        let mut env_vars = HashMap::new();
+        env_vars.insert("STAND_TEST_CHILD".to_string(), "visible".to_string());
+
+        let executor = CommandExecutor::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                "test -z \"$STAND_TEST_PARENT_ONLY\" && test \"$STAND_TEST_CHILD\" = visible"
+                    .to_string(),
+            ],
+        )
+        .with_env(env_vars)
+        .with_clear_env(true);
+
+        let exit_code = executor.execute().unwrap();
+        std::env::remove_var("STAND_TEST_PARENT_ONLY");
+
+        assert_eq!(exit_code, 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_execute_without_clear_env_inherits_parent_variables() {
+        std::env::set_var("STAND_TEST_PARENT_ONLY", "inherited");
+
+        let executor = CommandExecutor::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                "test \"$STAND_TEST_PARENT_ONLY\" = inherited".to_string(),
+            ],
+        );
+
+        let exit_code = executor.execute().unwrap();
+        std::env::remove_var("STAND_TEST_PARENT_ONLY");
+
+        assert_eq!(exit_code, 0);
+    }
+
+    #[test]
+    fn test_execute_in_current_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let executor = CommandExecutor::new(
+            "sh".to_string(),
+            vec!["-c".to_string(), "pwd > pwd.txt".to_string()],
+        )
+        .with_current_dir(dir.path());
+
+        assert_eq!(executor.execute().unwrap(), 0);
+
+        let printed = std::fs::read_to_string(dir.path().join("pwd.txt")).unwrap();
+        assert_eq!(
+            std::fs::canonicalize(printed.trim()).unwrap(),
+            std::fs::canonicalize(dir.path()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_execute_rejects_invalid_current_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file.txt");
+        std::fs::write(&file, "").unwrap();
+
+        let missing = CommandExecutor::new("true".to_string(), vec![])
+            .with_current_dir(dir.path().join("missing"))
+            .execute();
+        let not_dir = CommandExecutor::new("true".to_string(), vec![])
+            .with_current_dir(&file)
+            .execute();
+
+        assert!(missing.unwrap_err().to_string().contains("does not exist"));
+        assert!(not_dir
+            .unwrap_err()
+            .to_string()
+            .contains("is not a directory"));
+    }
 }
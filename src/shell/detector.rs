@@ -4,7 +4,7 @@
 // we're already inside a Stand shell session.
 
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Represents the type of shell
 #[derive(Debug, Clone, PartialEq)]
@@ -39,6 +39,25 @@ pub fn detect_user_shell() -> String {
     env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string())
 }
 
+/// Returns true if `path` is a regular file that can be executed
+///
+/// On non-Unix platforms only checks that the file exists.
+pub fn is_executable_file(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    if !metadata.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    true
+}
+
 /// Get the shell type for the current user
 pub fn get_shell_type() -> ShellType {
     ShellType::from_path(&detect_user_shell())
@@ -32,19 +32,8 @@ pub fn get_prompt_env_vars(shell_type: &ShellType, env_name: &str) -> HashMap<St
 
     match shell_type {
         ShellType::Bash => {
-            // For bash, PROMPT_COMMAND runs before each prompt display.
-            // We capture the original PS1 on first run, then prepend our prefix with color.
-            // Uses $STAND_ENVIRONMENT and $STAND_ENV_COLOR for dynamic values.
-            // Color codes: bold=1, reverse=7, green=32, reset=0
-            // Note: Using tr for uppercase conversion for compatibility with Bash 3.x (macOS default)
-            //
-            // Directory guard (when STAND_AUTO_EXIT=1):
-            // Checks if current directory is still within STAND_PROJECT_ROOT.
-            // If outside, reverts to the previous directory and shows a warning.
-            // Uses logical paths ($PWD) instead of physical paths (pwd -P) to allow
-            // symlinks within the project to work as expected.
-            let prompt_command = r#"if [ -z "$_stand_prev_dir" ]; then _stand_prev_dir="$PWD"; fi; if [ -z "$STAND_ORIGINAL_PS1" ]; then export STAND_ORIGINAL_PS1="$PS1"; fi; if [ "$STAND_AUTO_EXIT" = "1" ] && [ -n "$STAND_PROJECT_ROOT" ]; then case "$PWD" in "$STAND_PROJECT_ROOT"|"$STAND_PROJECT_ROOT"/*) _stand_prev_dir="$PWD";; *) if ! cd "$_stand_prev_dir" 2>/dev/null; then if ! cd "$STAND_PROJECT_ROOT" 2>/dev/null; then echo "⚠️  Cannot return to project directory. Exiting Stand shell."; exit 1; fi; fi; echo "⚠️  Cannot leave project directory while in Stand shell."; echo "    Type 'exit' to leave the Stand shell first.";; esac; fi; _c="${STAND_ENV_COLOR:-green}"; case "$_c" in red) _cc=31;; green) _cc=32;; yellow) _cc=33;; blue) _cc=34;; magenta|purple) _cc=35;; cyan) _cc=36;; *) _cc=32;; esac; _env_upper=$(echo "$STAND_ENVIRONMENT" | tr '[:lower:]' '[:upper:]'); PS1=$'\n\e[1;7;'"$_cc"'m stand:'"$_env_upper"$' \e[0m'"$STAND_ORIGINAL_PS1""#;
-            vars.insert("PROMPT_COMMAND".to_string(), prompt_command.to_string());
+            // Bash prompt customization is installed by the rcfile the spawner writes
+            // (see `bash_prompt_command`), so nothing leaks into child shells.
         }
         ShellType::Zsh => {
             // Zsh: Set STAND_ZSH_PRECMD which will be evaled by the spawner's init command.
@@ -64,6 +53,22 @@ pub fn get_prompt_env_vars(shell_type: &ShellType, env_name: &str) -> HashMap<St
     vars
 }
 
+/// Body of the bash prompt hook, run before each prompt is displayed
+///
+/// Prepends the Stand indicator to `$STAND_ORIGINAL_PS1`, which the rcfile sets to the
+/// user's prompt. Uses $STAND_ENVIRONMENT and $STAND_ENV_COLOR for dynamic values.
+/// Color codes: bold=1, reverse=7, green=32, reset=0
+/// Note: Using tr for uppercase conversion for compatibility with Bash 3.x (macOS default)
+///
+/// Directory guard (when STAND_AUTO_EXIT=1):
+/// Checks if current directory is still within STAND_PROJECT_ROOT.
+/// If outside, reverts to the previous directory and shows a warning.
+/// Uses logical paths ($PWD) instead of physical paths (pwd -P) to allow
+/// symlinks within the project to work as expected.
+pub fn bash_prompt_command() -> &'static str {
+    r#"if [ -z "$_stand_prev_dir" ]; then _stand_prev_dir="$PWD"; fi; if [ -z "$STAND_ORIGINAL_PS1" ]; then STAND_ORIGINAL_PS1="$PS1"; fi; if [ "$STAND_AUTO_EXIT" = "1" ] && [ -n "$STAND_PROJECT_ROOT" ]; then case "$PWD" in "$STAND_PROJECT_ROOT"|"$STAND_PROJECT_ROOT"/*) _stand_prev_dir="$PWD";; *) if ! cd "$_stand_prev_dir" 2>/dev/null; then if ! cd "$STAND_PROJECT_ROOT" 2>/dev/null; then echo "⚠️  Cannot return to project directory. Exiting Stand shell."; exit 1; fi; fi; echo "⚠️  Cannot leave project directory while in Stand shell."; echo "    Type 'exit' to leave the Stand shell first.";; esac; fi; _c="${STAND_ENV_COLOR:-green}"; case "$_c" in red) _cc=31;; green) _cc=32;; yellow) _cc=33;; blue) _cc=34;; magenta|purple) _cc=35;; cyan) _cc=36;; *) _cc=32;; esac; _env_upper=$(echo "$STAND_ENVIRONMENT" | tr '[:lower:]' '[:upper:]'); PS1=$'\n\e[1;7;'"$_cc"'m stand:'"$_env_upper"$' \e[0m'"$STAND_ORIGINAL_PS1""#
+}
+
 /// Generate a colored prompt prefix with ANSI escape codes
 ///
 /// Uses green color for the environment name
@@ -100,12 +105,19 @@ mod tests {
     }
 
     #[test]
-    fn test_get_prompt_env_vars_bash_sets_prompt_command() {
+    fn test_get_prompt_env_vars_bash_leaves_prompt_command_to_rcfile() {
         let vars = get_prompt_env_vars(&ShellType::Bash, "dev");
-        assert!(vars.contains_key("PROMPT_COMMAND"));
-        // Should capture original PS1 before modifying
-        let prompt_cmd = vars.get("PROMPT_COMMAND").unwrap();
+        // An exported PROMPT_COMMAND would leak into nested non-Stand shells
+        assert!(!vars.contains_key("PROMPT_COMMAND"));
+        assert!(!vars.contains_key("PS1"));
+    }
+
+    #[test]
+    fn test_bash_prompt_command() {
+        let prompt_cmd = bash_prompt_command();
+        // Should prepend to the original PS1 captured by the rcfile
         assert!(prompt_cmd.contains("STAND_ORIGINAL_PS1"));
+        assert!(!prompt_cmd.contains("export"));
         // Uses $STAND_ENVIRONMENT variable instead of embedded name for safety
         assert!(prompt_cmd.contains("STAND_ENVIRONMENT"));
         // Uses $STAND_ENV_COLOR for color customization
@@ -113,9 +125,8 @@ mod tests {
     }
 
     #[test]
-    fn test_get_prompt_env_vars_bash_includes_directory_guard() {
-        let vars = get_prompt_env_vars(&ShellType::Bash, "dev");
-        let prompt_cmd = vars.get("PROMPT_COMMAND").unwrap();
+    fn test_bash_prompt_command_includes_directory_guard() {
+        let prompt_cmd = bash_prompt_command();
         // Should check STAND_AUTO_EXIT and STAND_PROJECT_ROOT
         assert!(prompt_cmd.contains("STAND_AUTO_EXIT"));
         assert!(prompt_cmd.contains("STAND_PROJECT_ROOT"));
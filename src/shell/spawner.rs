@@ -3,9 +3,10 @@
 // Handles spawning interactive shell sessions with environment variables.
 
 use crate::shell::detector::ShellType;
-use crate::shell::prompt::get_prompt_env_vars;
+use crate::shell::prompt::{bash_prompt_command, get_prompt_env_vars};
 use anyhow::Result;
 use std::collections::HashMap;
+use std::path::Path;
 use std::process::Command;
 
 #[cfg(unix)]
@@ -45,30 +46,48 @@ pub fn build_shell_environment(
     env
 }
 
-/// Spawn an interactive shell with the given environment variables
+/// Spawn a shell with the given environment variables
 ///
 /// # Arguments
 /// * `shell_path` - Path to the shell executable (e.g., "/bin/bash")
 /// * `env_vars` - Environment variables to inject into the shell
+/// * `command` - If given, run this command with `-c` and exit instead of starting
+///   an interactive session
+/// * `working_dir` - Directory the shell starts in; `None` inherits the current one
 ///
 /// # Returns
-/// The exit code of the shell process
-pub fn spawn_shell(shell_path: &str, env_vars: HashMap<String, String>) -> Result<i32> {
+/// The exit code of the shell process (the command's exit code with `command`)
+pub fn spawn_shell(
+    shell_path: &str,
+    env_vars: HashMap<String, String>,
+    command: Option<&str>,
+    working_dir: Option<&Path>,
+) -> Result<i32> {
     let shell_type = ShellType::from_path(shell_path);
 
     // Build shell arguments based on shell type
-    let args = get_shell_args(&shell_type);
+    let args = get_shell_args(&shell_type, command);
 
     let mut cmd = Command::new(shell_path);
+
+    // For interactive bash, start from a custom rcfile (long options go first)
+    let rcfile_cleanup = if matches!(shell_type, ShellType::Bash) && command.is_none() {
+        Some(setup_bash_rcfile(&mut cmd)?)
+    } else {
+        None
+    };
     cmd.args(&args);
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
 
     // Add environment variables
     for (key, value) in &env_vars {
         cmd.env(key, value);
     }
 
-    // For zsh, set up ZDOTDIR with custom .zshrc
-    let zdotdir_cleanup = if matches!(shell_type, ShellType::Zsh) {
+    // For interactive zsh, set up ZDOTDIR with custom .zshrc
+    let zdotdir_cleanup = if matches!(shell_type, ShellType::Zsh) && command.is_none() {
         setup_zsh_zdotdir(&mut cmd, &env_vars)?
     } else {
         None
@@ -76,8 +95,8 @@ pub fn spawn_shell(shell_path: &str, env_vars: HashMap<String, String>) -> Resul
 
     let status = cmd.status()?;
 
-    // Clean up ZDOTDIR if we created one
-    if let Some(path) = zdotdir_cleanup {
+    // Clean up ZDOTDIR or the rcfile directory if we created one
+    for path in zdotdir_cleanup.into_iter().chain(rcfile_cleanup) {
         let _ = std::fs::remove_dir_all(path);
     }
 
@@ -202,8 +221,59 @@ precmd_functions+=(_stand_precmd)
     Ok(Some(temp_dir))
 }
 
-/// Get appropriate shell arguments for interactive mode
-fn get_shell_args(shell_type: &ShellType) -> Vec<String> {
+/// Content of the rcfile an interactive Stand bash starts with
+///
+/// It sources the user's `~/.bashrc`, as bash does without `--rcfile`, then captures
+/// the resulting prompt and installs the Stand hook in `PROMPT_COMMAND`. The hook and
+/// the saved prompt are shell variables, not exported ones, so nested shells that are
+/// not Stand shells start with the user's own prompt.
+fn bash_rcfile_content() -> String {
+    format!(
+        r#"# Stand temporary bashrc
+# Source user's original .bashrc if it exists
+[ -f "$HOME/.bashrc" ] && source "$HOME/.bashrc"
+
+# Prompt from the user's configuration, without the Stand indicator
+STAND_ORIGINAL_PS1="$PS1"
+
+# Stand prompt function (prompt customization and directory guard)
+_stand_prompt_command() {{
+    {}
+}}
+
+# Run after any existing PROMPT_COMMAND; keep both out of child processes
+PROMPT_COMMAND="${{PROMPT_COMMAND:+$PROMPT_COMMAND; }}_stand_prompt_command"
+export -n PROMPT_COMMAND STAND_ORIGINAL_PS1
+"#,
+        bash_prompt_command()
+    )
+}
+
+/// Write the bash rcfile to a temporary directory and pass it with `--rcfile`
+///
+/// Returns the path to the temp directory for cleanup
+fn setup_bash_rcfile(cmd: &mut Command) -> Result<std::path::PathBuf> {
+    let temp_dir = std::env::temp_dir().join(format!("stand-bash-{}", std::process::id()));
+    std::fs::create_dir_all(&temp_dir)?;
+
+    let rcfile_path = temp_dir.join("bashrc");
+    std::fs::write(&rcfile_path, bash_rcfile_content())?;
+
+    cmd.arg("--rcfile").arg(&rcfile_path);
+    // A STAND_ORIGINAL_PS1 exported by an older Stand must not replace the prompt
+    cmd.env_remove("STAND_ORIGINAL_PS1");
+
+    Ok(temp_dir)
+}
+
+/// Get appropriate shell arguments for interactive mode, or for running `command`
+fn get_shell_args(shell_type: &ShellType, command: Option<&str>) -> Vec<String> {
+    if let Some(command) = command {
+        // bash, zsh, fish and POSIX shells all accept `-c <command>`. The prompt and
+        // directory guard hooks are skipped because the shell never shows a prompt.
+        return vec!["-c".to_string(), command.to_string()];
+    }
+
     match shell_type {
         ShellType::Fish => {
             // Fish uses functions for prompts, not environment variables.
@@ -307,20 +377,36 @@ mod tests {
 
     #[test]
     fn test_get_shell_args_bash() {
-        let args = get_shell_args(&ShellType::Bash);
+        let args = get_shell_args(&ShellType::Bash, None);
         assert_eq!(args, vec!["-i".to_string()]);
     }
 
+    #[test]
+    fn test_bash_rcfile_sources_bashrc_then_installs_prompt() {
+        let content = bash_rcfile_content();
+
+        let source = content
+            .find("source \"$HOME/.bashrc\"")
+            .expect("rcfile should source the user's bashrc");
+        let capture = content.find("STAND_ORIGINAL_PS1=\"$PS1\"").unwrap();
+        let hook = content.find("_stand_prompt_command\"").unwrap();
+        // The prompt is captured after the user's configuration has set it
+        assert!(source < capture && capture < hook);
+
+        assert!(content.contains(bash_prompt_command()));
+        assert!(content.contains("export -n PROMPT_COMMAND STAND_ORIGINAL_PS1"));
+    }
+
     #[test]
     fn test_get_shell_args_zsh() {
-        let args = get_shell_args(&ShellType::Zsh);
+        let args = get_shell_args(&ShellType::Zsh, None);
         // Zsh uses -i for interactive mode, prompt customization via RPS1 env var
         assert_eq!(args, vec!["-i".to_string()]);
     }
 
     #[test]
     fn test_get_shell_args_fish() {
-        let args = get_shell_args(&ShellType::Fish);
+        let args = get_shell_args(&ShellType::Fish, None);
         assert_eq!(args.len(), 2);
         assert_eq!(args[0], "-C");
         // The init command should wrap fish_prompt and use STAND_ENVIRONMENT
@@ -330,7 +416,51 @@ mod tests {
 
     #[test]
     fn test_get_shell_args_other() {
-        let args = get_shell_args(&ShellType::Other("sh".to_string()));
+        let args = get_shell_args(&ShellType::Other("sh".to_string()), None);
         assert_eq!(args, vec!["-i".to_string()]);
     }
+
+    #[test]
+    fn test_get_shell_args_with_command() {
+        let expected = vec!["-c".to_string(), "rails console".to_string()];
+        for shell_type in [
+            ShellType::Bash,
+            ShellType::Zsh,
+            ShellType::Fish,
+            ShellType::Other("sh".to_string()),
+        ] {
+            assert_eq!(get_shell_args(&shell_type, Some("rails console")), expected);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_shell_with_command_returns_exit_code() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("STAND_TEST_CODE".to_string(), "7".to_string());
+
+        let code = spawn_shell("/bin/sh", env_vars, Some("exit $STAND_TEST_CODE"), None).unwrap();
+
+        assert_eq!(code, 7);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_shell_starts_in_working_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let expected = dir.path().canonicalize().unwrap();
+        let mut env_vars = HashMap::new();
+        env_vars.insert(
+            "STAND_TEST_DIR".to_string(),
+            expected.to_string_lossy().into_owned(),
+        );
+        let command = Some("test \"$(pwd -P)\" = \"$STAND_TEST_DIR\"");
+
+        let code = spawn_shell("/bin/sh", env_vars.clone(), command, Some(dir.path())).unwrap();
+        assert_eq!(code, 0);
+
+        // Without a working directory the shell inherits ours
+        let code = spawn_shell("/bin/sh", env_vars, command, None).unwrap();
+        assert_ne!(code, 0);
+    }
 }
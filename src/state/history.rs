@@ -0,0 +1,171 @@
+use crate::utils::write_atomic;
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Maximum number of entries kept in the history file; older entries are trimmed
+pub const MAX_HISTORY_ENTRIES: usize = 500;
+
+/// One recorded environment switch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Name of the environment that was activated
+    pub environment: String,
+}
+
+impl HistoryEntry {
+    /// Timestamp as `YYYY-MM-DD HH:MM:SS UTC`
+    pub fn format_timestamp(&self) -> String {
+        let days = self.timestamp / 86_400;
+        let secs = self.timestamp % 86_400;
+        let (year, month, day) = civil_from_days(days);
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            year,
+            month,
+            day,
+            secs / 3600,
+            secs % 3600 / 60,
+            secs % 60
+        )
+    }
+}
+
+/// Get the path to the history file (`.stand/history`) of a project root
+pub fn get_history_file_path_from(project_root: &Path) -> PathBuf {
+    project_root.join(".stand").join("history")
+}
+
+/// Append an entry for `env_name` with the current time
+///
+/// The file holds one `<unix seconds>\t<environment>` line per entry and is trimmed
+/// to the newest [`MAX_HISTORY_ENTRIES`] entries.
+pub fn append_history_entry(project_root: &Path, env_name: &str) -> Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let history_path = get_history_file_path_from(project_root);
+    if let Some(parent) = history_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history_path)
+        .with_context(|| format!("Failed to open history file: {}", history_path.display()))?;
+    writeln!(file, "{}\t{}", timestamp, env_name)
+        .with_context(|| format!("Failed to write history file: {}", history_path.display()))?;
+    drop(file);
+
+    trim_history(&history_path)
+}
+
+/// Load all entries, oldest first; malformed lines are skipped
+pub fn load_history_from(project_root: &Path) -> Result<Vec<HistoryEntry>> {
+    let history_path = get_history_file_path_from(project_root);
+    if !history_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&history_path)
+        .with_context(|| format!("Failed to read history file: {}", history_path.display()))?;
+    Ok(content.lines().filter_map(parse_line).collect())
+}
+
+fn parse_line(line: &str) -> Option<HistoryEntry> {
+    let (timestamp, environment) = line.split_once('\t')?;
+    Some(HistoryEntry {
+        timestamp: timestamp.parse().ok()?,
+        environment: environment.to_string(),
+    })
+}
+
+/// Rewrite the file with only the newest entries once it grows past the cap
+fn trim_history(history_path: &Path) -> Result<()> {
+    let content = fs::read_to_string(history_path)
+        .with_context(|| format!("Failed to read history file: {}", history_path.display()))?;
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.len() <= MAX_HISTORY_ENTRIES {
+        return Ok(());
+    }
+
+    let mut kept = lines[lines.len() - MAX_HISTORY_ENTRIES..].join("\n");
+    kept.push('\n');
+    write_atomic(history_path, kept)
+        .with_context(|| format!("Failed to write history file: {}", history_path.display()))
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Howard Hinnant's algorithm, shifted so the era starts on 0000-03-01
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_format_timestamp() {
+        let entry = |timestamp| HistoryEntry {
+            timestamp,
+            environment: "dev".to_string(),
+        };
+        assert_eq!(entry(0).format_timestamp(), "1970-01-01 00:00:00 UTC");
+        assert_eq!(
+            entry(951_782_400).format_timestamp(),
+            "2000-02-29 00:00:00 UTC"
+        );
+        assert_eq!(
+            entry(1_792_238_645).format_timestamp(),
+            "2026-10-17 12:04:05 UTC"
+        );
+    }
+
+    #[test]
+    fn test_history_is_trimmed() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_path = get_history_file_path_from(temp_dir.path());
+        fs::create_dir_all(history_path.parent().unwrap()).unwrap();
+        let old: String = (0..MAX_HISTORY_ENTRIES)
+            .map(|i| format!("{}\told-{}\n", i, i))
+            .collect();
+        fs::write(&history_path, old).unwrap();
+
+        append_history_entry(temp_dir.path(), "dev").unwrap();
+
+        let entries = load_history_from(temp_dir.path()).unwrap();
+        assert_eq!(entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(entries[0].environment, "old-1");
+        assert_eq!(entries.last().unwrap().environment, "dev");
+    }
+
+    #[test]
+    fn test_load_history_skips_malformed_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_path = get_history_file_path_from(temp_dir.path());
+        fs::create_dir_all(history_path.parent().unwrap()).unwrap();
+        fs::write(&history_path, "10\tdev\ngarbage\nx\tprod\n20\tstaging\n").unwrap();
+
+        let entries = load_history_from(temp_dir.path()).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.environment.as_str()).collect();
+        assert_eq!(names, vec!["dev", "staging"]);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::state::types::State;
 use crate::utils::paths::find_project_root;
+use crate::utils::write_atomic;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -29,7 +30,13 @@ pub fn get_state_file_path_from(project_root: &Path) -> Result<PathBuf> {
 
 /// Load state from file, or return default state if file doesn't exist
 pub fn load_state() -> Result<State> {
-    let state_path = get_state_file_path()?;
+    let project_root = find_project_root()?;
+    load_state_from(&project_root)
+}
+
+/// Load state for a specific project root, or return default state if file doesn't exist
+pub fn load_state_from(project_root: &Path) -> Result<State> {
+    let state_path = get_state_file_path_from(project_root)?;
 
     if !state_path.exists() {
         return Ok(State::default());
@@ -46,12 +53,18 @@ pub fn load_state() -> Result<State> {
 
 /// Save state to file
 pub fn save_state(state: &State) -> Result<()> {
-    let state_path = get_state_file_path()?;
+    let project_root = find_project_root()?;
+    save_state_to(&project_root, state)
+}
+
+/// Save state to the state file of a specific project root
+pub fn save_state_to(project_root: &Path, state: &State) -> Result<()> {
+    let state_path = get_state_file_path_from(project_root)?;
 
     let content =
         serde_json::to_string_pretty(state).with_context(|| "Failed to serialize state")?;
 
-    fs::write(&state_path, content)
+    write_atomic(&state_path, content)
         .with_context(|| format!("Failed to write state file: {}", state_path.display()))?;
 
     // Set secure permissions (0600) on Unix systems
@@ -109,4 +122,24 @@ mod tests {
         assert_eq!(state_path, expected);
         assert!(project_root.join(".stand").exists());
     }
+
+    #[test]
+    fn test_save_and_load_state_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+
+        assert_eq!(
+            load_state_from(project_root)
+                .unwrap()
+                .get_current_environment(),
+            None
+        );
+
+        let mut state = State::new();
+        state.set_current_environment("dev".to_string());
+        save_state_to(project_root, &state).unwrap();
+
+        let loaded = load_state_from(project_root).unwrap();
+        assert_eq!(loaded.get_current_environment(), Some("dev"));
+    }
 }
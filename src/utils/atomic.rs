@@ -0,0 +1,121 @@
+// Atomic file replacement for files Stand rewrites (.stand.toml, state, history)
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Distinguishes temporary files of concurrent writers within one process
+static WRITE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Replace `path` with `contents` so readers see either the old or the new file
+///
+/// The contents are written and synced to a temporary file in the same directory,
+/// which is then renamed over `path`. A concurrent `stand` invocation can therefore
+/// never observe a truncated or partially written file; with two writers the last
+/// rename wins. An existing file's permissions are kept.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let temp_path = temp_path_for(path);
+    let result =
+        write_temp(path, &temp_path, contents.as_ref()).and_then(|()| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_temp(path: &Path, temp_path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(temp_path)?;
+    if let Ok(metadata) = fs::metadata(path) {
+        file.set_permissions(metadata.permissions())?;
+    }
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+/// `.<name>.<pid>.<n>.tmp` next to `path`, so the rename never crosses filesystems
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let counter = WRITE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{}.{}.{}.tmp", name, process::id(), counter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_atomic_creates_and_replaces() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".stand.toml");
+
+        write_atomic(&path, "version = \"1.0\"\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "version = \"1.0\"\n");
+        write_atomic(&path, "version = \"2.0\"\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "version = \"2.0\"\n");
+
+        // No temporary files are left behind
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().flatten().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_is_never_observed_partially_written() {
+        let dir = tempdir().unwrap();
+        let path = Arc::new(dir.path().join("state.json"));
+        let small = "a".repeat(16);
+        let large = "b".repeat(1 << 20);
+        write_atomic(&path, &small).unwrap();
+
+        let writer = {
+            let path = Arc::clone(&path);
+            let (small, large) = (small.clone(), large.clone());
+            thread::spawn(move || {
+                for i in 0..50 {
+                    let contents = if i % 2 == 0 { &large } else { &small };
+                    write_atomic(&path, contents).unwrap();
+                }
+            })
+        };
+        while !writer.is_finished() {
+            let contents = fs::read_to_string(path.as_ref()).unwrap();
+            assert!(contents == small || contents == large);
+        }
+        writer.join().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_atomic(&path, "{\"a\": 1}").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_write_atomic_reports_missing_directory() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing").join("file");
+
+        assert!(write_atomic(&path, "x").is_err());
+        assert!(!path.exists());
+    }
+}
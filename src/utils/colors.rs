@@ -1,7 +1,58 @@
 use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Process-wide color switch set from the CLI (`--no-color`)
+static NO_COLOR_OVERRIDE: AtomicBool = AtomicBool::new(false);
+
+/// Disable (or re-enable) colored output for the whole process
+///
+/// Also forwards the choice to the `colored` crate so direct uses of it are covered.
+pub fn set_no_color(no_color: bool) {
+    NO_COLOR_OVERRIDE.store(no_color, Ordering::Relaxed);
+    if no_color {
+        colored::control::set_override(false);
+    } else {
+        colored::control::unset_override();
+    }
+}
+
+/// Returns true unless colors are disabled by `--no-color`, `NO_COLOR`, or `TERM=dumb`
+///
+/// Following <https://no-color.org>, `NO_COLOR` only counts when it is non-empty.
+pub fn colors_enabled() -> bool {
+    if NO_COLOR_OVERRIDE.load(Ordering::Relaxed) {
+        return false;
+    }
+    if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    std::env::var_os("TERM").is_none_or(|term| term != "dumb")
+}
+
+/// Success glyph (green `✓` when colors are enabled)
+pub fn success_mark() -> String {
+    if colors_enabled() {
+        "✓".green().to_string()
+    } else {
+        "✓".to_string()
+    }
+}
+
+/// Warning glyph (yellow `⚠` when colors are enabled)
+pub fn warning_mark() -> String {
+    if colors_enabled() {
+        "⚠".yellow().to_string()
+    } else {
+        "⚠".to_string()
+    }
+}
 
 /// Colorize an environment name with the specified color
 pub fn colorize_environment(env_name: &str, color: Option<&str>) -> String {
+    if !colors_enabled() {
+        return env_name.to_string();
+    }
+
     match color {
         Some(c) => {
             let color_lower = c.to_lowercase();
@@ -28,18 +79,78 @@ pub fn format_default_marker(is_default: bool) -> &'static str {
     }
 }
 
+/// Number of mask characters inserted in place of the hidden part of a value
+const MASK_LENGTH: usize = 8;
+
+/// Options controlling how much of a value [`mask_value_with_options`] reveals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskOptions {
+    /// Number of leading characters left visible
+    pub visible_prefix: usize,
+    /// Number of trailing characters left visible
+    pub visible_suffix: usize,
+    /// Character used for the masked part
+    pub mask_char: char,
+}
+
+impl Default for MaskOptions {
+    fn default() -> Self {
+        Self {
+            visible_prefix: 0,
+            visible_suffix: 0,
+            mask_char: '*',
+        }
+    }
+}
+
 /// Mask sensitive values for display
 pub fn mask_value(value: &str, show_values: bool) -> String {
-    if show_values || value.is_empty() {
+    if show_values {
         value.to_string()
     } else {
-        "********".to_string()
+        mask_value_with_options(value, &MaskOptions::default())
     }
 }
 
+/// Mask a value, keeping the configured prefix and suffix visible
+///
+/// The hidden part is always replaced by a fixed-width mask so the output does not
+/// reveal the value's length. Values no longer than `visible_prefix + visible_suffix`
+/// are masked entirely.
+pub fn mask_value_with_options(value: &str, options: &MaskOptions) -> String {
+    if value.is_empty() {
+        return String::new();
+    }
+
+    let mask: String = std::iter::repeat_n(options.mask_char, MASK_LENGTH).collect();
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= options.visible_prefix + options.visible_suffix {
+        return mask;
+    }
+
+    let prefix: String = chars[..options.visible_prefix].iter().collect();
+    let suffix: String = chars[chars.len() - options.visible_suffix..]
+        .iter()
+        .collect();
+    format!("{}{}{}", prefix, mask, suffix)
+}
+
+/// Summarize a secret as `KEY=********` for confirmation and diagnostic output
+///
+/// Unlike [`mask_value`] there is no option to show the value, and no characters
+/// of it are kept visible. Use this wherever a message mentions a secret value.
+pub fn format_secret_summary(key: &str, value: &str) -> String {
+    format!(
+        "{}={}",
+        key,
+        mask_value_with_options(value, &MaskOptions::default())
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_colorize_environment_with_green() {
@@ -122,4 +233,127 @@ mod tests {
         let result = mask_value("", false);
         assert_eq!(result, "");
     }
+
+    #[test]
+    fn test_format_secret_summary_never_contains_value() {
+        let secret = "sk-live-0123456789abcdef";
+
+        let summary = format_secret_summary("API_KEY", secret);
+
+        assert_eq!(summary, "API_KEY=********");
+        for start in 0..secret.len() - 3 {
+            assert!(!summary.contains(&secret[start..start + 4]));
+        }
+    }
+
+    #[test]
+    fn test_mask_value_with_default_options() {
+        let result = mask_value_with_options("sensitive_password", &MaskOptions::default());
+        assert_eq!(result, "********");
+    }
+
+    #[test]
+    fn test_mask_value_with_prefix_and_suffix() {
+        let options = MaskOptions {
+            visible_prefix: 3,
+            visible_suffix: 2,
+            mask_char: '#',
+        };
+        let result = mask_value_with_options("sk-live-abcdef", &options);
+        assert_eq!(result, "sk-########ef");
+    }
+
+    #[test]
+    fn test_mask_value_with_prefix_only() {
+        let options = MaskOptions {
+            visible_prefix: 4,
+            ..MaskOptions::default()
+        };
+        let result = mask_value_with_options("postgres://secret", &options);
+        assert_eq!(result, "post********");
+    }
+
+    #[test]
+    fn test_mask_value_shorter_than_visible_chars_is_fully_masked() {
+        let options = MaskOptions {
+            visible_prefix: 3,
+            visible_suffix: 3,
+            mask_char: '*',
+        };
+        assert_eq!(mask_value_with_options("abcdef", &options), "********");
+        assert_eq!(mask_value_with_options("abc", &options), "********");
+        assert_eq!(mask_value_with_options("", &options), "");
+    }
+
+    #[test]
+    fn test_mask_value_with_multibyte_characters() {
+        let options = MaskOptions {
+            visible_prefix: 2,
+            ..MaskOptions::default()
+        };
+        let result = mask_value_with_options("日本語のパスワード", &options);
+        assert_eq!(result, "日本********");
+    }
+
+    #[test]
+    #[serial]
+    fn test_no_color_env_disables_escape_sequences() {
+        colored::control::set_override(true);
+        std::env::set_var("NO_COLOR", "1");
+
+        let env_name = colorize_environment("prod", Some("red"));
+        let success = success_mark();
+        let warning = warning_mark();
+        let enabled = colors_enabled();
+
+        std::env::remove_var("NO_COLOR");
+        colored::control::unset_override();
+
+        assert!(!enabled);
+        assert_eq!(env_name, "prod");
+        assert_eq!(success, "✓");
+        assert_eq!(warning, "⚠");
+    }
+
+    #[test]
+    #[serial]
+    fn test_empty_no_color_does_not_disable_colors() {
+        colored::control::set_override(true);
+        std::env::set_var("NO_COLOR", "");
+        let term = std::env::var_os("TERM");
+        std::env::set_var("TERM", "xterm-256color");
+
+        let enabled = colors_enabled();
+        let env_name = colorize_environment("prod", Some("red"));
+
+        std::env::remove_var("NO_COLOR");
+        match term {
+            Some(term) => std::env::set_var("TERM", term),
+            None => std::env::remove_var("TERM"),
+        }
+        colored::control::unset_override();
+
+        assert!(enabled);
+        assert!(env_name.contains("\x1b["));
+    }
+
+    #[test]
+    #[serial]
+    fn test_dumb_terminal_disables_colors() {
+        colored::control::set_override(true);
+        let term = std::env::var_os("TERM");
+        std::env::set_var("TERM", "dumb");
+
+        let enabled = colors_enabled();
+        let success = success_mark();
+
+        match term {
+            Some(term) => std::env::set_var("TERM", term),
+            None => std::env::remove_var("TERM"),
+        }
+        colored::control::unset_override();
+
+        assert!(!enabled);
+        assert_eq!(success, "✓");
+    }
 }
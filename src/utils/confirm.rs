@@ -0,0 +1,261 @@
+use anyhow::{anyhow, Result};
+use std::io::{self, IsTerminal, Write};
+
+/// Check if stdin is an interactive terminal
+///
+/// Returns false if:
+/// - stdin is not a TTY
+/// - STAND_FORCE_NON_TTY environment variable is set (for testing)
+pub fn is_interactive_terminal() -> bool {
+    if std::env::var("STAND_FORCE_NON_TTY").is_ok() {
+        return false;
+    }
+    io::stdin().is_terminal()
+}
+
+/// Asks the user a yes/no question
+///
+/// Commands take a `&dyn Confirm` so confirmation flows can be tested without a TTY.
+pub trait Confirm {
+    /// Whether a user can answer; commands refuse to prompt otherwise
+    fn is_interactive(&self) -> bool;
+
+    /// Ask `prompt`, returning `default` for an empty answer
+    fn confirm(&self, prompt: &str, default: bool) -> Result<bool>;
+}
+
+/// Prompts on the terminal and reads the answer from stdin
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdinConfirm {
+    /// Write the prompt to stderr, for commands whose stdout may be redirected
+    pub to_stderr: bool,
+}
+
+impl StdinConfirm {
+    /// Prompt on stderr instead of stdout
+    pub fn stderr() -> Self {
+        Self { to_stderr: true }
+    }
+}
+
+impl Confirm for StdinConfirm {
+    fn is_interactive(&self) -> bool {
+        is_interactive_terminal()
+    }
+
+    fn confirm(&self, prompt: &str, default: bool) -> Result<bool> {
+        let choices = if default { "[Y/n]" } else { "[y/N]" };
+        if self.to_stderr {
+            eprint!("{} {}: ", prompt, choices);
+            io::stderr().flush()?;
+        } else {
+            print!("{} {}: ", prompt, choices);
+            io::stdout().flush()?;
+        }
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(parse_answer(&input, default))
+    }
+}
+
+/// `y`/`yes` and `n`/`no` (case-insensitive); anything else gives `default`
+fn parse_answer(input: &str, default: bool) -> bool {
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    }
+}
+
+/// Ask `prompt` on the terminal, returning `default` for an empty answer
+pub fn confirm(prompt: &str, default: bool) -> Result<bool> {
+    StdinConfirm::default().confirm(prompt, default)
+}
+
+/// Prompt user for confirmation before using a protected environment
+///
+/// Returns true if the user confirms, false otherwise
+pub fn prompt_confirmation(env_name: &str) -> Result<bool> {
+    prompt_confirmation_with(env_name, &StdinConfirm::default())
+}
+
+fn prompt_confirmation_with(env_name: &str, confirmer: &dyn Confirm) -> Result<bool> {
+    confirmer.confirm(
+        &format!(
+            "Environment '{}' requires confirmation.\nAre you sure you want to proceed?",
+            env_name
+        ),
+        false,
+    )
+}
+
+/// Print `question` followed by ` [y/N]: ` and read the answer from stdin
+///
+/// Returns true only for `y` or `yes` (case-insensitive).
+pub fn prompt_yes_no(question: &str) -> Result<bool> {
+    confirm(question, false)
+}
+
+/// Like [`prompt_yes_no`], but writes the question to stderr
+///
+/// Use this when stdout carries the command's output (e.g. it may be redirected to a file).
+pub fn prompt_yes_no_stderr(question: &str) -> Result<bool> {
+    StdinConfirm::stderr().confirm(question, false)
+}
+
+/// A [`Confirm`] with a fixed answer that records the prompts it was asked
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct MockConfirm {
+    pub answer: bool,
+    pub prompts: std::cell::RefCell<Vec<String>>,
+}
+
+#[cfg(test)]
+impl MockConfirm {
+    pub fn answering(answer: bool) -> Self {
+        Self {
+            answer,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+impl Confirm for MockConfirm {
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn confirm(&self, prompt: &str, _default: bool) -> Result<bool> {
+        self.prompts.borrow_mut().push(prompt.to_string());
+        Ok(self.answer)
+    }
+}
+
+/// Check whether `STAND_YES` asks to skip environment confirmation prompts
+///
+/// `1`, `true` and `yes` (case-insensitive) enable it; any other value is ignored.
+pub fn yes_from_env() -> bool {
+    std::env::var("STAND_YES")
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Ensure the user confirmed use of an environment that requires confirmation
+///
+/// Does nothing when `requires_confirmation` is false, or when `skip_confirmation`
+/// (`-y`) or `STAND_YES` is set. Fails fast without prompting when stdin is not a terminal.
+pub fn ensure_confirmed(
+    env_name: &str,
+    requires_confirmation: bool,
+    skip_confirmation: bool,
+) -> Result<()> {
+    ensure_confirmed_with(
+        env_name,
+        requires_confirmation,
+        skip_confirmation,
+        &StdinConfirm::default(),
+    )
+}
+
+/// Like [`ensure_confirmed`], asking through `confirmer`
+pub fn ensure_confirmed_with(
+    env_name: &str,
+    requires_confirmation: bool,
+    skip_confirmation: bool,
+    confirmer: &dyn Confirm,
+) -> Result<()> {
+    if !requires_confirmation || skip_confirmation || yes_from_env() {
+        return Ok(());
+    }
+
+    // Check if stdin is a terminal - fail fast in non-interactive environments
+    if !confirmer.is_interactive() {
+        return Err(anyhow!(
+            "Environment '{}' requires confirmation but stdin is not a terminal.\n\
+             Use -y or --yes (or set STAND_YES=1) to skip confirmation in non-interactive environments.",
+            env_name
+        ));
+    }
+
+    if !prompt_confirmation_with(env_name, confirmer)? {
+        return Err(anyhow!(
+            "Execution cancelled. Use -y or --yes to skip confirmation."
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_yes_from_env() {
+        for (value, expected) in [
+            ("1", true),
+            ("true", true),
+            ("YES", true),
+            ("0", false),
+            ("false", false),
+            ("", false),
+        ] {
+            std::env::set_var("STAND_YES", value);
+            assert_eq!(yes_from_env(), expected, "STAND_YES={:?}", value);
+        }
+        std::env::remove_var("STAND_YES");
+        assert!(!yes_from_env());
+    }
+
+    #[test]
+    #[serial]
+    fn test_ensure_confirmed_honours_stand_yes() {
+        std::env::set_var("STAND_FORCE_NON_TTY", "1");
+
+        std::env::set_var("STAND_YES", "1");
+        let with_env = ensure_confirmed("prod", true, false);
+        std::env::remove_var("STAND_YES");
+        let without_env = ensure_confirmed("prod", true, false);
+
+        std::env::remove_var("STAND_FORCE_NON_TTY");
+        assert!(with_env.is_ok());
+        assert!(without_env.is_err());
+    }
+
+    #[test]
+    fn test_parse_answer() {
+        assert!(parse_answer("y\n", false));
+        assert!(parse_answer(" YES ", false));
+        assert!(!parse_answer("n", true));
+        assert!(!parse_answer("no\n", true));
+        assert!(parse_answer("\n", true));
+        assert!(!parse_answer("", false));
+        assert!(!parse_answer("maybe", false));
+    }
+
+    #[test]
+    #[serial]
+    fn test_ensure_confirmed_with_mock() {
+        std::env::remove_var("STAND_YES");
+
+        let accept = MockConfirm::answering(true);
+        assert!(ensure_confirmed_with("prod", true, false, &accept).is_ok());
+        assert_eq!(accept.prompts.borrow().len(), 1);
+        assert!(accept.prompts.borrow()[0].contains("Environment 'prod'"));
+
+        let decline = MockConfirm::answering(false);
+        let err = ensure_confirmed_with("prod", true, false, &decline).unwrap_err();
+        assert!(err.to_string().contains("Execution cancelled"));
+
+        // No prompt when confirmation is not required or skipped
+        let unused = MockConfirm::answering(false);
+        assert!(ensure_confirmed_with("dev", false, false, &unused).is_ok());
+        assert!(ensure_confirmed_with("prod", true, true, &unused).is_ok());
+        assert!(unused.prompts.borrow().is_empty());
+    }
+}
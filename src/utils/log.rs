@@ -0,0 +1,84 @@
+//! Trace output for the global `-v` / `-vv` flags.
+//!
+//! A process-wide level set once by `main`; messages go to stderr so they never
+//! mix with data a command prints on stdout. Never log decrypted values.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much trace output to print
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// `-v`: which files are read and which values are decrypted
+    Verbose = 1,
+    /// `-vv`: also inheritance and interpolation steps
+    Debug = 2,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Set the level from the number of `-v` flags (0 disables tracing)
+pub fn set_verbosity(count: u8) {
+    LEVEL.store(count, Ordering::Relaxed);
+}
+
+/// Returns true if messages at `level` are printed
+pub fn enabled(level: LogLevel) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Print `message` to stderr if `level` is enabled
+///
+/// Prefer the [`log_verbose!`](crate::log_verbose) and [`log_debug!`](crate::log_debug)
+/// macros, which skip formatting when the level is disabled.
+pub fn log(level: LogLevel, message: std::fmt::Arguments) {
+    if enabled(level) {
+        let prefix = match level {
+            LogLevel::Verbose => "verbose",
+            LogLevel::Debug => "debug",
+        };
+        eprintln!("[{}] {}", prefix, message);
+    }
+}
+
+/// Trace a message shown with `-v` and above
+#[macro_export]
+macro_rules! log_verbose {
+    ($($arg:tt)*) => {
+        if $crate::utils::log::enabled($crate::utils::log::LogLevel::Verbose) {
+            $crate::utils::log::log($crate::utils::log::LogLevel::Verbose, format_args!($($arg)*));
+        }
+    };
+}
+
+/// Trace a message shown with `-vv`
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        if $crate::utils::log::enabled($crate::utils::log::LogLevel::Debug) {
+            $crate::utils::log::log($crate::utils::log::LogLevel::Debug, format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_levels_are_cumulative() {
+        set_verbosity(0);
+        assert!(!enabled(LogLevel::Verbose));
+
+        set_verbosity(1);
+        assert!(enabled(LogLevel::Verbose));
+        assert!(!enabled(LogLevel::Debug));
+
+        // More flags than levels still means everything
+        set_verbosity(3);
+        assert!(enabled(LogLevel::Debug));
+
+        set_verbosity(0);
+    }
+}
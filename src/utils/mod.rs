@@ -1,6 +1,13 @@
+pub mod atomic;
 pub mod colors;
+pub mod confirm;
+pub mod log;
 pub mod paths;
 
 // Re-export commonly used functions for convenience
-pub use colors::{colorize_environment, format_default_marker, mask_value};
+pub use atomic::write_atomic;
+pub use colors::{
+    colorize_environment, colors_enabled, format_default_marker, mask_value,
+    mask_value_with_options, set_no_color, MaskOptions,
+};
 pub use paths::{find_project_root, find_project_root_from, get_config_path};
@@ -12,8 +12,8 @@ pub fn find_project_root_from(start_dir: &Path) -> Result<PathBuf> {
     let mut dir = start_dir;
 
     loop {
-        // Check for .stand.toml file
-        if dir.join(".stand.toml").exists() {
+        // Check for .stand.toml or .stand.yaml file
+        if dir.join(".stand.toml").exists() || dir.join(".stand.yaml").exists() {
             return Ok(dir.to_path_buf());
         }
 
@@ -100,6 +100,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_project_root_with_yaml_config() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".stand.yaml"), "version: \"2.0\"\n").unwrap();
+
+        let result = find_project_root_from(temp_dir.path());
+
+        assert_eq!(result.unwrap(), temp_dir.path());
+    }
+
     #[test]
     fn test_get_config_path() {
         let project_root = Path::new("/some/project");
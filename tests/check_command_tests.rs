@@ -0,0 +1,264 @@
+use serial_test::serial;
+use stand::commands::check;
+use stand::crypto::{encrypt_value, generate_key_pair, keys};
+use std::env;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+#[serial]
+fn test_check_reports_undefined_reference_and_bad_ciphertext() {
+    env::remove_var("STAND_PRIVATE_KEY");
+    env::remove_var("STAND_CHECK_UNDEFINED_HOST");
+
+    let dir = tempdir().unwrap();
+    let key_pair = generate_key_pair();
+    keys::save_private_key(&dir.path().join(".stand.keys"), &key_pair.private_key).unwrap();
+    let good = encrypt_value("secret", &key_pair.to_recipient().unwrap()).unwrap();
+
+    let config_content = format!(
+        r#"
+version = "2.0"
+
+[encryption]
+public_key = "{}"
+
+[environments.dev]
+description = "Development environment"
+API_URL = "https://${{STAND_CHECK_UNDEFINED_HOST}}/api"
+GOOD_SECRET = "{}"
+BAD_SECRET = "encrypted:bm90LWFuLWFnZS1maWxl"
+PLAIN = "value"
+"#,
+        key_pair.public_key, good
+    );
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let report = check::check_environment(dir.path(), "dev").unwrap();
+
+    assert!(!report.is_ok());
+    assert_eq!(report.issues.len(), 2);
+    assert_eq!(report.issues[0].variable.as_deref(), Some("API_URL"));
+    assert!(report.issues[0]
+        .message
+        .contains("STAND_CHECK_UNDEFINED_HOST"));
+    assert_eq!(report.issues[1].variable.as_deref(), Some("BAD_SECRET"));
+
+    let output = report.format();
+    assert!(output.contains("2 problems"));
+    assert!(output.contains("API_URL"));
+    assert!(output.contains("BAD_SECRET"));
+}
+
+#[test]
+#[serial]
+fn test_check_passes_for_resolvable_environment() {
+    env::set_var("STAND_CHECK_DB_HOST", "localhost");
+
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[common]
+APP_NAME = "MyApp"
+
+[environments.base]
+description = "Base environment"
+PORT = "3000"
+
+[environments.dev]
+description = "Development environment"
+extends = "base"
+DATABASE_URL = "postgres://${STAND_CHECK_DB_HOST}/dev"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let report = check::check_environment(dir.path(), "dev").unwrap();
+    env::remove_var("STAND_CHECK_DB_HOST");
+
+    assert!(report.is_ok(), "{}", report.format());
+    assert_eq!(report.variable_count, 3);
+    assert!(report.format().contains("resolves (3 variables)"));
+}
+
+#[test]
+#[serial]
+fn test_check_accepts_stand_pseudo_variables() {
+    env::remove_var("STAND_ENV");
+
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+LOG_PREFIX = "${STAND_ENV}-app"
+CONFIG_DIR = "${STAND_PROJECT_ROOT}/config"
+"#,
+    )
+    .unwrap();
+
+    let report = check::check_environment(dir.path(), "dev").unwrap();
+
+    assert!(report.is_ok(), "{}", report.format());
+}
+
+#[test]
+fn test_check_reports_missing_parent() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+extends = "missing"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let report = check::check_environment(dir.path(), "dev").unwrap();
+
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.issues[0].message.contains("'missing'"));
+}
+
+#[test]
+#[serial]
+fn test_check_reports_missing_private_key() {
+    env::remove_var("STAND_PRIVATE_KEY");
+
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[encryption]
+public_key = "age1test"
+
+[environments.dev]
+description = "Development environment"
+SECRET = "encrypted:abc"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let report = check::check_environment(dir.path(), "dev").unwrap();
+
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.issues[0].message.contains("cannot be decrypted"));
+}
+
+#[test]
+fn test_check_nonexistent_environment_is_error() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\n",
+    )
+    .unwrap();
+
+    let result = check::check_environment(dir.path(), "prod");
+
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("not found"));
+}
+
+#[test]
+#[serial]
+fn test_check_all_marks_only_failing_environment() {
+    env::remove_var("STAND_CHECK_ALL_UNDEFINED");
+
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+PORT = "3000"
+
+[environments.prod]
+description = "Production environment"
+API_URL = "https://${STAND_CHECK_ALL_UNDEFINED}/api"
+
+[environments.staging]
+description = "Staging environment"
+extends = "dev"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let reports = check::check_all_environments(dir.path()).unwrap();
+
+    let names: Vec<_> = reports.iter().map(|r| r.env_name.as_str()).collect();
+    assert_eq!(names, vec!["dev", "prod", "staging"]);
+    assert!(reports[0].is_ok());
+    assert!(!reports[1].is_ok());
+    assert!(reports[2].is_ok());
+
+    let summary = check::format_summary(&reports);
+    assert_eq!(
+        summary,
+        "  dev:      OK\n  prod:     1 error\n  staging:  OK\n"
+    );
+}
+
+#[test]
+fn test_check_includes_dotenv_cascade() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[settings]
+dotenv_cascade = true
+
+[environments.dev]
+description = "Development environment"
+PORT = "3000"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+    // Dotenv values are used as written, so the placeholder is not an undefined reference
+    fs::write(
+        dir.path().join(".env.dev"),
+        "TEMPLATE=${STAND_CHECK_NEVER_SET}\nLOG_LEVEL=debug\n",
+    )
+    .unwrap();
+
+    let report = check::check_environment(dir.path(), "dev").unwrap();
+
+    assert!(report.is_ok(), "{}", report.format());
+    assert_eq!(report.variable_count, 3);
+}
+
+#[test]
+#[serial]
+fn test_check_applies_local_overlay() {
+    env::remove_var("STAND_CHECK_OVERLAY_UNDEFINED");
+
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_URL = "https://${STAND_CHECK_OVERLAY_UNDEFINED}/api"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+    fs::write(
+        dir.path().join(".stand.local.toml"),
+        r#"
+[environments.dev]
+API_URL = "http://localhost:3000/api"
+LOCAL_ONLY = "1"
+
+[environments.scratch]
+description = "Local scratch environment"
+"#,
+    )
+    .unwrap();
+
+    let report = check::check_environment(dir.path(), "dev").unwrap();
+    assert!(report.is_ok(), "{}", report.format());
+    assert_eq!(report.variable_count, 2);
+
+    let reports = check::check_all_environments(dir.path()).unwrap();
+    let names: Vec<_> = reports.iter().map(|r| r.env_name.as_str()).collect();
+    assert_eq!(names, vec!["dev", "scratch"]);
+}
@@ -44,6 +44,31 @@ fn test_cli_init_creates_config() {
     assert!(content.contains("[environments.prod]"));
 }
 
+#[test]
+fn test_cli_init_with_custom_environments() {
+    let dir = tempdir().unwrap();
+
+    cargo_bin_cmd!("stand")
+        .current_dir(dir.path())
+        .args(["init", "--env", "staging", "--env", "qa"])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+    assert!(content.contains("[environments.staging]"));
+    assert!(content.contains("[environments.qa]"));
+    assert!(!content.contains("[environments.dev]"));
+    assert!(content.contains(r#"default_environment = "staging""#));
+
+    // Other commands select a single environment
+    cargo_bin_cmd!("stand")
+        .current_dir(dir.path())
+        .args(["env", "--env", "staging", "--env", "qa"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--env can only be given once"));
+}
+
 #[test]
 fn test_cli_init_fails_when_exists() {
     let dir = tempdir().unwrap();
@@ -90,6 +115,161 @@ fn test_cli_shell_command_no_config() {
         .stderr(predicate::str::contains("Error:")); // Should fail without .stand.toml file
 }
 
+#[test]
+fn test_cli_shell_prints_start_banner() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#,
+    )
+    .unwrap();
+
+    // stdin is closed, so the interactive shell exits immediately
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env_remove("STAND_ACTIVE")
+        .env_remove("STAND_ENVIRONMENT")
+        .args(["shell", "dev", "--shell", "/bin/sh"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Starting shell with environment"));
+}
+
+#[test]
+fn test_cli_shell_quiet_suppresses_start_banner() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env_remove("STAND_ACTIVE")
+        .env_remove("STAND_ENVIRONMENT")
+        .args(["--quiet", "shell", "dev", "--shell", "/bin/sh"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Starting shell").not());
+}
+
+#[test]
+fn test_cli_quiet_still_prints_errors() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env_remove("STAND_ACTIVE")
+        .env_remove("STAND_ENVIRONMENT")
+        .args(["--quiet", "shell", "missing", "--shell", "/bin/sh"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Error:"))
+        .stderr(predicate::str::contains("Starting shell").not());
+}
+
+#[test]
+fn test_cli_quiet_init_and_encrypt_print_nothing() {
+    let dir = tempdir().unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["init", "--quiet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["--quiet", "encrypt", "enable"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    assert!(dir.path().join(".stand.keys").exists());
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["--quiet", "encrypt", "enable"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Error:"));
+}
+
+#[test]
+fn test_cli_stand_yes_skips_confirmation() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.prod]
+description = "Production"
+requires_confirmation = true
+TARGET = "production"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_FORCE_NON_TTY", "1")
+        .env_remove("STAND_YES")
+        .args(["exec", "prod", "--", "true"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires confirmation"));
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_FORCE_NON_TTY", "1")
+        .env("STAND_YES", "1")
+        .args(["exec", "prod", "--", "sh", "-c", "echo $TARGET"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("production"));
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_FORCE_NON_TTY", "1")
+        .env("STAND_YES", "1")
+        .env_remove("STAND_ACTIVE")
+        .env_remove("STAND_ENVIRONMENT")
+        .args([
+            "shell",
+            "prod",
+            "--shell",
+            "/bin/sh",
+            "--command",
+            "echo $TARGET",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("production"));
+}
+
 #[test]
 fn test_cli_list_command_no_config_basic() {
     let dir = tempdir().unwrap();
@@ -256,6 +436,46 @@ fn test_cli_env_command_options_conflict() {
         .stderr(predicate::str::contains("cannot be used with"));
 }
 
+#[test]
+fn test_cli_env_command_inside_subshell() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\nAPI_URL = \"http://localhost\"\n",
+    )
+    .unwrap();
+    let stand_env = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("stand");
+        cmd.current_dir(dir.path())
+            .env("STAND_ACTIVE", "1")
+            .env("STAND_ENVIRONMENT", "dev")
+            .env("STAND_PROJECT_ROOT", dir.path())
+            .arg("env")
+            .args(args)
+            .assert()
+            .success()
+    };
+
+    stand_env(&[])
+        .stdout(predicate::str::contains("# Stand Environment\n"))
+        .stdout(predicate::str::contains("STAND_ENVIRONMENT=dev\n"))
+        .stdout(predicate::str::contains(
+            "# User Variables\nAPI_URL=http://localhost\n",
+        ));
+    stand_env(&["--user-only"]).stdout("# User Variables\nAPI_URL=http://localhost\n");
+    stand_env(&["--stand-only"])
+        .stdout(predicate::str::contains("STAND_ACTIVE=1\n"))
+        .stdout(predicate::str::contains("API_URL").not());
+
+    let output = stand_env(&["--json", "--user-only"])
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["user"]["API_URL"], "http://localhost");
+    assert!(json.get("stand").is_none());
+}
+
 // === Encryption Integration Tests ===
 
 #[test]
@@ -308,6 +528,51 @@ fn test_cli_encrypt_enable_already_enabled() {
         .stderr(predicate::str::contains("already enabled"));
 }
 
+#[test]
+fn test_cli_keys_show_prints_configured_key() {
+    let dir = tempdir().unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["init", "--encrypt"])
+        .assert()
+        .success();
+
+    let config: toml::Value =
+        toml::from_str(&fs::read_to_string(dir.path().join(".stand.toml")).unwrap()).unwrap();
+    let public_key = config["encryption"]["public_key"].as_str().unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    let output = cmd
+        .current_dir(dir.path())
+        .args(["keys", "show"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("Public key:  {}", public_key)));
+    assert!(stdout.contains(&format!(
+        "Fingerprint: {}",
+        stand::crypto::keys::public_key_fingerprint(public_key)
+    )));
+}
+
+#[test]
+fn test_cli_keys_show_without_encryption() {
+    let dir = tempdir().unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path()).arg("init").assert().success();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["keys", "show"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Encryption is not enabled"));
+}
+
 #[test]
 fn test_cli_init_with_encrypt_flag() {
     let dir = tempdir().unwrap();
@@ -363,6 +628,81 @@ fn test_cli_set_and_get_encrypted_value() {
         .stdout(predicate::str::contains("secret-value-123"));
 }
 
+#[test]
+fn test_cli_set_from_stdin_multiline_encrypted() {
+    let dir = tempdir().unwrap();
+    let cert = "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n";
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["init", "--encrypt"])
+        .assert()
+        .success();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["set", "dev", "TLS_CERT", "--from-stdin", "--encrypt"])
+        .write_stdin(cert)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(encrypted)"));
+
+    let config_content = fs::read_to_string(dir.path().join(".stand.toml")).unwrap();
+    assert!(!config_content.contains("MIIB"));
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "dev", "TLS_CERT"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", cert));
+}
+
+#[test]
+fn test_cli_set_common() {
+    let dir = tempdir().unwrap();
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path()).arg("init").assert().success();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["set", "--common", "APP_NAME", "shared"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Set APP_NAME in [common]"));
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "prod", "APP_NAME"])
+        .assert()
+        .success()
+        .stdout("shared\n");
+
+    // An environment cannot be named together with --common
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["set", "--common", "dev", "APP_NAME", "shared"])
+        .assert()
+        .failure();
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["set", "--common", "dev", "APP_NAME"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'dev' is an environment"));
+}
+
+#[test]
+fn test_cli_set_from_stdin_conflicts_with_value() {
+    let dir = tempdir().unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["set", "dev", "KEY", "value", "--from-stdin"])
+        .assert()
+        .failure();
+}
+
 #[test]
 fn test_cli_inspect_shows_encrypted_marker() {
     let dir = tempdir().unwrap();
@@ -464,3 +804,730 @@ SECRET = "encrypted:invaliddata"
         .failure()
         .stderr(predicate::str::contains("decrypt").or(predicate::str::contains("private key")));
 }
+
+#[test]
+fn test_cli_complete_lists_matching_environments() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+
+[environments.demo]
+description = "Demo"
+
+[environments.prod]
+description = "Production"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["__complete", "de"])
+        .assert()
+        .success()
+        .stdout("demo\ndev\n");
+}
+
+#[test]
+fn test_cli_complete_without_config_prints_nothing() {
+    let dir = tempdir().unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["__complete", "d"])
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn test_cli_switch_protected_env_requires_yes_in_non_tty() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.prod]
+description = "Production"
+requires_confirmation = true
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_FORCE_NON_TTY", "1")
+        .args(["switch", "prod"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires confirmation"));
+    assert!(!dir.path().join(".stand").join("state.json").exists());
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_FORCE_NON_TTY", "1")
+        .args(["switch", "prod", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Switched to environment 'prod'"));
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("prod"));
+}
+
+#[test]
+fn test_cli_inspect_selects_environment_from_process_variable() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[settings]
+environment_from = "APP_ENV"
+default_environment = "dev"
+
+[environments.dev]
+description = "Development"
+DEV_ONLY = "1"
+
+[environments.prod]
+description = "Production"
+PROD_ONLY = "1"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("APP_ENV", "prod")
+        .arg("inspect")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PROD_ONLY"));
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("APP_ENV", "prod")
+        .args(["inspect", "--env", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DEV_ONLY"));
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env_remove("APP_ENV")
+        .arg("inspect")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DEV_ONLY"));
+}
+
+#[test]
+fn test_cli_validate_all_prints_environment_summary() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+PORT = "3000"
+
+[environments.prod]
+description = "Production"
+API_URL = "https://${STAND_CLI_VALIDATE_ALL_UNDEFINED}/api"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env_remove("STAND_CLI_VALIDATE_ALL_UNDEFINED")
+        .args(["validate", "--all"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("dev:   OK"))
+        .stdout(predicate::str::contains("prod:  1 error"));
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_CLI_VALIDATE_ALL_UNDEFINED", "example.com")
+        .args(["check", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("prod:  OK"));
+}
+
+#[test]
+fn test_cli_validate_all_applies_local_overlay() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+API_URL = "https://${STAND_CLI_VALIDATE_OVERLAY_UNDEFINED}/api"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join(".stand.local.toml"),
+        "[environments.dev]\nAPI_URL = \"http://localhost:3000/api\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env_remove("STAND_CLI_VALIDATE_OVERLAY_UNDEFINED")
+        .args(["validate", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dev:  OK"));
+}
+
+#[test]
+fn test_cli_validate_all_reads_yaml_project() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.yaml"),
+        "version: \"2.0\"\nenvironments:\n  dev:\n    description: Development\n    PORT: \"3000\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["validate", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dev:  OK"));
+}
+
+#[test]
+fn test_cli_list_tree_all_includes_hidden_environments() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+
+[environments.scratch]
+description = "Scratch"
+hidden = true
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["list", "--tree"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scratch").not());
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["list", "--tree", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scratch  Scratch (hidden)"));
+}
+
+#[test]
+fn test_cli_get_without_environment_uses_active_environment() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[settings]
+default_environment = "dev"
+
+[environments.dev]
+description = "Development"
+API_URL = "http://localhost"
+
+[environments.staging]
+description = "Staging"
+API_URL = "https://staging.example.com"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "API_URL"])
+        .assert()
+        .success()
+        .stdout("http://localhost\n");
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["switch", "staging"])
+        .assert()
+        .success();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "API_URL"])
+        .assert()
+        .success()
+        .stdout("https://staging.example.com\n");
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "dev", "API_URL"])
+        .assert()
+        .success()
+        .stdout("http://localhost\n");
+}
+
+#[test]
+fn test_cli_get_all() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[settings]
+default_environment = "dev"
+mask_visible_chars = 4
+
+[environments.dev]
+description = "Development"
+API_URL = "http://localhost"
+TOKEN = "dev-token-123"
+
+[environments.staging]
+description = "Staging"
+API_URL = "https://staging.example.com"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "--all"])
+        .assert()
+        .success()
+        .stdout("API_URL=http://localhost\nTOKEN=dev-token-123\n");
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "--all", "staging"])
+        .assert()
+        .success()
+        .stdout("API_URL=https://staging.example.com\n");
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "--all", "--mask"])
+        .assert()
+        .success()
+        .stdout("API_URL=http********\nTOKEN=dev-********\n");
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["get", "dev", "TOKEN", "--all"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_current_print_path() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\n",
+    )
+    .unwrap();
+    let root = dir.path().canonicalize().unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env_remove("STAND_PROJECT_ROOT")
+        .args(["current", "--print-path"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Project root: {}\n",
+            root.display()
+        )))
+        .stdout(predicate::str::contains(
+            root.join(".stand.toml").display().to_string(),
+        ));
+
+    let empty = tempdir().unwrap();
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(empty.path())
+        .env_remove("STAND_PROJECT_ROOT")
+        .args(["current", "--print-path"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No .stand.toml found"));
+}
+
+#[test]
+fn test_cli_doctor() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env_remove("STAND_ACTIVE")
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Configuration: "))
+        .stdout(predicate::str::contains(
+            "Stand shell: not inside a Stand shell",
+        ));
+
+    let empty = tempdir().unwrap();
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(empty.path())
+        .arg("doctor")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "✗ Configuration: no .stand.toml found",
+        ))
+        .stdout(predicate::str::contains("hint: run 'stand init'"));
+}
+
+#[test]
+fn test_cli_exec_capture_prints_json() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Dev\"\nNAME = \"stand\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    let output = cmd
+        .current_dir(dir.path())
+        .args([
+            "exec",
+            "--capture",
+            "dev",
+            "--",
+            "sh",
+            "-c",
+            "echo \"hi $NAME\"; echo warn >&2; exit 2",
+        ])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["exit_code"], 2);
+    assert_eq!(json["stdout"], "hi stand\n");
+    assert_eq!(json["stderr"], "warn\n");
+}
+
+#[test]
+fn test_cli_validate_json() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"version = "2.0"
+
+[environments.dev]
+description = ""
+
+[environments.prod]
+description = "Production"
+extends = "nonexistent"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    let output = cmd
+        .current_dir(dir.path())
+        .args(["validate", "--json"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let issues: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let codes: Vec<_> = issues
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(codes, vec!["validation_error", "invalid_environment"]);
+    assert_eq!(issues[1]["environment"], "prod");
+    assert_eq!(issues[1]["field"], "extends");
+}
+
+#[test]
+fn test_cli_verbose_traces_config_resolution() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"version = "2.0"
+
+[environments.base]
+description = "Base"
+PORT = "3000"
+
+[environments.dev]
+description = "Development"
+extends = "base"
+DATA_DIR = "${STAND_PROJECT_ROOT}/data"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["-vv", "inspect", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[debug]").not())
+        .stderr(predicate::str::contains(
+            "[verbose] Loaded configuration from ",
+        ))
+        .stderr(predicate::str::contains(
+            "[debug] Inheritance: 'dev' extends 'base'",
+        ))
+        .stderr(predicate::str::contains(
+            "[debug] Interpolated ${STAND_PROJECT_ROOT} (built-in)",
+        ));
+
+    // -v only shows the files that were read
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["-v", "inspect", "dev"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[verbose] Loaded configuration"))
+        .stderr(predicate::str::contains("[debug]").not());
+
+    // Without the flag nothing is traced
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["inspect", "dev"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+}
+
+const ALIAS_CONFIG: &str = r#"
+version = "2.0"
+
+[settings]
+aliases = { development = "dev" }
+
+[environments.base]
+description = "Base"
+SHARED = "1"
+
+[environments.dev]
+description = "Development"
+extends = "base"
+FOO = "old"
+URL = "https://${STAND_CLI_ALIAS_HOST}"
+"#;
+
+fn alias_project() -> tempfile::TempDir {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), ALIAS_CONFIG).unwrap();
+    dir
+}
+
+fn read_config(dir: &tempfile::TempDir) -> toml::Table {
+    fs::read_to_string(dir.path().join(".stand.toml"))
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_cli_set_resolves_alias() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_CLI_ALIAS_HOST", "example.com")
+        .args(["set", "development", "FOO", "bar"])
+        .assert()
+        .success();
+
+    let config = read_config(&dir);
+    assert_eq!(config["environments"]["dev"]["FOO"].as_str(), Some("bar"));
+    assert!(!config["environments"]
+        .as_table()
+        .unwrap()
+        .contains_key("development"));
+}
+
+#[test]
+fn test_cli_set_prints_summary() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_CLI_ALIAS_HOST", "example.com")
+        .args(["set", "dev", "FOO", "bar"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Set FOO in [environments.dev]"));
+}
+
+#[test]
+fn test_cli_set_quiet_suppresses_output() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_CLI_ALIAS_HOST", "example.com")
+        .args(["-q", "set", "dev", "FOO", "bar"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let config = read_config(&dir);
+    assert_eq!(config["environments"]["dev"]["FOO"].as_str(), Some("bar"));
+}
+
+#[test]
+fn test_cli_unset_resolves_alias() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["unset", "development", "FOO"])
+        .assert()
+        .success();
+
+    let config = read_config(&dir);
+    assert!(!config["environments"]["dev"]
+        .as_table()
+        .unwrap()
+        .contains_key("FOO"));
+}
+
+#[test]
+fn test_cli_rename_resolves_alias() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["rename", "development", "local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Renamed environment 'dev' to 'local'",
+        ));
+
+    let config = read_config(&dir);
+    assert_eq!(config["environments"]["local"]["FOO"].as_str(), Some("old"));
+    assert!(!config["environments"]
+        .as_table()
+        .unwrap()
+        .contains_key("dev"));
+}
+
+#[test]
+fn test_cli_copy_resolves_alias() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_CLI_ALIAS_HOST", "example.com")
+        .args(["copy", "development", "review"])
+        .assert()
+        .success();
+
+    let config = read_config(&dir);
+    assert_eq!(
+        config["environments"]["review"]["FOO"].as_str(),
+        Some("old")
+    );
+}
+
+#[test]
+fn test_cli_import_resolves_alias() {
+    let dir = alias_project();
+    fs::write(dir.path().join("extra.env"), "IMPORTED=yes\n").unwrap();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_CLI_ALIAS_HOST", "example.com")
+        .args(["import", "development", "extra.env"])
+        .assert()
+        .success();
+
+    let config = read_config(&dir);
+    assert_eq!(
+        config["environments"]["dev"]["IMPORTED"].as_str(),
+        Some("yes")
+    );
+}
+
+#[test]
+fn test_cli_export_resolves_alias() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_CLI_ALIAS_HOST", "example.com")
+        .args(["export", "development", "out.env"])
+        .assert()
+        .success();
+
+    let exported = fs::read_to_string(dir.path().join("out.env")).unwrap();
+    assert!(exported.contains("FOO=old"), "{}", exported);
+}
+
+#[test]
+fn test_cli_check_resolves_alias() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .env("STAND_CLI_ALIAS_HOST", "example.com")
+        .args(["check", "development"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Environment 'dev' resolves"));
+}
+
+#[test]
+fn test_cli_deps_resolves_alias() {
+    let dir = alias_project();
+
+    let mut cmd = cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["deps", "development"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("STAND_CLI_ALIAS_HOST"));
+}
@@ -0,0 +1,95 @@
+use stand::config::context::ConfigContext;
+use stand::config::loader;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+const CONFIG: &str = r#"
+version = "2.0"
+
+[settings]
+dotenv_cascade = true
+
+[common]
+LOG_LEVEL = "info"
+
+[environments.dev]
+description = "Development"
+API_URL = "http://localhost:3000"
+
+[environments.prod]
+description = "Production"
+"#;
+
+fn write_project(dir: &Path, config: &str) {
+    fs::write(dir.join(".stand.toml"), config).unwrap();
+    fs::write(
+        dir.join(".env"),
+        "SHARED=base\nLEVEL=env\nLOG_LEVEL=debug\nAPI_URL=http://dotenv\n",
+    )
+    .unwrap();
+    fs::write(dir.join(".env.dev"), "LEVEL=env.dev\nDEV_ONLY=yes\n").unwrap();
+    fs::write(dir.join(".env.dev.local"), "LEVEL=env.dev.local\n").unwrap();
+}
+
+#[test]
+fn test_more_specific_dotenv_files_win() {
+    let dir = tempdir().unwrap();
+    write_project(dir.path(), CONFIG);
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+
+    let dev = &config.environments["dev"].variables;
+    assert_eq!(dev["SHARED"], "base");
+    assert_eq!(dev["LEVEL"], "env.dev.local");
+    assert_eq!(dev["DEV_ONLY"], "yes");
+
+    // Missing .env.prod files are skipped; only .env applies
+    let prod = &config.environments["prod"].variables;
+    assert_eq!(prod["LEVEL"], "env");
+    assert!(!prod.contains_key("DEV_ONLY"));
+}
+
+#[test]
+fn test_configured_variables_win_over_dotenv_files() {
+    let dir = tempdir().unwrap();
+    write_project(dir.path(), CONFIG);
+
+    let context = ConfigContext::load(dir.path()).unwrap();
+    let dev = &context.resolved().environments["dev"].variables;
+
+    assert_eq!(dev["API_URL"], "http://localhost:3000");
+    // [common] counts as configured
+    assert_eq!(dev["LOG_LEVEL"], "info");
+}
+
+#[test]
+fn test_dotenv_values_are_not_interpolated() {
+    let dir = tempdir().unwrap();
+    write_project(dir.path(), CONFIG);
+    fs::write(
+        dir.path().join(".env"),
+        "TEMPLATE=${STAND_UNSET_VARIABLE}\n",
+    )
+    .unwrap();
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+
+    assert_eq!(
+        config.environments["dev"].variables["TEMPLATE"],
+        "${STAND_UNSET_VARIABLE}"
+    );
+}
+
+#[test]
+fn test_dotenv_cascade_is_opt_in() {
+    let dir = tempdir().unwrap();
+    write_project(
+        dir.path(),
+        &CONFIG.replace("dotenv_cascade = true", "dotenv_cascade = false"),
+    );
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+
+    assert!(!config.environments["dev"].variables.contains_key("SHARED"));
+}
@@ -1,4 +1,7 @@
-use stand::environment::parser::{parse_env_content, ParseError};
+use stand::environment::parser::{
+    parse_env_content, parse_env_content_with_options, parse_env_content_with_warnings, ParseError,
+    ParseOptions, ParseWarning,
+};
 
 #[test]
 fn test_parse_basic_key_value() {
@@ -53,6 +56,29 @@ KEY3=value3 # Inline comment
     assert_eq!(result.get("KEY3"), Some(&"value3".to_string()));
 }
 
+#[test]
+fn test_parse_hash_only_starts_comment_after_whitespace() {
+    let content = r#"
+FRAGMENT=a#b
+URL=http://example.com/#frag
+COMMENTED=a #comment
+TABBED=a	#comment
+QUOTED="a # b"
+EMPTY=#comment
+"#;
+
+    let result = parse_env_content(content).unwrap();
+
+    assert_eq!(result.get("FRAGMENT"), Some(&"a#b".to_string()));
+    assert_eq!(
+        result.get("URL"),
+        Some(&"http://example.com/#frag".to_string())
+    );
+    assert_eq!(result.get("COMMENTED"), Some(&"a".to_string()));
+    assert_eq!(result.get("QUOTED"), Some(&"a # b".to_string()));
+    assert_eq!(result.get("EMPTY"), Some(&"".to_string()));
+}
+
 #[test]
 fn test_parse_escape_sequences() {
     let content = r#"
@@ -198,3 +224,91 @@ KEY=final_value
 
     assert_eq!(result.get("KEY"), Some(&"final_value".to_string()));
 }
+
+#[test]
+fn test_parse_expands_bare_variables() {
+    let content = r#"
+FOO=/opt/app
+BIN=$FOO/bar
+LIB=${FOO}lib
+PRICE=$5
+"#;
+
+    let result = parse_env_content(content).unwrap();
+
+    assert_eq!(result.get("BIN"), Some(&"/opt/app/bar".to_string()));
+    assert_eq!(result.get("LIB"), Some(&"/opt/applib".to_string()));
+    assert_eq!(result.get("PRICE"), Some(&"$5".to_string()));
+
+    let options = ParseOptions {
+        expand_bare_vars: false,
+        ..ParseOptions::default()
+    };
+    let result = parse_env_content_with_options(content, &options).unwrap();
+    assert_eq!(result.get("BIN"), Some(&"$FOO/bar".to_string()));
+    assert_eq!(result.get("LIB"), Some(&"/opt/applib".to_string()));
+}
+
+#[test]
+fn test_parse_strips_utf8_bom() {
+    let content = "\u{feff}KEY=value\nOTHER=1\n";
+    let result = parse_env_content(content).unwrap();
+
+    assert_eq!(result.get("KEY"), Some(&"value".to_string()));
+    assert_eq!(result.get("OTHER"), Some(&"1".to_string()));
+}
+
+#[test]
+fn test_parse_crlf_line_endings() {
+    let content = "\u{feff}PLAIN=value\r\n\
+                   MULTI=\"line one\r\nline two\"\r\n\
+                   SINGLE='a\r\nb'\r\n\
+                   LAST=end\r";
+    let result = parse_env_content(content).unwrap();
+
+    assert_eq!(result.get("PLAIN"), Some(&"value".to_string()));
+    assert_eq!(result.get("MULTI"), Some(&"line one\nline two".to_string()));
+    assert_eq!(result.get("SINGLE"), Some(&"a\nb".to_string()));
+    assert_eq!(result.get("LAST"), Some(&"end".to_string()));
+    assert!(result.values().all(|value| !value.contains('\r')));
+}
+
+#[test]
+fn test_trailing_whitespace_warning_only_for_unquoted_values() {
+    let content = "TOKEN=abc \n\
+                   QUOTED=\"abc \"\n\
+                   SINGLE='abc '\n\
+                   COMMENTED=abc # note\n\
+                   CLEAN=abc\n\
+                   TABBED=abc\t\n";
+    let options = ParseOptions {
+        warn_trailing_whitespace: true,
+        ..ParseOptions::default()
+    };
+
+    let (result, warnings) = parse_env_content_with_warnings(content, &options).unwrap();
+
+    // Values are still returned as written
+    assert_eq!(result.get("TOKEN"), Some(&"abc ".to_string()));
+    assert_eq!(result.get("QUOTED"), Some(&"abc ".to_string()));
+    assert_eq!(
+        warnings,
+        [
+            ParseWarning {
+                line: 1,
+                key: "TOKEN".to_string()
+            },
+            ParseWarning {
+                line: 6,
+                key: "TABBED".to_string()
+            },
+        ]
+    );
+    assert!(warnings[0]
+        .to_string()
+        .starts_with("Line 1: unquoted value of TOKEN"));
+
+    // Off by default
+    let (_, warnings) = parse_env_content_with_warnings(content, &ParseOptions::default()).unwrap();
+    assert!(warnings.is_empty());
+}
@@ -5,7 +5,8 @@ use std::path::PathBuf;
 use tempfile::TempDir;
 
 use stand::environment::resolver::{
-    EnvironmentResolver, ResolutionOptions, ResolveError, UndefinedVariableBehavior, VariableSource,
+    EnvironmentResolver, ResolutionOptions, ResolveError, SourceKind, UndefinedVariableBehavior,
+    VariableSource,
 };
 
 #[test]
@@ -195,6 +196,7 @@ fn test_resolve_undefined_variable_handling() {
 
     let options = ResolutionOptions {
         undefined_variable_behavior: UndefinedVariableBehavior::EmptyString,
+        ..Default::default()
     };
     let resolved = resolver.resolve_with_options(&options).unwrap();
 
@@ -271,6 +273,34 @@ fn test_resolve_env_file_not_found() {
     }
 }
 
+#[test]
+fn test_resolve_error_identifies_failing_source() {
+    let dir = TempDir::new().unwrap();
+    let env_file = dir.path().join(".env");
+    fs::write(&env_file, "KEY=value\n").unwrap();
+    let missing = dir.path().join(".env.local");
+
+    let mut resolver = EnvironmentResolver::new();
+    resolver.add_source(VariableSource::EnvFile(env_file));
+    resolver.add_source(VariableSource::EnvFile(missing.clone()));
+
+    let error = resolver.resolve().unwrap_err();
+
+    match &error {
+        ResolveError::SourceError {
+            index, description, ..
+        } => {
+            assert_eq!(*index, 2);
+            assert_eq!(*description, format!("EnvFile({})", missing.display()));
+        }
+        _ => panic!("Expected SourceError"),
+    }
+    assert!(error.to_string().starts_with(&format!(
+        "Error loading from source #2 (EnvFile({}))",
+        missing.display()
+    )));
+}
+
 #[test]
 fn test_resolve_preserve_insertion_order() {
     let mut resolver = EnvironmentResolver::new();
@@ -382,6 +412,7 @@ fn test_resolve_with_options_strict_undefined() {
 
     let options = ResolutionOptions {
         undefined_variable_behavior: UndefinedVariableBehavior::Error,
+        ..Default::default()
     };
 
     let result = resolver.resolve_with_options(&options);
@@ -394,3 +425,113 @@ fn test_resolve_with_options_strict_undefined() {
         _ => panic!("Expected UndefinedVariable error"),
     }
 }
+
+#[test]
+fn test_resolve_priority_overrides_insertion_order() {
+    let mut resolver = EnvironmentResolver::new();
+
+    let mut cli_args = IndexMap::new();
+    cli_args.insert("KEY".to_string(), "cli".to_string());
+    resolver.add_source(VariableSource::CliArgs(cli_args));
+
+    let mut defaults = IndexMap::new();
+    defaults.insert("KEY".to_string(), "default".to_string());
+    defaults.insert("DEFAULT_ONLY".to_string(), "yes".to_string());
+    resolver.add_source(VariableSource::Default(defaults));
+
+    // Insertion order: the last source added wins
+    let resolved = resolver.resolve().unwrap();
+    assert_eq!(resolved.get("KEY"), Some(&"default".to_string()));
+
+    let cli_wins = ResolutionOptions {
+        priority: vec![SourceKind::Default, SourceKind::CliArgs],
+        ..Default::default()
+    };
+    let resolved = resolver.resolve_with_options(&cli_wins).unwrap();
+    assert_eq!(resolved.get("KEY"), Some(&"cli".to_string()));
+    assert_eq!(resolved.get("DEFAULT_ONLY"), Some(&"yes".to_string()));
+
+    let default_wins = ResolutionOptions {
+        priority: vec![SourceKind::CliArgs, SourceKind::Default],
+        ..Default::default()
+    };
+    let resolved = resolver.resolve_with_options(&default_wins).unwrap();
+    assert_eq!(resolved.get("KEY"), Some(&"default".to_string()));
+}
+
+#[test]
+fn test_resolve_priority_unlisted_kinds_lose() {
+    let mut resolver = EnvironmentResolver::new();
+
+    let mut defaults = IndexMap::new();
+    defaults.insert("KEY".to_string(), "default".to_string());
+    resolver.add_source(VariableSource::Default(defaults));
+
+    let mut first = IndexMap::new();
+    first.insert("KEY".to_string(), "cli-first".to_string());
+    resolver.add_source(VariableSource::CliArgs(first));
+
+    let mut second = IndexMap::new();
+    second.insert("KEY".to_string(), "cli-second".to_string());
+    resolver.add_source(VariableSource::CliArgs(second));
+
+    // Default is listed, CliArgs is not, so every CliArgs source is merged first
+    let options = ResolutionOptions {
+        priority: vec![SourceKind::Default],
+        ..Default::default()
+    };
+    let resolved = resolver.resolve_with_options(&options).unwrap();
+    assert_eq!(resolved.get("KEY"), Some(&"default".to_string()));
+
+    // Within a kind, insertion order still applies
+    let options = ResolutionOptions {
+        priority: vec![SourceKind::Default, SourceKind::CliArgs],
+        ..Default::default()
+    };
+    let resolved = resolver.resolve_with_options(&options).unwrap();
+    assert_eq!(resolved.get("KEY"), Some(&"cli-second".to_string()));
+}
+
+#[test]
+fn test_resolve_strict_rejects_unexpanded_placeholders() {
+    let mut resolver = EnvironmentResolver::new();
+
+    let mut variables = IndexMap::new();
+    variables.insert("HOST".to_string(), "localhost".to_string());
+    variables.insert("URL".to_string(), "http://${HOST}/${FOO".to_string());
+    variables.insert("LITERAL".to_string(), "$${HOST}".to_string());
+    resolver.add_source(VariableSource::Default(variables));
+
+    // Without strict mode the leftovers are passed through
+    let resolved = resolver.resolve().unwrap();
+    assert_eq!(resolved["URL"], "http://localhost/${FOO");
+    assert_eq!(resolved["LITERAL"], "${HOST}");
+
+    let options = ResolutionOptions {
+        strict: true,
+        ..Default::default()
+    };
+    match resolver.resolve_with_options(&options).unwrap_err() {
+        ResolveError::UnexpandedPlaceholder { variables } => {
+            assert_eq!(variables, ["LITERAL", "URL"]);
+        }
+        other => panic!("Expected UnexpandedPlaceholder error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_resolve_strict_accepts_expanded_values() {
+    let mut resolver = EnvironmentResolver::new();
+
+    let mut variables = IndexMap::new();
+    variables.insert("HOST".to_string(), "localhost".to_string());
+    variables.insert("URL".to_string(), "http://${HOST}/$$5".to_string());
+    resolver.add_source(VariableSource::Default(variables));
+
+    let options = ResolutionOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let resolved = resolver.resolve_with_options(&options).unwrap();
+    assert_eq!(resolved["URL"], "http://localhost/$5");
+}
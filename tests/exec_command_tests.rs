@@ -175,6 +175,46 @@ DATABASE_URL = "postgres://${DB_HOST}:${DB_PORT}/dev"
     std::env::remove_var("DB_PORT");
 }
 
+#[test]
+fn test_exec_no_inherit_passes_only_local_variables() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[common]
+APP_NAME = "MyApp"
+
+[environments.base]
+description = "Base environment"
+PORT = "3000"
+
+[environments.prod]
+description = "Production environment"
+extends = "base"
+DEBUG = "false"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let options = exec::ExecOptions {
+        no_inherit: true,
+        ..Default::default()
+    };
+    let exit_code = exec::execute_with_options(
+        dir.path(),
+        "prod",
+        vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "test \"$DEBUG\" = \"false\" && test -z \"${PORT+x}\" && test -z \"${APP_NAME+x}\""
+                .to_string(),
+        ],
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(exit_code, 0);
+}
+
 #[test]
 fn test_exec_nonexistent_command() {
     let dir = tempdir().unwrap();
@@ -220,6 +260,45 @@ description = "Development environment"
     assert!(error_msg.contains("Command cannot be empty"));
 }
 
+#[test]
+fn test_exec_runs_default_command() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+default_command = "sh -c 'echo \"started $APP_NAME\"'"
+APP_NAME = "my app"
+
+[environments.worker]
+description = "Worker"
+extends = "dev"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+    let options = exec::ExecOptions::default();
+
+    let output = exec::execute_captured_with_options(dir.path(), "dev", vec![], &options).unwrap();
+    assert_eq!(output.exit_code, 0);
+    assert_eq!(output.stdout, "started my app\n");
+
+    // Inherited from the parent
+    let output =
+        exec::execute_captured_with_options(dir.path(), "worker", vec![], &options).unwrap();
+    assert_eq!(output.stdout, "started my app\n");
+
+    // An explicit command wins
+    let output = exec::execute_captured_with_options(
+        dir.path(),
+        "dev",
+        vec!["echo".to_string(), "explicit".to_string()],
+        &options,
+    )
+    .unwrap();
+    assert_eq!(output.stdout, "explicit\n");
+}
+
 #[test]
 fn test_exec_exit_code_propagation() {
     let dir = tempdir().unwrap();
@@ -358,3 +437,807 @@ TEST_VAR = "dev_value"
 
     assert_eq!(exit_code, 0);
 }
+
+#[test]
+#[serial]
+fn test_exec_isolated_environment_hides_parent_variables() {
+    env::set_var("STAND_TEST_PARENT_SECRET", "leaked");
+
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.base]
+description = "Base environment"
+isolated = true
+
+[environments.dev]
+description = "Development environment"
+extends = "base"
+TEST_VAR = "test_value"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    // `isolated` is inherited from base
+    let exit_code = exec::execute_with_environment(
+        dir.path(),
+        "dev",
+        vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "test -z \"$STAND_TEST_PARENT_SECRET\" && test \"$TEST_VAR\" = \"test_value\""
+                .to_string(),
+        ],
+        false,
+    );
+
+    env::remove_var("STAND_TEST_PARENT_SECRET");
+    assert_eq!(exit_code.unwrap(), 0);
+}
+
+#[test]
+#[serial]
+fn test_exec_isolated_option_hides_parent_variables() {
+    env::set_var("STAND_TEST_PARENT_SECRET", "leaked");
+
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+TEST_VAR = "test_value"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "test -z \"$STAND_TEST_PARENT_SECRET\" && test \"$TEST_VAR\" = \"test_value\"".to_string(),
+    ];
+
+    let isolated = exec::execute_with_options(
+        dir.path(),
+        "dev",
+        command.clone(),
+        &exec::ExecOptions {
+            isolated: true,
+            ..Default::default()
+        },
+    );
+    let inherited =
+        exec::execute_with_options(dir.path(), "dev", command, &exec::ExecOptions::default());
+
+    env::remove_var("STAND_TEST_PARENT_SECRET");
+    assert_eq!(isolated.unwrap(), 0);
+    assert_eq!(inherited.unwrap(), 1);
+}
+
+#[test]
+fn test_exec_stdin_env_overrides_configured_values() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_KEY = "from-config"
+KEPT = "config"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args([
+            "exec",
+            "dev",
+            "--stdin-env",
+            "--",
+            "sh",
+            "-c",
+            "printf '%s %s %s' \"$API_KEY\" \"$KEPT\" \"$EXTRA\"",
+        ])
+        .write_stdin("API_KEY=from-stdin\nEXTRA=\"with spaces\"\n")
+        .assert()
+        .success()
+        .stdout("from-stdin config with spaces");
+}
+
+#[test]
+fn test_exec_stdin_env_keeps_configured_values_literal() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+HOST = "db.internal"
+LITERAL = "$${HOST}"
+PRICE = "$$5 $$$$"
+PARTIAL = "a$${b"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args([
+            "exec",
+            "dev",
+            "--stdin-env",
+            "--",
+            "sh",
+            "-c",
+            "printf '%s|%s|%s|%s' \"$LITERAL\" \"$PRICE\" \"$PARTIAL\" \"$URL\"",
+        ])
+        // stdin values may still reference configured variables
+        .write_stdin("URL=postgres://${HOST}/app\n")
+        .assert()
+        .success()
+        .stdout("${HOST}|$5 $$|a${b|postgres://db.internal/app");
+}
+
+#[test]
+#[serial]
+fn test_exec_with_encryption_enabled_but_nothing_encrypted_needs_no_key() {
+    env::remove_var("STAND_PRIVATE_KEY");
+    let dir = tempdir().unwrap();
+    let key_pair = stand::crypto::generate_key_pair();
+    let config_content = format!(
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_URL = "http://localhost"
+
+[encryption]
+public_key = "{}"
+"#,
+        key_pair.public_key
+    );
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+    assert!(!dir.path().join(".stand.keys").exists());
+
+    let exit_code = exec::execute_with_environment(
+        dir.path(),
+        "dev",
+        vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "test \"$API_URL\" = \"http://localhost\"".to_string(),
+        ],
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(exit_code, 0);
+}
+
+#[test]
+fn test_exec_cwd_runs_command_in_directory() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+    fs::create_dir(dir.path().join("service")).unwrap();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args([
+            "exec", "dev", "--cwd", "service", "--", "sh", "-c", "pwd -P",
+        ])
+        .assert()
+        .success()
+        .stdout(format!(
+            "{}\n",
+            fs::canonicalize(dir.path().join("service"))
+                .unwrap()
+                .display()
+        ));
+}
+
+#[test]
+fn test_exec_chdir_to_root_runs_command_in_project_root() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+    let nested = dir.path().join("packages/web");
+    fs::create_dir_all(&nested).unwrap();
+    let out = dir.path().join("pwd.txt");
+
+    let options = exec::ExecOptions {
+        chdir_to_root: true,
+        ..Default::default()
+    };
+    let exit_code = exec::execute_with_options(
+        &nested,
+        "dev",
+        vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("pwd -P > '{}'", out.display()),
+        ],
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(exit_code, 0);
+    assert_eq!(
+        fs::read_to_string(&out).unwrap().trim_end(),
+        fs::canonicalize(dir.path()).unwrap().display().to_string()
+    );
+
+    // The same from the command line
+    assert_cmd::cargo::cargo_bin_cmd!("stand")
+        .current_dir(&nested)
+        .args(["exec", "dev", "--chdir-to-root", "--", "sh", "-c", "pwd -P"])
+        .assert()
+        .success()
+        .stdout(format!(
+            "{}\n",
+            fs::canonicalize(dir.path()).unwrap().display()
+        ));
+}
+
+#[test]
+fn test_exec_cwd_rejects_missing_directory() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let options = exec::ExecOptions {
+        cwd: Some(dir.path().join("missing")),
+        ..Default::default()
+    };
+    let result = exec::execute_with_options(dir.path(), "dev", vec!["true".to_string()], &options);
+
+    assert!(result.unwrap_err().to_string().contains("does not exist"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_forwards_sigterm_to_child() {
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+    let pid_file = dir.path().join("child.pid");
+
+    let mut stand = Command::new(env!("CARGO_BIN_EXE_stand"))
+        .current_dir(dir.path())
+        .args([
+            "exec",
+            "dev",
+            "--",
+            "sh",
+            "-c",
+            "echo $$ > child.pid.tmp && mv child.pid.tmp child.pid && exec sleep 30",
+        ])
+        .stdin(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !pid_file.exists() {
+        assert!(Instant::now() < deadline, "child did not start");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let child_pid: libc::pid_t = fs::read_to_string(&pid_file)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+
+    unsafe {
+        libc::kill(stand.id() as libc::pid_t, libc::SIGTERM);
+    }
+
+    let status = loop {
+        if let Some(status) = stand.try_wait().unwrap() {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = stand.kill();
+            unsafe {
+                libc::kill(child_pid, libc::SIGKILL);
+            }
+            panic!("stand did not exit after SIGTERM");
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    // Stand reports the child's signal as 128 + SIGTERM
+    assert_eq!(status.code(), Some(128 + libc::SIGTERM));
+    // The child has been reaped and no longer exists
+    assert_eq!(unsafe { libc::kill(child_pid, 0) }, -1);
+}
+
+#[test]
+fn test_exec_timeout_kills_command_with_exit_code_124() {
+    use std::time::{Duration, Instant};
+
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let started = Instant::now();
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args(["exec", "dev", "--timeout", "1", "--", "sleep", "5"])
+        .assert()
+        .code(124)
+        .stderr(predicates::str::contains("timed out after 1s"));
+
+    assert!(started.elapsed() < Duration::from_secs(3));
+}
+
+#[test]
+fn test_exec_timeout_not_reached_keeps_exit_code() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let options = exec::ExecOptions {
+        timeout: Some(std::time::Duration::from_secs(10)),
+        ..Default::default()
+    };
+    let command = vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()];
+
+    let result = exec::execute_with_options(dir.path(), "dev", command, &options);
+
+    assert_eq!(result.unwrap(), 3);
+}
+
+#[test]
+fn test_exec_capture_returns_output_and_exit_code() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+GREETING = "hello"
+"#,
+    )
+    .unwrap();
+
+    let output = exec::execute_captured_with_options(
+        dir.path(),
+        "dev",
+        vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo \"$GREETING\"; echo oops >&2; exit 7".to_string(),
+        ],
+        &exec::ExecOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(output.exit_code, 7);
+    assert_eq!(output.stdout, "hello\n");
+    assert_eq!(output.stderr, "oops\n");
+}
+
+#[test]
+#[serial]
+fn test_exec_print_env_to_writes_env_and_runs_command() {
+    env::remove_var("STAND_PRIVATE_KEY");
+    let dir = tempdir().unwrap();
+    let key_pair = stand::crypto::generate_key_pair();
+    stand::crypto::keys::save_private_key(&dir.path().join(".stand.keys"), &key_pair.private_key)
+        .unwrap();
+    let encrypted =
+        stand::crypto::encrypt_value("s3cret", &key_pair.to_recipient().unwrap()).unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        format!(
+            r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_URL = "http://localhost"
+GREETING = "hello world"
+API_TOKEN = "{}"
+
+[encryption]
+public_key = "{}"
+"#,
+            encrypted, key_pair.public_key
+        ),
+    )
+    .unwrap();
+
+    let dump_path = dir.path().join("env.dump");
+    let options = exec::ExecOptions {
+        print_env_to: Some(dump_path.clone()),
+        ..Default::default()
+    };
+    let command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "test \"$API_TOKEN\" = s3cret && exit 7".to_string(),
+    ];
+
+    let result = exec::execute_with_options(dir.path(), "dev", command, &options);
+
+    assert_eq!(result.unwrap(), 7);
+    let dump = fs::read_to_string(&dump_path).unwrap();
+    assert_eq!(
+        dump,
+        "# Resolved Stand environment 'dev'\n\
+         API_TOKEN=********\n\
+         API_URL=http://localhost\n\
+         GREETING=\"hello world\"\n"
+    );
+}
+
+#[test]
+fn test_exec_no_decrypt_passes_encrypted_values() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_TOKEN = "encrypted:abc"
+
+[encryption]
+public_key = "age1placeholder"
+"#,
+    )
+    .unwrap();
+
+    // No private key is available, so decrypting would fail
+    let options = exec::ExecOptions {
+        no_decrypt: true,
+        ..Default::default()
+    };
+    let command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "test \"$API_TOKEN\" = encrypted:abc".to_string(),
+    ];
+
+    let result = exec::execute_with_options(dir.path(), "dev", command, &options);
+
+    assert_eq!(result.unwrap(), 0);
+}
+
+#[test]
+#[serial]
+fn test_exec_shell_runs_pipelines() {
+    let original_shell = env::var("SHELL").ok();
+    env::set_var("SHELL", "/bin/sh");
+
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+NEEDLE = "two"
+"#,
+    )
+    .unwrap();
+    let out = dir.path().join("out.txt");
+    let options = exec::ExecOptions {
+        use_shell: true,
+        ..Default::default()
+    };
+
+    let found = exec::execute_with_options(
+        dir.path(),
+        "dev",
+        vec![
+            "printf '%s\\n' one two three |".to_string(),
+            format!("grep \"$NEEDLE\" > {}", out.display()),
+        ],
+        &options,
+    );
+    let missing = exec::execute_with_options(
+        dir.path(),
+        "dev",
+        vec!["printf 'one\\n' | grep -q two".to_string()],
+        &options,
+    );
+
+    match original_shell {
+        Some(shell) => env::set_var("SHELL", shell),
+        None => env::remove_var("SHELL"),
+    }
+    assert_eq!(found.unwrap(), 0);
+    assert_eq!(fs::read_to_string(&out).unwrap(), "two\n");
+    assert_eq!(missing.unwrap(), 1);
+}
+
+#[test]
+fn test_exec_retry_reruns_until_success() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development environment\"\n",
+    )
+    .unwrap();
+    let counter = dir.path().join("attempts");
+    // Fails on the first two attempts, succeeds on the third
+    let script = format!(
+        "echo x >> '{0}'; [ $(wc -l < '{0}') -ge 3 ]",
+        counter.display()
+    );
+    let command = || vec!["sh".to_string(), "-c".to_string(), script.clone()];
+
+    let options = exec::ExecOptions {
+        retry: 2,
+        ..Default::default()
+    };
+    let result = exec::execute_with_options(dir.path(), "dev", command(), &options);
+    assert_eq!(result.unwrap(), 0);
+    assert_eq!(fs::read_to_string(&counter).unwrap().lines().count(), 3);
+
+    // Out of retries: the last attempt's exit code is returned
+    fs::remove_file(&counter).unwrap();
+    let options = exec::ExecOptions {
+        retry: 1,
+        ..Default::default()
+    };
+    let result = exec::execute_with_options(dir.path(), "dev", command(), &options);
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(fs::read_to_string(&counter).unwrap().lines().count(), 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_retry_skips_signal_terminated_commands() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development environment\"\n",
+    )
+    .unwrap();
+    let counter = dir.path().join("attempts");
+    let script = format!("echo x >> '{}'; kill -KILL $$", counter.display());
+
+    let options = exec::ExecOptions {
+        retry: 3,
+        ..Default::default()
+    };
+    let command = vec!["sh".to_string(), "-c".to_string(), script];
+    let result = exec::execute_with_options(dir.path(), "dev", command, &options);
+
+    assert_eq!(result.unwrap(), 128 + 9);
+    assert_eq!(fs::read_to_string(&counter).unwrap().lines().count(), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_sigterm_during_retry_delay_stops_retrying() {
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development environment\"\n",
+    )
+    .unwrap();
+    let counter = dir.path().join("attempts");
+
+    let mut stand = Command::new(env!("CARGO_BIN_EXE_stand"))
+        .current_dir(dir.path())
+        .args([
+            "exec",
+            "dev",
+            "--retry",
+            "3",
+            "--retry-delay",
+            "30",
+            "--",
+            "sh",
+            "-c",
+            "echo x >> attempts; exit 1",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // The retry notice is printed once the first attempt is over and the delay starts
+    let mut stderr = BufReader::new(stand.stderr.take().unwrap());
+    let mut line = String::new();
+    while !line.contains("retrying in") {
+        line.clear();
+        assert!(stderr.read_line(&mut line).unwrap() > 0, "no retry notice");
+    }
+
+    let started = Instant::now();
+    unsafe {
+        libc::kill(stand.id() as libc::pid_t, libc::SIGTERM);
+    }
+
+    let status = loop {
+        if let Some(status) = stand.try_wait().unwrap() {
+            break status;
+        }
+        if started.elapsed() >= Duration::from_secs(10) {
+            let _ = stand.kill();
+            panic!("stand did not exit after SIGTERM during the retry delay");
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+    assert_eq!(fs::read_to_string(&counter).unwrap().lines().count(), 1);
+}
+
+#[test]
+fn test_exec_retry_reports_attempts_on_stderr() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development environment\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("stand");
+    cmd.current_dir(dir.path())
+        .args([
+            "exec",
+            "dev",
+            "--retry",
+            "1",
+            "--retry-delay",
+            "0",
+            "--",
+            "sh",
+            "-c",
+            "exit 4",
+        ])
+        .assert()
+        .code(4)
+        .stderr(predicates::str::contains(
+            "Attempt 1/2 failed with exit code 4; retrying in 0s",
+        ));
+}
+
+#[test]
+fn test_exec_strict_rejects_leftover_placeholders() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+# `$$` escapes the placeholder, leaving a literal "${FOO" behind
+URL = "http://${STAND_ENV}.example.com/$${FOO"
+"#,
+    )
+    .unwrap();
+    let strict = exec::ExecOptions {
+        strict: true,
+        ..Default::default()
+    };
+
+    let result = exec::execute_with_options(dir.path(), "dev", vec!["true".to_string()], &strict);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Unexpanded '${' left in variables: URL"
+    );
+
+    // Without --strict the value is passed as is
+    let result = exec::execute_with_options(
+        dir.path(),
+        "dev",
+        vec!["true".to_string()],
+        &exec::ExecOptions::default(),
+    );
+    assert_eq!(result.unwrap(), 0);
+}
+
+#[test]
+fn test_exec_strict_accepts_clean_config() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+URL = "http://${STAND_ENV}.example.com/api"
+"#,
+    )
+    .unwrap();
+    let strict = exec::ExecOptions {
+        strict: true,
+        ..Default::default()
+    };
+
+    let result = exec::execute_with_options(
+        dir.path(),
+        "dev",
+        vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "test \"$URL\" = http://dev.example.com/api".to_string(),
+        ],
+        &strict,
+    );
+    assert_eq!(result.unwrap(), 0);
+}
+
+#[test]
+fn test_exec_runs_hidden_environment() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.experiment]
+description = "Experimental environment"
+hidden = true
+FEATURE_FLAG = "on"
+"#,
+    )
+    .unwrap();
+
+    let result = exec::execute_with_environment(
+        dir.path(),
+        "experiment",
+        vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "test \"$FEATURE_FLAG\" = on && test -z \"$hidden\"".to_string(),
+        ],
+        false,
+    );
+
+    assert_eq!(result.unwrap(), 0);
+}
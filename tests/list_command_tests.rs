@@ -125,3 +125,270 @@ requires_confirmation = true
     // Environments requiring confirmation have special display
     assert!(output.contains("(requires confirmation)"));
 }
+
+#[test]
+fn test_list_tree_shows_three_level_chain() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[settings]
+default_environment = "dev"
+
+[environments.base]
+description = "Base environment"
+
+[environments.dev]
+description = "Development environment"
+extends = "base"
+
+[environments.prod]
+description = "Production environment"
+extends = "dev"
+requires_confirmation = true
+
+[environments.standalone]
+description = "Standalone environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let output = list::list_environment_tree(dir.path()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(
+        lines,
+        vec![
+            "Environment hierarchy:",
+            "  base  Base environment",
+            "* └─ dev  Development environment",
+            "     └─ prod  Production environment (requires confirmation)",
+            "  standalone  Standalone environment",
+        ]
+    );
+}
+
+#[test]
+fn test_list_tree_marks_cycles() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.a]
+description = "A"
+extends = "b"
+
+[environments.b]
+description = "B"
+extends = "a"
+
+[environments.root]
+description = "Root"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let output = list::list_environment_tree(dir.path()).unwrap();
+
+    assert!(output.contains("  root  Root"));
+    assert!(output.contains("  a  A"));
+    assert!(output.contains("└─ b  B"));
+    assert!(output.contains("└─ a (cycle)"));
+}
+
+#[test]
+fn test_list_tree_shows_environment_under_each_parent() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.db]
+description = "Database"
+
+[environments.logging]
+description = "Logging"
+
+[environments.app]
+description = "Application"
+extends = ["logging", "db"]
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let output = list::list_environment_tree(dir.path()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(
+        lines,
+        vec![
+            "Environment hierarchy:",
+            "  db  Database",
+            "  └─ app  Application",
+            "  logging  Logging",
+            "  └─ app  Application",
+        ]
+    );
+}
+
+const FILTER_CONFIG: &str = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Local development"
+color = "green"
+
+[environments.staging]
+description = "Pre-release testing"
+color = "yellow"
+
+[environments.staging-eu]
+description = "Pre-release testing in Europe"
+
+[environments.prod]
+description = "Production"
+requires_confirmation = true
+"#;
+
+#[test]
+fn test_list_filter_by_name_fragment() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), FILTER_CONFIG).unwrap();
+
+    let output = list::list_environments_matching(dir.path(), Some("STAG")).unwrap();
+
+    assert!(output.contains("  staging     Pre-release testing [yellow] (0 vars)\n"));
+    assert!(output.contains("  staging-eu     Pre-release testing in Europe (0 vars)\n"));
+    assert!(!output.contains("dev"));
+    assert!(!output.contains("prod"));
+}
+
+#[test]
+fn test_list_filter_by_description_word() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), FILTER_CONFIG).unwrap();
+
+    let output = list::list_environments_matching(dir.path(), Some("europe")).unwrap();
+    assert!(output.contains("staging-eu"));
+    assert!(!output.contains("  staging     "));
+
+    let output = list::list_environments_matching(dir.path(), Some("production")).unwrap();
+    assert!(output.contains("  prod     Production (requires confirmation) (0 vars)\n"));
+    assert_eq!(output.lines().count(), 2);
+}
+
+#[test]
+fn test_list_filter_without_matches() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), FILTER_CONFIG).unwrap();
+
+    let output = list::list_environments_matching(dir.path(), Some("qa")).unwrap();
+
+    assert_eq!(output, "No environments match 'qa'\n");
+}
+
+#[test]
+fn test_list_shows_variable_counts_after_inheritance() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[common]
+APP_NAME = "demo"
+
+[environments.dev]
+description = "Development environment"
+DATABASE_URL = "postgres://localhost:5432/dev"
+DEBUG = "true"
+
+[environments.prod]
+description = "Production environment"
+extends = "dev"
+DEBUG = "false"
+REPLICAS = "3"
+
+[environments.empty]
+description = "Empty environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let output = list::list_environments(dir.path()).unwrap();
+
+    assert!(output.contains("Development environment (3 vars)"));
+    // dev's DATABASE_URL and DEBUG, [common]'s APP_NAME, and REPLICAS
+    assert!(output.contains("Production environment (4 vars)"));
+    assert!(output.contains("Empty environment (1 var)"));
+}
+
+const HIDDEN_CONFIG: &str = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_URL = "http://localhost:3000"
+
+[environments.experiment]
+description = "Experimental environment"
+extends = "dev"
+hidden = true
+FEATURE_FLAG = "on"
+
+[environments.experiment-eu]
+description = "Experimental environment in Europe"
+extends = "experiment"
+"#;
+
+#[test]
+fn test_list_omits_hidden_environments() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), HIDDEN_CONFIG).unwrap();
+
+    let output = list::list_environments(dir.path()).unwrap();
+
+    assert!(output.contains("  dev     "));
+    assert!(!output.contains("  experiment     "));
+    // `hidden` is not inherited, and is not counted as a variable
+    assert!(output.contains("  experiment-eu     Experimental environment in Europe (2 vars)\n"));
+}
+
+#[test]
+fn test_list_all_shows_hidden_environments() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), HIDDEN_CONFIG).unwrap();
+    let options = list::ListOptions {
+        all: true,
+        ..Default::default()
+    };
+
+    let output = list::list_environments_with_options(dir.path(), &options).unwrap();
+
+    assert!(output.contains("  experiment     Experimental environment (2 vars)\n"));
+    assert_eq!(output.lines().count(), 4);
+}
+
+#[test]
+fn test_list_tree_omits_hidden_environments_but_keeps_ancestors() {
+    let dir = tempdir().unwrap();
+    let config_content = format!(
+        "{}\n[environments.scratch]\ndescription = \"Scratch environment\"\nhidden = true\n",
+        HIDDEN_CONFIG
+    );
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let output = list::list_environment_tree(dir.path()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    // experiment is hidden, but experiment-eu extends it
+    assert_eq!(
+        lines,
+        vec![
+            "Environment hierarchy:",
+            "  dev  Development environment",
+            "  └─ experiment  Experimental environment (hidden)",
+            "     └─ experiment-eu  Experimental environment in Europe",
+        ]
+    );
+
+    let options = list::ListOptions {
+        all: true,
+        ..Default::default()
+    };
+    let output = list::list_environment_tree_with_options(dir.path(), &options).unwrap();
+    assert!(output.ends_with("  scratch  Scratch environment (hidden)\n"));
+}
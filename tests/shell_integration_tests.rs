@@ -0,0 +1,175 @@
+//! End-to-end tests that drive a real interactive subshell through a pty
+//!
+//! Each test runs `stand shell` with a specific shell binary and skips itself
+//! when that shell is not installed.
+
+#![cfg(unix)]
+
+use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Upper bound for a whole shell session before the test is considered hung
+const SESSION_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Locate a shell binary on PATH
+fn find_shell(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Write a minimal project with a `dev` environment
+fn write_project(dir: &Path) {
+    fs::write(
+        dir.join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+color = "green"
+MY_VAR = "hello"
+"#,
+    )
+    .unwrap();
+}
+
+/// Run `stand shell dev --shell <shell>` in a pty, type `input`, and return everything
+/// the terminal printed until the shell exits
+fn run_shell_session(shell: &Path, input: &str) -> String {
+    let project = TempDir::new().unwrap();
+    write_project(project.path());
+
+    let pty = native_pty_system()
+        .openpty(PtySize {
+            rows: 24,
+            cols: 200,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .unwrap();
+
+    let mut cmd = CommandBuilder::new(env!("CARGO_BIN_EXE_stand"));
+    cmd.args(["shell", "dev", "--shell"]);
+    cmd.arg(shell);
+    cmd.cwd(project.path());
+    // Keep the user's rc files out of the session
+    cmd.env("HOME", project.path());
+    cmd.env("XDG_CONFIG_HOME", project.path().join(".config"));
+    cmd.env("TERM", "dumb");
+    cmd.env_remove("STAND_ACTIVE");
+    cmd.env_remove("STAND_ENVIRONMENT");
+    cmd.env_remove("STAND_PROJECT_ROOT");
+
+    let mut child = pty.slave.spawn_command(cmd).unwrap();
+    drop(pty.slave);
+
+    let mut reader = pty.master.try_clone_reader().unwrap();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let mut buf = [0u8; 4096];
+        // The read fails with EIO once the shell exits and the pty closes
+        while let Ok(n) = reader.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            output.extend_from_slice(&buf[..n]);
+        }
+        let _ = tx.send(output);
+    });
+
+    let mut writer = pty.master.take_writer().unwrap();
+    writer.write_all(input.as_bytes()).unwrap();
+    writer.flush().unwrap();
+
+    let output = match rx.recv_timeout(SESSION_TIMEOUT) {
+        Ok(output) => output,
+        Err(_) => {
+            let _ = child.kill();
+            panic!("shell session did not finish within {:?}", SESSION_TIMEOUT);
+        }
+    };
+    let status = child.wait().unwrap();
+    assert!(status.success(), "shell exited with {:?}", status);
+
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// Assert that the session saw the Stand markers and produced no shell errors
+fn assert_markers_visible(output: &str) {
+    assert!(
+        output.contains("MARK:dev:1:hello"),
+        "markers not visible in output:\n{}",
+        output
+    );
+    for error in ["command not found", "syntax error", "parse error"] {
+        assert!(
+            !output.contains(error),
+            "shell reported '{}':\n{}",
+            error,
+            output
+        );
+    }
+}
+
+#[test]
+fn test_bash_session_exposes_stand_markers() {
+    let Some(bash) = find_shell("bash") else {
+        eprintln!("skipping: bash not found");
+        return;
+    };
+
+    let output = run_shell_session(
+        &bash,
+        "echo \"MARK:$STAND_ENVIRONMENT:$STAND_ACTIVE:$MY_VAR\"\n\
+         bash -c 'echo \"CHILD:[$PROMPT_COMMAND]\"'\nexit\n",
+    );
+
+    assert_markers_visible(&output);
+    // PROMPT_COMMAND prefixes the prompt with the environment name
+    assert!(
+        output.contains("stand:DEV"),
+        "prompt not customized:\n{}",
+        output
+    );
+    // ...but is not inherited by child processes
+    assert!(output.contains("CHILD:[]"), "hook leaked:\n{}", output);
+}
+
+#[test]
+fn test_zsh_session_exposes_stand_markers() {
+    let Some(zsh) = find_shell("zsh") else {
+        eprintln!("skipping: zsh not found");
+        return;
+    };
+
+    let output = run_shell_session(
+        &zsh,
+        "echo \"MARK:$STAND_ENVIRONMENT:$STAND_ACTIVE:$MY_VAR\"\nexit\n",
+    );
+
+    assert_markers_visible(&output);
+}
+
+#[test]
+fn test_fish_session_exposes_stand_markers() {
+    let Some(fish) = find_shell("fish") else {
+        eprintln!("skipping: fish not found");
+        return;
+    };
+
+    let output = run_shell_session(
+        &fish,
+        "echo \"MARK:$STAND_ENVIRONMENT:$STAND_ACTIVE:$MY_VAR\"\nexit\n",
+    );
+
+    assert_markers_visible(&output);
+}
@@ -232,9 +232,31 @@ LOG_LEVEL = "error"
     assert!(result.contains("Environment: prod"));
     assert!(result.contains("APP_NAME (from common)"));
     assert!(result.contains("PORT (inherited from base)"));
-    assert!(result.contains("LOG_LEVEL"));
-    // LOG_LEVEL should be local (no suffix) since it's overridden in prod
-    assert!(!result.contains("LOG_LEVEL ("));
+    // LOG_LEVEL is defined in prod, shadowing the common value
+    assert!(result.contains("LOG_LEVEL (overrides common)"));
+}
+
+#[test]
+fn test_show_annotates_local_keys_that_override_common() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[common]
+PORT = "3000"
+
+[environments.dev]
+description = "Development environment"
+PORT = "8080"
+DEBUG = "true"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let result = show::show_environment(dir.path(), "dev", true).unwrap();
+
+    assert!(result.contains("PORT=8080 (overrides common)"));
+    assert!(result.contains("DEBUG=true\n"));
 }
 
 #[test]
@@ -272,3 +294,92 @@ BETA = "second"
     assert!(var_lines[1].contains("BETA"));
     assert!(var_lines[2].contains("ZEBRA"));
 }
+
+#[test]
+fn test_show_masked_values_honor_mask_visible_chars() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[settings]
+mask_visible_chars = 3
+
+[environments.dev]
+description = "Development environment"
+API_KEY = "sk-live-abcdef"
+PIN = "12"
+SECRET = "encrypted:abc"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let options = show::ShowOptions {
+        mask: true,
+        ..show::ShowOptions::default()
+    };
+    let result = show::show_environment_with_options(dir.path(), "dev", &options).unwrap();
+
+    assert!(result.contains("API_KEY=sk-********"));
+    assert!(!result.contains("abcdef"));
+    // Values shorter than the visible prefix are fully masked
+    assert!(result.contains("PIN=********"));
+    assert!(result.contains("SECRET=[ENCRYPTED]"));
+}
+
+#[test]
+fn test_show_masked_values_default_hides_everything() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_KEY = "sk-live-abcdef"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let options = show::ShowOptions {
+        mask: true,
+        ..show::ShowOptions::default()
+    };
+    let result = show::show_environment_with_options(dir.path(), "dev", &options).unwrap();
+
+    assert!(result.contains("API_KEY=********"));
+}
+
+#[test]
+fn test_show_values_respects_auto_decrypt() {
+    let dir = tempdir().unwrap();
+    let config = |auto_decrypt: &str| {
+        format!(
+            r#"
+version = "2.0"
+
+[settings]
+{}
+
+[environments.dev]
+description = "Development environment"
+SECRET = "encrypted:abc"
+"#,
+            auto_decrypt
+        )
+    };
+    let options = show::ShowOptions {
+        show_values: true,
+        ..show::ShowOptions::default()
+    };
+
+    fs::write(dir.path().join(".stand.toml"), config("")).unwrap();
+    let result = show::show_environment_with_options(dir.path(), "dev", &options).unwrap();
+    assert!(result.contains("SECRET=[ENCRYPTED]"));
+
+    fs::write(
+        dir.path().join(".stand.toml"),
+        config("auto_decrypt = false"),
+    )
+    .unwrap();
+    let result = show::show_environment_with_options(dir.path(), "dev", &options).unwrap();
+    assert!(result.contains("SECRET=encrypted:abc"));
+}
@@ -73,7 +73,7 @@ DEBUG = "false"
         assert_eq!(prod_env.description, "Production environment");
         assert_eq!(prod_env.color, Some("red".to_string()));
         assert_eq!(prod_env.requires_confirmation, Some(true));
-        assert_eq!(prod_env.extends, Some("dev".to_string()));
+        assert_eq!(prod_env.parents(), ["dev"]);
         assert_eq!(
             prod_env.variables.get("DATABASE_URL"),
             Some(&"postgres://prod.example.com/myapp".to_string())
@@ -89,6 +89,16 @@ DEBUG = "false"
                 nested_shell_behavior: Some(NestedBehavior::Prevent),
                 show_env_in_prompt: Some(true),
                 auto_exit_on_dir_change: None,
+                quiet: None,
+                default_environment: None,
+                environment_from: None,
+                mask_visible_chars: None,
+                fuzzy_env_match: None,
+                shell: None,
+                auto_decrypt: None,
+                dotenv_cascade: None,
+                aliases: None,
+                shell_cwd: None,
             },
             common: Some({
                 let mut map = HashMap::new();
@@ -96,6 +106,8 @@ DEBUG = "false"
                 map
             }),
             environments: HashMap::new(),
+            encryption: None,
+            include: Vec::new(),
         };
 
         let mut dev_env = Environment {
@@ -104,6 +116,9 @@ DEBUG = "false"
             variables: HashMap::new(),
             color: Some("green".to_string()),
             requires_confirmation: Some(false),
+            isolated: None,
+            default_command: None,
+            hidden: None,
         };
         dev_env
             .variables
@@ -0,0 +1,150 @@
+use stand::config::loader;
+use stand::config::ConfigError;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+const BASE: &str = r#"
+version = "2.0"
+include = ["envs/dev.toml", "envs/prod.toml"]
+
+[common]
+APP_NAME = "demo"
+LOG_LEVEL = "info"
+
+[environments.staging]
+description = "Staging"
+extends = "prod"
+API_URL = "https://staging.example.com"
+"#;
+
+const DEV: &str = r#"
+[common]
+LOG_LEVEL = "debug"
+DEV_ONLY = "yes"
+
+[environments.dev]
+description = "Development"
+API_URL = "http://localhost:3000"
+"#;
+
+const PROD: &str = r#"
+version = "2.0"
+
+[environments.prod]
+description = "Production"
+API_URL = "https://api.example.com"
+REPLICAS = "3"
+"#;
+
+fn write(dir: &Path, path: &str, content: &str) {
+    let path = dir.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_included_files_contribute_environments() {
+    let dir = tempdir().unwrap();
+    write(dir.path(), ".stand.toml", BASE);
+    write(dir.path(), "envs/dev.toml", DEV);
+    write(dir.path(), "envs/prod.toml", PROD);
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+
+    let mut names: Vec<_> = config.environments.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(names, ["dev", "prod", "staging"]);
+    assert_eq!(config.version, "2.0");
+    assert!(config.include.is_empty());
+
+    // The including file wins over included ones; included values fill the rest
+    let common = config.common.as_ref().unwrap();
+    assert_eq!(common["LOG_LEVEL"], "info");
+    assert_eq!(common["DEV_ONLY"], "yes");
+
+    // Inheritance works across files
+    let staging = &config.environments["staging"].variables;
+    assert_eq!(staging["REPLICAS"], "3");
+    assert_eq!(staging["API_URL"], "https://staging.example.com");
+    assert_eq!(
+        config.environments["dev"].variables["API_URL"],
+        "http://localhost:3000"
+    );
+}
+
+#[test]
+fn test_nested_includes_are_relative_to_project_root() {
+    let dir = tempdir().unwrap();
+    write(
+        dir.path(),
+        ".stand.toml",
+        "version = \"2.0\"\ninclude = [\"envs/all.toml\"]\n\n[environments.base]\ndescription = \"Base\"\n",
+    );
+    write(
+        dir.path(),
+        "envs/all.toml",
+        "include = [\"envs/dev.toml\"]\n\n[environments.base]\nPORT = \"3000\"\n",
+    );
+    write(dir.path(), "envs/dev.toml", DEV);
+
+    let config = loader::load_config_toml(dir.path()).unwrap();
+
+    assert!(config.environments.contains_key("dev"));
+    assert_eq!(config.environments["base"].description, "Base");
+    assert_eq!(config.environments["base"].variables["PORT"], "3000");
+}
+
+#[test]
+fn test_missing_include_is_an_error() {
+    let dir = tempdir().unwrap();
+    write(dir.path(), ".stand.toml", BASE);
+    write(dir.path(), "envs/dev.toml", DEV);
+
+    let err = loader::load_config_toml(dir.path()).unwrap_err();
+
+    match err {
+        ConfigError::FileNotFound {
+            configured_path, ..
+        } => assert_eq!(configured_path, "envs/prod.toml"),
+        other => panic!("expected FileNotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_include_cycle_is_an_error() {
+    let dir = tempdir().unwrap();
+    write(
+        dir.path(),
+        ".stand.toml",
+        "version = \"2.0\"\ninclude = [\"a.toml\"]\n\n[environments.dev]\ndescription = \"Dev\"\n",
+    );
+    write(dir.path(), "a.toml", "include = [\"b.toml\"]\n");
+    write(dir.path(), "b.toml", "include = [\".stand.toml\"]\n");
+
+    let err = loader::load_config_toml(dir.path()).unwrap_err();
+
+    assert_eq!(err.code(), "include_cycle");
+    assert_eq!(
+        err.to_string(),
+        "Include cycle detected: .stand.toml -> a.toml -> b.toml -> .stand.toml"
+    );
+}
+
+#[test]
+fn test_invalid_included_file_names_the_file() {
+    let dir = tempdir().unwrap();
+    write(dir.path(), ".stand.toml", BASE);
+    write(dir.path(), "envs/dev.toml", DEV);
+    write(
+        dir.path(),
+        "envs/prod.toml",
+        "[environments.prod]\nREPLICAS = \"3\"\n",
+    );
+
+    let err = loader::load_config_toml(dir.path()).unwrap_err();
+
+    assert!(err
+        .to_string()
+        .contains("Failed to load included file 'envs/prod.toml'"));
+}
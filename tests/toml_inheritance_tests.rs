@@ -132,3 +132,144 @@ DEBUG = "false"
         Err(e) => panic!("Failed to load TOML config with inheritance chain: {}", e),
     }
 }
+
+#[test]
+fn test_multiple_parents_merge_left_to_right() {
+    let dir = tempdir().unwrap();
+
+    let toml_content = r#"
+version = "2.0"
+
+[environments.logging]
+description = "Logging defaults"
+LOG_LEVEL = "info"
+TIMEOUT = "10"
+
+[environments.db]
+description = "Database defaults"
+DATABASE_URL = "postgres://localhost:5432/app"
+TIMEOUT = "30"
+color = "blue"
+
+[environments.app]
+description = "Application"
+extends = ["logging", "db"]
+LOG_LEVEL = "debug"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), toml_content).unwrap();
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+    let app = &config.environments["app"];
+    assert_eq!(app.parents(), ["logging", "db"]);
+    // Later parents override earlier ones
+    assert_eq!(app.variables["TIMEOUT"], "30");
+    assert_eq!(
+        app.variables["DATABASE_URL"],
+        "postgres://localhost:5432/app"
+    );
+    // The child overrides every parent
+    assert_eq!(app.variables["LOG_LEVEL"], "debug");
+    assert_eq!(app.color.as_deref(), Some("blue"));
+}
+
+#[test]
+fn test_diamond_inheritance() {
+    let dir = tempdir().unwrap();
+
+    let toml_content = r#"
+version = "2.0"
+
+[environments.base]
+description = "Base"
+REGION = "eu"
+NAME = "base"
+
+[environments.left]
+description = "Left"
+extends = "base"
+NAME = "left"
+
+[environments.right]
+description = "Right"
+extends = "base"
+PORT = "8080"
+
+[environments.app]
+description = "Application"
+extends = ["left", "right"]
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), toml_content).unwrap();
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+    let app = &config.environments["app"];
+    assert_eq!(app.variables["REGION"], "eu");
+    assert_eq!(app.variables["PORT"], "8080");
+    // `right` carries base's NAME and is merged after `left`
+    assert_eq!(app.variables["NAME"], "base");
+}
+
+#[test]
+fn test_diamond_inheritance_cycle_detection() {
+    let dir = tempdir().unwrap();
+
+    let toml_content = r#"
+version = "2.0"
+
+[environments.base]
+description = "Base"
+extends = "app"
+
+[environments.left]
+description = "Left"
+extends = "base"
+
+[environments.right]
+description = "Right"
+extends = "base"
+
+[environments.app]
+description = "Application"
+extends = ["left", "right"]
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), toml_content).unwrap();
+
+    let error = loader::load_config_toml_with_inheritance(dir.path()).unwrap_err();
+    assert!(error.to_string().contains("Circular reference"));
+
+    let error = loader::load_config_toml_with_validation(dir.path()).unwrap_err();
+    assert!(error.to_string().contains("Circular reference"));
+}
+
+#[test]
+fn test_variable_count_includes_inherited_variables() {
+    let dir = tempdir().unwrap();
+    let toml_content = r#"
+version = "2.0"
+
+[common]
+APP_NAME = "MyApp"
+
+[environments.dev]
+description = "Development environment"
+DATABASE_URL = "postgres://localhost:5432/dev"
+DEBUG = "true"
+
+[environments.prod]
+description = "Production environment"
+extends = "dev"
+DEBUG = "false"
+"#;
+    fs::write(dir.path().join(".stand.toml"), toml_content).unwrap();
+
+    // Without inheritance only the variables written in each section count
+    let config = loader::load_config_toml(dir.path()).unwrap();
+    assert_eq!(config.environments["dev"].variable_count(), 2);
+    assert_eq!(config.environments["prod"].variable_count(), 1);
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+    assert_eq!(config.environments["dev"].variable_count(), 3);
+    assert_eq!(config.environments["prod"].variable_count(), 3);
+}
@@ -1,5 +1,6 @@
 use serial_test::serial;
 use stand::config::loader;
+use stand::config::ConfigError;
 use std::fs;
 use tempfile::tempdir;
 
@@ -150,3 +151,143 @@ DEBUG = "true"
     // Clean up environment variables
     std::env::remove_var("APP_PREFIX");
 }
+
+#[test]
+#[serial]
+fn test_interpolation_dollar_escapes() {
+    let dir = tempdir().unwrap();
+    std::env::set_var("ESCAPE_TEST_HOST", "db.example.com");
+
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development"
+TEMPLATE = "$${ESCAPE_TEST_HOST}"
+PRICE = "$$5"
+MIXED = "${ESCAPE_TEST_HOST} $${ESCAPE_TEST_HOST} $$"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let result = loader::load_config_toml_with_validation(dir.path());
+    std::env::remove_var("ESCAPE_TEST_HOST");
+
+    let config = result.unwrap();
+    let vars = &config.environments["dev"].variables;
+    assert_eq!(vars["TEMPLATE"], "${ESCAPE_TEST_HOST}");
+    assert_eq!(vars["PRICE"], "$5");
+    assert_eq!(vars["MIXED"], "db.example.com ${ESCAPE_TEST_HOST} $");
+}
+
+#[test]
+#[serial]
+fn test_interpolation_cycle_across_common_and_environment() {
+    let dir = tempdir().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[common]
+BASE_URL = "https://${API_HOST}"
+
+[environments.dev]
+description = "Development"
+API_HOST = "${BASE_URL}/api"
+PATH = "/opt/dev/bin:${PATH}"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    // Detected even when process variables of the same names would satisfy
+    // interpolation, e.g. inside a Stand shell started from this configuration
+    std::env::set_var("BASE_URL", "https://example.com");
+    std::env::set_var("API_HOST", "example.com");
+    let result = loader::load_config_toml_with_validation(dir.path());
+    std::env::remove_var("BASE_URL");
+    std::env::remove_var("API_HOST");
+
+    match result.unwrap_err() {
+        ConfigError::CircularReference { cycle } => {
+            assert_eq!(cycle, ["API_HOST", "BASE_URL", "API_HOST"]);
+        }
+        other => panic!("expected CircularReference, got {:?}", other),
+    }
+}
+
+#[test]
+#[serial]
+fn test_interpolation_stand_pseudo_variables() {
+    let dir = tempdir().unwrap();
+    // The environment being loaded wins over a process variable of the same name
+    std::env::set_var("STAND_ENV", "from-process");
+
+    let config_content = r#"
+version = "2.0"
+
+[common]
+LOG_PREFIX = "${STAND_ENV}-app"
+
+[environments.base]
+description = "Base for ${STAND_ENV}"
+CONFIG_PATH = "${STAND_PROJECT_ROOT}/config/${STAND_ENV}.yml"
+ESCAPED = "$${STAND_ENV}"
+
+[environments.dev]
+description = "Development"
+extends = "base"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let result = loader::load_config_toml_with_validation(dir.path());
+    std::env::remove_var("STAND_ENV");
+
+    let config = result.unwrap();
+    let root = dir.path().display().to_string();
+    let base = &config.environments["base"];
+    assert_eq!(base.description, "Base for base");
+    assert_eq!(base.variables["LOG_PREFIX"], "base-app");
+    assert_eq!(
+        base.variables["CONFIG_PATH"],
+        format!("{}/config/base.yml", root)
+    );
+    assert_eq!(base.variables["ESCAPED"], "${STAND_ENV}");
+
+    // Inherited and common values expand to the inheriting environment
+    let dev = &config.environments["dev"];
+    assert_eq!(dev.variables["LOG_PREFIX"], "dev-app");
+    assert_eq!(
+        dev.variables["CONFIG_PATH"],
+        format!("{}/config/dev.yml", root)
+    );
+}
+
+#[test]
+fn test_interpolation_stand_pseudo_variables_can_be_overridden() {
+    let dir = tempdir().unwrap();
+
+    let config_content = r#"
+version = "2.0"
+
+[common]
+STAND_PROJECT_ROOT = "/srv/app"
+
+[environments.dev]
+description = "Development"
+STAND_ENV = "development"
+LOG_PREFIX = "${STAND_ENV}-app"
+DATA_DIR = "${STAND_PROJECT_ROOT}/data"
+
+[environments.prod]
+description = "Production"
+LOG_PREFIX = "${STAND_ENV}-app"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+
+    let dev = &config.environments["dev"].variables;
+    assert_eq!(dev["LOG_PREFIX"], "development-app");
+    assert_eq!(dev["DATA_DIR"], "/srv/app/data");
+    assert_eq!(
+        config.environments["prod"].variables["LOG_PREFIX"],
+        "prod-app"
+    );
+}
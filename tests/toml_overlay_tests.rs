@@ -0,0 +1,173 @@
+use stand::config::loader;
+use stand::config::types::Configuration;
+use std::fs;
+use tempfile::tempdir;
+
+const BASE: &str = r#"
+version = "2.0"
+
+[settings]
+default_environment = "dev"
+quiet = true
+
+[common]
+APP_NAME = "demo"
+LOG_LEVEL = "info"
+
+[environments.dev]
+description = "Development"
+color = "green"
+DATABASE_URL = "postgres://localhost/dev"
+API_URL = "http://localhost:3000"
+
+[environments.prod]
+description = "Production"
+requires_confirmation = true
+API_URL = "https://api.example.com"
+"#;
+
+const OVERLAY: &str = r#"
+[settings]
+default_environment = "sandbox"
+
+[common]
+LOG_LEVEL = "debug"
+
+[environments.dev]
+DATABASE_URL = "postgres://localhost:5433/dev"
+
+[environments.sandbox]
+description = "Personal sandbox"
+extends = "dev"
+"#;
+
+#[test]
+fn test_merge_overrides_and_adds() {
+    let mut base: Configuration = toml::from_str(BASE).unwrap();
+    let overlay: Configuration = toml::from_str(
+        r#"
+version = "2.0"
+
+[settings]
+default_environment = "sandbox"
+
+[environments.dev]
+description = "Local development"
+DEBUG = "true"
+API_URL = "http://localhost:4000"
+
+[environments.sandbox]
+description = "Personal sandbox"
+"#,
+    )
+    .unwrap();
+
+    base.merge(overlay);
+
+    let dev = &base.environments["dev"];
+    assert_eq!(dev.description, "Local development");
+    assert_eq!(dev.color.as_deref(), Some("green"));
+    assert_eq!(dev.variables["DATABASE_URL"], "postgres://localhost/dev");
+    assert_eq!(dev.variables["API_URL"], "http://localhost:4000");
+    assert_eq!(dev.variables["DEBUG"], "true");
+    assert!(base.environments.contains_key("sandbox"));
+    assert_eq!(base.environments["prod"].requires_confirmation, Some(true));
+    assert_eq!(
+        base.settings.default_environment.as_deref(),
+        Some("sandbox")
+    );
+    assert_eq!(base.settings.quiet, Some(true));
+}
+
+#[test]
+fn test_load_with_overlay() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), BASE).unwrap();
+    let overlay_path = dir.path().join(".stand.local.toml");
+    fs::write(&overlay_path, OVERLAY).unwrap();
+
+    let config = loader::load_config_toml_with_overlay(dir.path(), &overlay_path).unwrap();
+
+    let dev = &config.environments["dev"];
+    assert_eq!(dev.description, "Development");
+    assert_eq!(
+        dev.variables["DATABASE_URL"],
+        "postgres://localhost:5433/dev"
+    );
+    assert_eq!(dev.variables["API_URL"], "http://localhost:3000");
+    assert_eq!(config.environments["sandbox"].parents(), ["dev"]);
+
+    let common = config.common.as_ref().unwrap();
+    assert_eq!(common["LOG_LEVEL"], "debug");
+    assert_eq!(common["APP_NAME"], "demo");
+    assert_eq!(
+        config.settings.default_environment.as_deref(),
+        Some("sandbox")
+    );
+    assert_eq!(config.settings.quiet, Some(true));
+}
+
+#[test]
+fn test_load_with_missing_overlay_is_base() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), BASE).unwrap();
+
+    let config =
+        loader::load_config_toml_with_overlay(dir.path(), &dir.path().join(".stand.local.toml"))
+            .unwrap();
+
+    assert_eq!(config.environments.len(), 2);
+    assert_eq!(
+        config.environments["dev"].variables["DATABASE_URL"],
+        "postgres://localhost/dev"
+    );
+}
+
+#[test]
+fn test_overlay_new_environment_requires_description() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), BASE).unwrap();
+    let overlay_path = dir.path().join(".stand.local.toml");
+    fs::write(&overlay_path, "[environments.sandbox]\nKEY = \"value\"\n").unwrap();
+
+    let result = loader::load_config_toml_with_overlay(dir.path(), &overlay_path);
+
+    assert!(result.unwrap_err().to_string().contains("description"));
+}
+
+#[test]
+fn test_load_config_toml_applies_local_overlay() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), BASE).unwrap();
+    fs::write(dir.path().join(".stand.local.toml"), OVERLAY).unwrap();
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+
+    // Inheritance runs after the merge, so sandbox sees the local DATABASE_URL
+    let sandbox = &config.environments["sandbox"];
+    assert_eq!(
+        sandbox.variables["DATABASE_URL"],
+        "postgres://localhost:5433/dev"
+    );
+    assert_eq!(sandbox.variables["LOG_LEVEL"], "debug");
+    assert_eq!(
+        config.settings.default_environment.as_deref(),
+        Some("sandbox")
+    );
+}
+
+#[test]
+fn test_load_config_toml_without_local_file() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), BASE).unwrap();
+
+    let config = loader::load_config_toml(dir.path()).unwrap();
+    let base: Configuration = toml::from_str(BASE).unwrap();
+
+    assert_eq!(config.environments.len(), base.environments.len());
+    assert_eq!(
+        config.environments["dev"].variables,
+        base.environments["dev"].variables
+    );
+    assert_eq!(config.settings.default_environment.as_deref(), Some("dev"));
+}
@@ -51,7 +51,7 @@ DEBUG = "false"
 
             // Check prod environment
             let prod_env = config.environments.get("prod").unwrap();
-            assert_eq!(prod_env.extends, Some("dev".to_string()));
+            assert_eq!(prod_env.parents(), ["dev"]);
             assert_eq!(prod_env.variables.get("DEBUG").unwrap(), "false");
         }
         Err(e) => panic!("Failed to load TOML config: {}", e),
@@ -65,3 +65,47 @@ fn test_load_toml_missing_file() {
     let result = loader::load_config_toml(dir.path());
     assert!(result.is_err());
 }
+
+#[test]
+fn test_load_toml_empty_file_suggests_init() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), "# nothing yet\n").unwrap();
+
+    let err = loader::load_config_toml(dir.path()).unwrap_err();
+
+    assert_eq!(err.code(), "validation_error");
+    assert_eq!(
+        err.to_string(),
+        "Configuration validation failed: .stand.toml is empty. \
+         Run 'stand init --force' to regenerate the template."
+    );
+}
+
+#[test]
+fn test_load_toml_without_version_or_environments_suggests_init() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join(".stand.toml");
+
+    fs::write(&config_path, "version = \"2.0\"\n").unwrap();
+    let message = loader::load_config_toml(dir.path())
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains(".stand.toml defines no environments"));
+    assert!(message.contains("stand init --force"));
+
+    fs::write(
+        &config_path,
+        "[environments.dev]\ndescription = \"Development\"\n",
+    )
+    .unwrap();
+    let message = loader::load_config_toml(dir.path())
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains(".stand.toml has no 'version'"));
+    assert!(message.contains("stand init --force"));
+
+    // Other errors keep the parser's message
+    fs::write(&config_path, "version = [\n").unwrap();
+    let err = loader::load_config_toml(dir.path()).unwrap_err();
+    assert_eq!(err.code(), "toml_error");
+}
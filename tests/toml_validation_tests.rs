@@ -122,3 +122,204 @@ DATABASE_URL = "postgres://localhost/dev"
     let result = loader::load_config_toml_with_validation(dir.path());
     assert!(result.is_err());
 }
+
+#[test]
+fn test_validation_fails_for_encrypted_values_without_encryption_section() {
+    let dir = TempDir::new().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[common]
+SHARED_TOKEN = "encrypted:abc"
+
+[environments.dev]
+description = "Development environment"
+API_KEY = "encrypted:def"
+DEBUG = "true"
+
+[environments.prod]
+description = "Production environment"
+extends = "dev"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let result = loader::load_config_toml_with_validation(dir.path());
+    assert!(result.is_err());
+
+    let message = result.unwrap_err().to_string();
+    assert!(message.contains("[encryption]"));
+    assert!(message.contains("common.SHARED_TOKEN, dev.API_KEY"));
+    // Inherited copies are not reported
+    assert!(!message.contains("prod.API_KEY"));
+}
+
+#[test]
+fn test_validation_accepts_encrypted_values_with_encryption_section() {
+    let dir = TempDir::new().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[encryption]
+public_key = "age1test"
+
+[environments.dev]
+description = "Development environment"
+API_KEY = "encrypted:def"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let result = loader::load_config_toml_with_validation(dir.path());
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().encryption.unwrap().public_key, "age1test");
+}
+
+#[test]
+fn test_validation_accepts_plain_values_without_encryption_section() {
+    let dir = TempDir::new().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[environments.dev]
+description = "Development environment"
+API_KEY = "plain"
+"#;
+
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let result = loader::load_config_toml_with_validation(dir.path());
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_warns_when_default_environment_requires_confirmation() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[settings]
+default_environment = "prod"
+
+[environments.dev]
+description = "Development"
+
+[environments.prod]
+description = "Production"
+requires_confirmation = true
+"#,
+    )
+    .unwrap();
+
+    let (_, warnings) = loader::load_config_toml_with_warnings(dir.path()).unwrap();
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("default_environment 'prod'"));
+    assert!(warnings[0].message.contains("requires confirmation"));
+}
+
+#[test]
+fn test_no_warning_when_default_environment_needs_no_confirmation() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[settings]
+default_environment = "dev"
+
+[environments.dev]
+description = "Development"
+
+[environments.prod]
+description = "Production"
+requires_confirmation = true
+"#,
+    )
+    .unwrap();
+
+    let (_, warnings) = loader::load_config_toml_with_warnings(dir.path()).unwrap();
+
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_warns_about_common_variable_overridden_everywhere() {
+    let dir = TempDir::new().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[common]
+APP_NAME = "TestApp"
+DATABASE_URL = "postgres://localhost/default"
+
+[environments.dev]
+description = "Development environment"
+DATABASE_URL = "postgres://localhost/dev"
+
+[environments.base]
+description = "Base environment"
+DATABASE_URL = "postgres://localhost/base"
+
+[environments.prod]
+description = "Production environment"
+extends = "base"
+DATABASE_URL = "postgres://localhost/prod"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let (_, warnings) = loader::load_config_toml_with_warnings(dir.path()).unwrap();
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].code, "unused_common_variable");
+    assert!(warnings[0].message.contains("'DATABASE_URL'"));
+}
+
+#[test]
+fn test_no_warning_for_common_variable_interpolating_per_environment() {
+    let dir = TempDir::new().unwrap();
+    // The value differs in every environment after interpolation, but comes from [common]
+    let config_content = r#"
+version = "2.0"
+
+[common]
+APP = "app-${STAND_ENV}"
+
+[environments.dev]
+description = "Development environment"
+
+[environments.prod]
+description = "Production environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let (_, warnings) = loader::load_config_toml_with_warnings(dir.path()).unwrap();
+
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn test_no_warning_for_common_variable_used_as_is() {
+    let dir = TempDir::new().unwrap();
+    let config_content = r#"
+version = "2.0"
+
+[common]
+DATABASE_URL = "postgres://localhost/default"
+
+[environments.dev]
+description = "Development environment"
+DATABASE_URL = "postgres://localhost/dev"
+
+[environments.test]
+description = "Test environment"
+"#;
+    fs::write(dir.path().join(".stand.toml"), config_content).unwrap();
+
+    let (_, warnings) = loader::load_config_toml_with_warnings(dir.path()).unwrap();
+
+    assert!(warnings.is_empty());
+}
@@ -0,0 +1,92 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn config(greeting: &str) -> String {
+    format!(
+        "version = \"2.0\"\n\n[environments.dev]\ndescription = \"Development\"\nGREETING = \"{}\"\n",
+        greeting
+    )
+}
+
+/// Lines of `path`, waiting until there are `count` of them
+fn wait_for_lines(path: &Path, count: usize, stand: &mut Child) -> Vec<String> {
+    let deadline = Instant::now() + TIMEOUT;
+    loop {
+        let lines: Vec<String> = fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect();
+        if lines.len() >= count {
+            return lines;
+        }
+        if Instant::now() >= deadline {
+            let _ = stand.kill();
+            panic!("expected {} run(s), got {:?}", count, lines);
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[test]
+fn test_watch_restarts_command_on_config_change() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.toml"), config("one")).unwrap();
+    let runs = dir.path().join("runs.txt");
+
+    let mut stand = Command::new(env!("CARGO_BIN_EXE_stand"))
+        .current_dir(dir.path())
+        .args([
+            "watch",
+            "dev",
+            "--",
+            "sh",
+            "-c",
+            "echo \"$$ $GREETING\" >> runs.txt && exec sleep 30",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let lines = wait_for_lines(&runs, 1, &mut stand);
+    assert!(lines[0].ends_with(" one"));
+
+    fs::write(dir.path().join(".stand.toml"), config("two")).unwrap();
+    let lines = wait_for_lines(&runs, 2, &mut stand);
+    assert!(lines[1].ends_with(" two"), "{:?}", lines);
+
+    // The command writing into the project directory does not restart it
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(fs::read_to_string(&runs).unwrap().lines().count(), 2);
+
+    unsafe {
+        libc::kill(stand.id() as libc::pid_t, libc::SIGTERM);
+    }
+    let deadline = Instant::now() + TIMEOUT;
+    let status = loop {
+        if let Some(status) = stand.try_wait().unwrap() {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = stand.kill();
+            panic!("stand did not exit after SIGTERM");
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    assert_eq!(status.code(), Some(0));
+
+    // Both runs have been stopped and reaped
+    for line in &lines {
+        let pid: libc::pid_t = line.split(' ').next().unwrap().parse().unwrap();
+        assert_eq!(unsafe { libc::kill(pid, 0) }, -1, "run {} still alive", pid);
+    }
+}
@@ -0,0 +1,233 @@
+use serial_test::serial;
+use stand::config::loader;
+use stand::config::ConfigError;
+use std::env;
+use std::fs;
+use tempfile::tempdir;
+
+const YAML_CONFIG: &str = r#"
+version: "2.0"
+
+settings:
+  default_environment: dev
+
+common:
+  APP_NAME: MyApp
+  LOG_LEVEL: info
+
+environments:
+  dev:
+    description: Development environment
+    DATABASE_URL: postgres://localhost:5432/dev
+    DEBUG: "true"
+  staging:
+    description: Staging environment
+    extends: dev
+    color: yellow
+    DATABASE_URL: postgres://staging.example.com/myapp
+  prod:
+    description: Production environment
+    extends: staging
+    requires_confirmation: true
+    DEBUG: "false"
+"#;
+
+#[test]
+fn test_yaml_variable_inheritance() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.yaml"), YAML_CONFIG).unwrap();
+
+    let config = loader::load_config_yaml_with_inheritance(dir.path()).unwrap();
+
+    assert_eq!(config.settings.default_environment.as_deref(), Some("dev"));
+
+    let dev = &config.environments["dev"];
+    assert_eq!(dev.variables["APP_NAME"], "MyApp");
+    assert_eq!(dev.variables["LOG_LEVEL"], "info");
+    assert_eq!(dev.variables["DEBUG"], "true");
+
+    // prod: common + dev + staging + its own variables
+    let prod = &config.environments["prod"];
+    assert_eq!(prod.variables["APP_NAME"], "MyApp");
+    assert_eq!(
+        prod.variables["DATABASE_URL"],
+        "postgres://staging.example.com/myapp"
+    );
+    assert_eq!(prod.variables["DEBUG"], "false");
+    assert_eq!(prod.color.as_deref(), Some("yellow"));
+    assert_eq!(prod.requires_confirmation, Some(true));
+}
+
+#[test]
+fn test_yaml_matches_equivalent_toml() {
+    let toml_dir = tempdir().unwrap();
+    fs::write(
+        toml_dir.path().join(".stand.toml"),
+        r#"
+version = "2.0"
+
+[common]
+APP_NAME = "MyApp"
+
+[environments.dev]
+description = "Development environment"
+DEBUG = "true"
+
+[environments.prod]
+description = "Production environment"
+extends = "dev"
+DEBUG = "false"
+"#,
+    )
+    .unwrap();
+    let yaml_dir = tempdir().unwrap();
+    fs::write(
+        yaml_dir.path().join(".stand.yaml"),
+        r#"
+version: "2.0"
+common:
+  APP_NAME: MyApp
+environments:
+  dev:
+    description: Development environment
+    DEBUG: "true"
+  prod:
+    description: Production environment
+    extends: dev
+    DEBUG: "false"
+"#,
+    )
+    .unwrap();
+
+    let from_toml = loader::load_config_toml_with_inheritance(toml_dir.path()).unwrap();
+    let from_yaml = loader::load_config_yaml_with_inheritance(yaml_dir.path()).unwrap();
+
+    for name in ["dev", "prod"] {
+        assert_eq!(
+            from_toml.environments[name].variables,
+            from_yaml.environments[name].variables
+        );
+    }
+}
+
+#[test]
+fn test_yaml_circular_inheritance_detection() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.yaml"),
+        r#"
+version: "2.0"
+environments:
+  dev:
+    description: Development
+    extends: prod
+  prod:
+    description: Production
+    extends: dev
+"#,
+    )
+    .unwrap();
+
+    let result = loader::load_config_yaml_with_inheritance(dir.path());
+
+    assert!(matches!(result, Err(ConfigError::CircularReference { .. })));
+}
+
+#[test]
+#[serial]
+fn test_yaml_interpolation() {
+    env::set_var("STAND_YAML_TEST_HOST", "db.internal");
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.yaml"),
+        r#"
+version: "2.0"
+common:
+  HOST: ${STAND_YAML_TEST_HOST}
+environments:
+  dev:
+    description: Development
+    DATABASE_URL: postgres://${STAND_YAML_TEST_HOST}:5432/dev
+    PRICE: $$5
+"#,
+    )
+    .unwrap();
+
+    let result = loader::load_config_yaml_with_inheritance(dir.path());
+
+    env::remove_var("STAND_YAML_TEST_HOST");
+    let config = result.unwrap();
+    let dev = &config.environments["dev"];
+    assert_eq!(dev.variables["HOST"], "db.internal");
+    assert_eq!(
+        dev.variables["DATABASE_URL"],
+        "postgres://db.internal:5432/dev"
+    );
+    assert_eq!(dev.variables["PRICE"], "$5");
+}
+
+#[test]
+#[serial]
+fn test_yaml_interpolation_missing_variable() {
+    env::remove_var("STAND_YAML_TEST_MISSING");
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(".stand.yaml"),
+        r#"
+version: "2.0"
+environments:
+  dev:
+    description: Development
+    TOKEN: ${STAND_YAML_TEST_MISSING}
+"#,
+    )
+    .unwrap();
+
+    let result = loader::load_config_yaml_with_inheritance(dir.path());
+
+    assert!(matches!(
+        result,
+        Err(ConfigError::InterpolationError { variable }) if variable == "STAND_YAML_TEST_MISSING"
+    ));
+}
+
+#[test]
+fn test_loader_auto_detects_yaml() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.yaml"), YAML_CONFIG).unwrap();
+
+    let config = loader::load_config_toml_with_inheritance(dir.path()).unwrap();
+
+    assert_eq!(config.environments.len(), 3);
+    assert_eq!(config.environments["prod"].variables["APP_NAME"], "MyApp");
+}
+
+#[test]
+fn test_loader_prefers_toml_over_yaml() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.yaml"), YAML_CONFIG).unwrap();
+    fs::write(
+        dir.path().join(".stand.toml"),
+        "version = \"2.0\"\n\n[environments.only_toml]\ndescription = \"TOML\"\n",
+    )
+    .unwrap();
+
+    let config = loader::load_config_toml(dir.path()).unwrap();
+
+    assert_eq!(config.environments.len(), 1);
+    assert!(config.environments.contains_key("only_toml"));
+}
+
+#[test]
+fn test_check_reads_yaml_project() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".stand.yaml"), YAML_CONFIG).unwrap();
+
+    let reports = stand::commands::check::check_all_environments(dir.path()).unwrap();
+
+    let names: Vec<_> = reports.iter().map(|r| r.env_name.as_str()).collect();
+    assert_eq!(names, vec!["dev", "prod", "staging"]);
+    assert!(reports.iter().all(|r| r.is_ok()));
+    // prod: APP_NAME, LOG_LEVEL, DATABASE_URL and DEBUG
+    assert_eq!(reports[1].variable_count, 4);
+}